@@ -0,0 +1,101 @@
+use async_periodic_job::{Job, Scheduler, TimerMode};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const JOBS: [usize; 3] = [1_000, 10_000, 100_000];
+const MODES: [TimerMode; 3] = [TimerMode::PerJob, TimerMode::Shared, TimerMode::Auto];
+const WINDOW: Duration = Duration::from_secs(2);
+
+struct Tick {
+    name: String,
+    period: Duration,
+}
+
+impl Job for Tick {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn period(&self) -> Duration {
+        self.period
+    }
+
+    async fn run(&mut self) {}
+}
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .unwrap()
+}
+
+async fn schedule(mode: TimerMode, jobs: usize) {
+    let scheduler = Scheduler::new().timer_mode(mode);
+    for index in 0..jobs {
+        scheduler.spawn_with_handle(Tick {
+            name: format!("job-{index}"),
+            period: Duration::from_millis(100 * (1 + index as u64 % 10)),
+        });
+    }
+    tokio::time::sleep(WINDOW).await;
+    scheduler.stop().await;
+}
+
+fn memory(mode: TimerMode, jobs: usize) {
+    let runtime = runtime();
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    runtime.block_on(schedule(mode, jobs));
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    eprintln!(
+        "timers/{mode:?}/{jobs}: peak {:.1} MiB, {} bytes per job",
+        peak as f64 / (1024.0 * 1024.0),
+        peak / jobs
+    );
+}
+
+fn timers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timers");
+    group.sample_size(10);
+    for jobs in JOBS {
+        for mode in MODES {
+            memory(mode, jobs);
+            let runtime = runtime();
+            group.bench_with_input(
+                BenchmarkId::new(format!("{mode:?}"), jobs),
+                &jobs,
+                |b, &jobs| b.iter(|| runtime.block_on(schedule(mode, jobs))),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, timers);
+criterion_main!(benches);
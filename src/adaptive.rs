@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+const SMOOTHING: f64 = 0.3;
+const MAX_PERIOD: Duration = Duration::from_secs(365 * 86_400);
+
+pub(crate) struct Adaptive {
+    duty_cycle: Option<f64>,
+    average: Option<f64>,
+}
+
+impl Adaptive {
+    pub(crate) fn new(duty_cycle: Option<f64>) -> Self {
+        Self {
+            duty_cycle: duty_cycle.filter(|duty| *duty > 0.0 && *duty <= 1.0),
+            average: None,
+        }
+    }
+
+    pub(crate) fn record(&mut self, duration: Duration) {
+        if self.duty_cycle.is_none() {
+            return;
+        }
+        let duration = duration.as_secs_f64();
+        self.average = Some(match self.average {
+            Some(average) => average + SMOOTHING * (duration - average),
+            None => duration,
+        });
+    }
+
+    pub(crate) fn period(&self, period: Duration, truncate: bool) -> Duration {
+        let (Some(duty_cycle), Some(average)) = (self.duty_cycle, self.average) else {
+            return period;
+        };
+        let required = average / duty_cycle;
+        let base = period.as_secs_f64();
+        if required <= base || base == 0.0 {
+            return period;
+        }
+        if truncate {
+            let most = (MAX_PERIOD.as_secs_f64() / base).floor().max(1.0);
+            let multiple = (required / base).ceil().min(most) as u32;
+            period.checked_mul(multiple).unwrap_or(MAX_PERIOD)
+        } else {
+            Duration::try_from_secs_f64(required)
+                .unwrap_or(MAX_PERIOD)
+                .min(MAX_PERIOD)
+        }
+    }
+}
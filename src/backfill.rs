@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Notify, oneshot};
+
+const NANO: Duration = Duration::from_nanos(1);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    pub ticks: u64,
+    pub runs: u64,
+    pub failures: u64,
+}
+
+struct Request {
+    from: SystemTime,
+    to: SystemTime,
+    done: oneshot::Sender<BackfillReport>,
+}
+
+pub(crate) struct Requests {
+    queue: Mutex<Option<VecDeque<Request>>>,
+    limit: usize,
+    notify: Notify,
+}
+
+impl Requests {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            queue: Mutex::new(Some(VecDeque::new())),
+            limit,
+            notify: Notify::new(),
+        }
+    }
+
+    pub(crate) fn push(
+        &self,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> oneshot::Receiver<BackfillReport> {
+        let (done, receiver) = oneshot::channel();
+        if let Some(queue) = &mut *self.queue.lock().unwrap()
+            && queue.len() < self.limit
+        {
+            queue.push_back(Request { from, to, done });
+            self.notify.notify_one();
+        }
+        receiver
+    }
+
+    pub(crate) fn close(&self) {
+        self.queue.lock().unwrap().take();
+    }
+
+    pub(crate) async fn requested(&self) {
+        self.notify.notified().await
+    }
+}
+
+struct Current {
+    request: Request,
+    last: Option<SystemTime>,
+    repeat: bool,
+    report: BackfillReport,
+}
+
+#[derive(Default)]
+pub(crate) struct Backfill(Option<Current>);
+
+impl Backfill {
+    pub(crate) fn next(
+        &mut self,
+        requests: &Requests,
+        fire_after: impl Fn(SystemTime, SystemTime) -> Option<SystemTime>,
+    ) -> Option<SystemTime> {
+        loop {
+            let current = match &mut self.0 {
+                Some(current) => current,
+                None => {
+                    let request = requests.queue.lock().unwrap().as_mut()?.pop_front()?;
+                    self.0.insert(Current {
+                        request,
+                        last: None,
+                        repeat: false,
+                        report: BackfillReport::default(),
+                    })
+                }
+            };
+            if let Some(last) = current.last.filter(|_| current.repeat) {
+                current.repeat = false;
+                return Some(last);
+            }
+            let after = current.last.unwrap_or(current.request.from - NANO);
+            match fire_after(current.request.from, after).filter(|tick| *tick < current.request.to)
+            {
+                Some(tick) => {
+                    current.last = Some(tick);
+                    current.report.ticks += 1;
+                    return Some(tick);
+                }
+                None => {
+                    let current = self.0.take().unwrap();
+                    let _ = current.request.done.send(current.report);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn retry(&mut self) {
+        if let Some(current) = &mut self.0 {
+            current.repeat = true;
+        }
+    }
+
+    pub(crate) fn record(&mut self, ok: bool) {
+        if let Some(current) = &mut self.0 {
+            current.report.runs += 1;
+            if !ok {
+                current.report.failures += 1;
+            }
+        }
+    }
+}
+
+pub(crate) fn anchored(
+    anchor: SystemTime,
+    period: Duration,
+    after: SystemTime,
+) -> Option<SystemTime> {
+    let period = period.as_nanos() as i128;
+    if period == 0 {
+        return None;
+    }
+    let elapsed = match after.duration_since(anchor) {
+        Ok(elapsed) => elapsed.as_nanos() as i128,
+        Err(error) => -(error.duration().as_nanos() as i128),
+    };
+    let ticks = elapsed.div_euclid(period) + 1;
+    let offset = ticks * period;
+    match offset >= 0 {
+        true => anchor.checked_add(Duration::from_nanos(offset as u64)),
+        false => anchor.checked_sub(Duration::from_nanos(-offset as u64)),
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Budget {
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    pub(crate) limit: Duration,
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    pub(crate) window: Duration,
+}
+
+impl Budget {
+    pub fn new(limit: Duration, window: Duration) -> Self {
+        Self { limit, window }
+    }
+}
+
+pub(crate) struct Spending {
+    budget: Option<Budget>,
+    started: Instant,
+    used: Duration,
+}
+
+impl Spending {
+    pub(crate) fn new(budget: Option<Budget>) -> Self {
+        Self {
+            budget,
+            started: Instant::now(),
+            used: Duration::ZERO,
+        }
+    }
+
+    pub(crate) fn exhausted(&mut self) -> bool {
+        let Some(budget) = self.budget else {
+            return false;
+        };
+        if self.started.elapsed() >= budget.window {
+            self.started = Instant::now();
+            self.used = Duration::ZERO;
+        }
+        self.used >= budget.limit
+    }
+
+    pub(crate) fn spend(&mut self, duration: Duration) -> Option<Duration> {
+        let budget = self.budget?;
+        let exhausted = self.used >= budget.limit;
+        self.used += duration;
+        (!exhausted && self.used >= budget.limit).then_some(self.used)
+    }
+}
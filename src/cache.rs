@@ -0,0 +1,189 @@
+use crate::{
+    AlertRule, Budget, Completion, DstPolicy, Job, Labels, Layer, LoadPolicy, LogLevel, Misfire,
+    OutputJob, PeriodLimit, Quarantine, RunContext, Sampling, Schedule, Supervisor, SuspendPolicy,
+    TriggerPolicy, TruncateZone,
+};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached<T> {
+    pub value: T,
+    pub tick: SystemTime,
+    pub updated: SystemTime,
+}
+
+impl<T> Cached<T> {
+    pub fn age(&self) -> Duration {
+        self.updated.elapsed().unwrap_or_default()
+    }
+
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+pub struct CachedJob<J: OutputJob> {
+    job: J,
+    sender: watch::Sender<Option<Cached<J::Output>>>,
+}
+
+impl<J: OutputJob> CachedJob<J> {
+    pub fn new(job: J) -> Self {
+        Self {
+            job,
+            sender: watch::Sender::new(None),
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Option<Cached<J::Output>>> {
+        self.sender.subscribe()
+    }
+
+    pub fn inner(&self) -> &J {
+        &self.job
+    }
+
+    pub fn inner_mut(&mut self) -> &mut J {
+        &mut self.job
+    }
+}
+
+impl<J: OutputJob> Job for CachedJob<J> {
+    fn name(&self) -> &str {
+        self.job.name()
+    }
+
+    fn period(&self) -> Duration {
+        self.job.period()
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.job.with_truncate_time()
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        self.job.truncate_zone()
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        self.job.dst_policy()
+    }
+
+    async fn run_with_context(&mut self, ctx: RunContext) {
+        let tick = ctx.tick();
+        self.job.run_with_context(ctx).await;
+        if let Some(value) = self.job.output() {
+            self.sender.send_replace(Some(Cached {
+                value,
+                tick,
+                updated: SystemTime::now(),
+            }));
+        }
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.job.supervisor()
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        self.job.layers()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        self.job.exclusion_group()
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        self.job.shard_key()
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        self.job.alert_rule()
+    }
+
+    fn critical(&self) -> bool {
+        self.job.critical()
+    }
+
+    fn labels(&self) -> Labels {
+        self.job.labels()
+    }
+
+    fn enabled(&self) -> bool {
+        self.job.enabled()
+    }
+
+    fn abortable(&self) -> bool {
+        self.job.abortable()
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        self.job.trigger_policy()
+    }
+
+    fn log_level(&self) -> LogLevel {
+        self.job.log_level()
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        self.job.schedule()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.job.dry_run()
+    }
+
+    fn stagger(&self) -> bool {
+        self.job.stagger()
+    }
+
+    fn finish_by(&self) -> bool {
+        self.job.finish_by()
+    }
+
+    fn sampling(&self) -> Sampling {
+        self.job.sampling()
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        self.job.max_duty_cycle()
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        self.job.period_limit()
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        self.job.quarantine()
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        self.job.budget()
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        self.job.completion()
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        self.job.on_suspend()
+    }
+
+    fn misfire(&self) -> Misfire {
+        self.job.misfire()
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        self.job.load_policy()
+    }
+
+    fn durable(&self) -> bool {
+        self.job.durable()
+    }
+
+    fn dedup(&self) -> bool {
+        self.job.dedup()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::cron::{DayOfMonth, DayOfWeek, Fields, ParseCronError, mask, values};
+use crate::schedule::zone_name;
+use crate::{Schedule, TruncateZone};
+use chrono::Weekday;
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    expression: String,
+    zone: TruncateZone,
+    fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCalendarError(String);
+
+impl fmt::Display for ParseCalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid calendar expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseCalendarError {}
+
+impl From<ParseCronError> for ParseCalendarError {
+    fn from(error: ParseCronError) -> Self {
+        Self(error.0)
+    }
+}
+
+impl Calendar {
+    pub fn parse(expression: &str) -> Result<Self, ParseCalendarError> {
+        let trimmed = expression.trim();
+        let mut tokens: Vec<&str> = match expand(trimmed) {
+            Some(expanded) => expanded.split_whitespace().collect(),
+            None => trimmed.split_whitespace().collect(),
+        };
+        if tokens.is_empty() {
+            return Err(ParseCalendarError("empty expression".into()));
+        }
+        let mut zone = TruncateZone::Local;
+        if tokens.len() > 1
+            && let Some(last) = tokens.last()
+            && let Some(parsed) = time_zone(last)?
+        {
+            zone = parsed;
+            tokens.pop();
+        }
+        let mut tokens = tokens.into_iter().peekable();
+        let day_of_week = match tokens.peek() {
+            Some(token) if token.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                Some(weekdays(tokens.next().unwrap())?)
+            }
+            _ => None,
+        };
+        let date = tokens.next_if(|token| !token.contains(':'));
+        let time = tokens.next();
+        if let Some(extra) = tokens.next() {
+            return Err(ParseCalendarError(format!("unexpected `{extra}`")));
+        }
+        if date.is_none() && time.is_none() && day_of_week.is_none() {
+            return Err(ParseCalendarError(format!(
+                "`{trimmed}` has no date or time"
+            )));
+        }
+        let (years, months, day_of_month) = match date {
+            Some(date) => parse_date(date)?,
+            None => (None, u64::MAX, None),
+        };
+        let (hours, minutes, seconds) = match time {
+            Some(time) => parse_time(time)?,
+            None => (1, 1, 1),
+        };
+        Ok(Self {
+            expression: trimmed.to_string(),
+            zone,
+            fields: Fields {
+                seconds,
+                minutes,
+                hours,
+                months,
+                day_of_month,
+                day_of_week,
+                years,
+                either_day: false,
+            },
+        })
+    }
+
+    pub fn zone(mut self, zone: TruncateZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl FromStr for Calendar {
+    type Err = ParseCalendarError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl Schedule for Calendar {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        self.fields.next_after(self.zone, after)
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.zone
+    }
+
+    fn describe(&self) -> String {
+        format!("{}, in {}", self.fields.describe(), zone_name(self.zone))
+    }
+}
+
+fn expand(expression: &str) -> Option<&'static str> {
+    let expanded = match expression.to_ascii_lowercase().as_str() {
+        "minutely" => "*-*-* *:*:00",
+        "hourly" => "*-*-* *:00:00",
+        "daily" => "*-*-* 00:00:00",
+        "weekly" => "Mon *-*-* 00:00:00",
+        "monthly" => "*-*-01 00:00:00",
+        "quarterly" => "*-01,04,07,10-01 00:00:00",
+        "semiannually" => "*-01,07-01 00:00:00",
+        "yearly" | "annually" => "*-01-01 00:00:00",
+        _ => return None,
+    };
+    Some(expanded)
+}
+
+fn time_zone(token: &str) -> Result<Option<TruncateZone>, ParseCalendarError> {
+    if token.eq_ignore_ascii_case("utc") {
+        return Ok(Some(TruncateZone::Utc));
+    }
+    if !token.starts_with(|c: char| c.is_ascii_uppercase()) || token.contains(':') {
+        return Ok(None);
+    }
+    #[cfg(feature = "tz")]
+    {
+        token
+            .parse()
+            .map(|tz| Some(TruncateZone::Tz(tz)))
+            .map_err(|_| ParseCalendarError(format!("unknown time zone `{token}`")))
+    }
+    #[cfg(not(feature = "tz"))]
+    Err(ParseCalendarError(format!(
+        "time zone `{token}` requires the `tz` feature"
+    )))
+}
+
+fn weekdays(token: &str) -> Result<DayOfWeek, ParseCalendarError> {
+    let day = |name: &str| {
+        let prefix = name.to_ascii_lowercase();
+        WEEKDAYS
+            .into_iter()
+            .find(|day| prefix.len() >= 3 && weekday_name(*day).starts_with(&prefix))
+            .ok_or_else(|| ParseCalendarError(format!("unknown weekday `{name}` in `{token}`")))
+    };
+    let mut days = 0;
+    for part in token.split(',') {
+        let (start, end) = match part.split_once("..") {
+            Some((start, end)) => (day(start)?, day(end)?),
+            None => (day(part)?, day(part)?),
+        };
+        let (start, end) = (start.num_days_from_monday(), end.num_days_from_monday());
+        if start > end {
+            return Err(ParseCalendarError(format!(
+                "weekday range `{part}` is reversed"
+            )));
+        }
+        days |= (start..=end).fold(0, |days, day| days | 1 << day);
+    }
+    Ok(DayOfWeek::Days(days))
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+type Date = (Option<Vec<u32>>, u64, Option<DayOfMonth>);
+
+fn parse_date(token: &str) -> Result<Date, ParseCalendarError> {
+    let (rest, last) = match token.split_once('~') {
+        Some((rest, day)) => (rest, Some(day)),
+        None => (token, None),
+    };
+    let parts: Vec<&str> = rest.split('-').collect();
+    let (year, month, day) = match (parts.as_slice(), last) {
+        ([month], Some(day)) => ("*", *month, day),
+        ([year, month], Some(day)) => (*year, *month, day),
+        ([month, day], None) => ("*", *month, *day),
+        ([year, month, day], None) => (*year, *month, *day),
+        _ => {
+            return Err(ParseCalendarError(format!(
+                "date `{token}` must look like `[year-]month-day`"
+            )));
+        }
+    };
+    let years = match year {
+        "*" => None,
+        year => Some(values("year", &range(year), 1970, 2199, &[], 0)?),
+    };
+    let months = mask(values("month", &range(month), 1, 12, &[], 0)?);
+    let day_of_month = match (day, last.is_some()) {
+        ("*", false) => None,
+        (day, false) => Some(DayOfMonth::Days(mask(values(
+            "day",
+            &range(day),
+            1,
+            31,
+            &[],
+            0,
+        )?))),
+        (day, true) => match day.parse::<u32>() {
+            Ok(before @ 1..=31) => Some(DayOfMonth::Last(before - 1)),
+            _ => {
+                return Err(ParseCalendarError(format!(
+                    "`~{day}` must count 1 to 31 days from the end of the month"
+                )));
+            }
+        },
+    };
+    Ok((years, months, day_of_month))
+}
+
+fn parse_time(token: &str) -> Result<(u64, u64, u64), ParseCalendarError> {
+    let parts: Vec<&str> = token.split(':').collect();
+    let (hour, minute, second) = match parts.as_slice() {
+        [hour, minute] => (*hour, *minute, "00"),
+        [hour, minute, second] => (*hour, *minute, *second),
+        _ => {
+            return Err(ParseCalendarError(format!(
+                "time `{token}` must look like `hour:minute[:second]`"
+            )));
+        }
+    };
+    Ok((
+        mask(values("hour", &range(hour), 0, 23, &[], 0)?),
+        mask(values("minute", &range(minute), 0, 59, &[], 0)?),
+        mask(values("second", &range(second), 0, 59, &[], 0)?),
+    ))
+}
+
+fn range(component: &str) -> String {
+    component.replace("..", "-")
+}
@@ -0,0 +1,97 @@
+use crate::{Layer, Next, Run};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::select;
+use tokio::time::sleep;
+
+#[derive(Clone)]
+pub struct Chaos {
+    delay: f64,
+    max_delay: Duration,
+    fail: f64,
+    panic: f64,
+    cancel: f64,
+    state: Arc<Mutex<u64>>,
+}
+
+impl Chaos {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            delay: 0.0,
+            max_delay: Duration::ZERO,
+            fail: 0.0,
+            panic: 0.0,
+            cancel: 0.0,
+            state: Arc::new(Mutex::new(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)),
+        }
+    }
+
+    pub fn delay(mut self, probability: f64, max: Duration) -> Self {
+        self.delay = probability;
+        self.max_delay = max;
+        self
+    }
+
+    pub fn fail(mut self, probability: f64) -> Self {
+        self.fail = probability;
+        self
+    }
+
+    pub fn panic(mut self, probability: f64) -> Self {
+        self.panic = probability;
+        self
+    }
+
+    pub fn cancel(mut self, probability: f64) -> Self {
+        self.cancel = probability;
+        self
+    }
+
+    fn random(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        (*state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn roll(&self, probability: f64) -> bool {
+        probability > 0.0 && self.random() < probability
+    }
+}
+
+impl Layer for Chaos {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let ctx = next.context();
+            let token = ctx.token();
+            if self.roll(self.delay) {
+                let delay = self.max_delay.mul_f64(self.random());
+                injected(ctx.name(), "delay");
+                select! {
+                    biased;
+                    _ = token.cancelled() => return ctx.skip(),
+                    _ = sleep(delay) => {},
+                }
+            }
+            if self.roll(self.panic) {
+                injected(ctx.name(), "panic");
+                panic!("chaos: injected panic");
+            }
+            if self.roll(self.fail) {
+                injected(ctx.name(), "failure");
+                return ctx.fail("chaos: injected failure");
+            }
+            if self.roll(self.cancel) {
+                injected(ctx.name(), "cancellation");
+                token.cancel();
+            }
+            next.run().await
+        })
+    }
+}
+
+fn injected(_job: &str, _fault: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(job = _job, fault = _fault, "chaos: injecting fault");
+}
@@ -0,0 +1,67 @@
+use crate::Store;
+use std::io;
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+pub trait StatefulJob: crate::Job {
+    type State: serde::Serialize + serde::de::DeserializeOwned + Send;
+
+    fn snapshot(&self) -> Self::State;
+
+    fn restore(&mut self, state: Self::State);
+}
+
+pub(crate) struct Checkpoint<J> {
+    store: Arc<dyn Store>,
+    key: String,
+    snapshot: fn(&J) -> io::Result<Vec<u8>>,
+    restore: fn(&mut J, &[u8]) -> io::Result<()>,
+}
+
+impl<J> Checkpoint<J> {
+    #[cfg(feature = "serde")]
+    pub(crate) fn new(store: Arc<dyn Store>, name: &str) -> Self
+    where
+        J: StatefulJob,
+    {
+        Self {
+            store,
+            key: format!("state/{name}"),
+            snapshot: |job| Ok(serde_json::to_vec(&job.snapshot())?),
+            restore: |job, bytes| {
+                job.restore(serde_json::from_slice(bytes)?);
+                Ok(())
+            },
+        }
+    }
+
+    pub(crate) async fn restore(&self, job: &mut J) {
+        let result = match self.store.load(&self.key).await {
+            Ok(Some(bytes)) => (self.restore)(job, &bytes),
+            Ok(None) => Ok(()),
+            Err(error) => Err(error),
+        };
+        #[cfg(feature = "tracing")]
+        if let Err(error) = result {
+            tracing::warn!(key = self.key, %error, "failed to restore job state");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+
+    pub(crate) fn save(&self, job: &J) -> impl Future<Output = ()> + Send + '_ {
+        let snapshot = (self.snapshot)(job);
+        async move {
+            let result = match snapshot {
+                Ok(bytes) => self.store.save(&self.key, bytes).await,
+                Err(error) => Err(error),
+            };
+            #[cfg(feature = "tracing")]
+            if let Err(error) = result {
+                tracing::warn!(key = self.key, %error, "failed to checkpoint job state");
+            }
+            #[cfg(not(feature = "tracing"))]
+            let _ = result;
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::SystemTime;
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) enum Clock {
+    #[default]
+    System,
+    #[cfg_attr(not(feature = "simulation"), allow(dead_code))]
+    Virtual { start: SystemTime, origin: Instant },
+}
+
+impl Clock {
+    pub(crate) fn now(&self) -> SystemTime {
+        match self {
+            Self::System => SystemTime::now(),
+            Self::Virtual { start, origin } => *start + origin.elapsed(),
+        }
+    }
+}
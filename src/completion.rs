@@ -0,0 +1,30 @@
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Completion {
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    pub(crate) within: Duration,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) skip_late: bool,
+}
+
+impl Completion {
+    pub fn within(within: Duration) -> Self {
+        Self {
+            within,
+            skip_late: false,
+        }
+    }
+
+    pub fn skip_late(mut self) -> Self {
+        self.skip_late = true;
+        self
+    }
+
+    pub(crate) fn late(&self, tick: SystemTime, at: SystemTime) -> Option<Duration> {
+        at.duration_since(tick + self.within)
+            .ok()
+            .filter(|late| !late.is_zero())
+    }
+}
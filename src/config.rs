@@ -0,0 +1,196 @@
+use crate::status::JobShared;
+use crate::{Error, Job, JobHandle, JobOptions, Scheduler, StopReport, Token, env};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{fmt, fs, io};
+use tokio::runtime::Handle;
+use tokio::select;
+use tokio::time::sleep;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Yaml(serde_yaml::Error),
+    UnknownFormat(PathBuf),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read config: {error}"),
+            Self::Toml(error) => write!(f, "invalid toml config: {error}"),
+            Self::Yaml(error) => write!(f, "invalid yaml config: {error}"),
+            Self::UnknownFormat(path) => {
+                write!(f, "unknown config format: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Toml(error) => Some(error),
+            Self::Yaml(error) => Some(error),
+            Self::UnknownFormat(_) => None,
+        }
+    }
+}
+
+struct Config {
+    path: PathBuf,
+    source: String,
+    jobs: HashMap<String, JobOptions>,
+    spawned: Vec<(Arc<JobShared>, JobOptions)>,
+    env: bool,
+}
+
+impl Config {
+    fn update(&mut self, source: String) -> Result<(), ConfigError> {
+        if source == self.source {
+            return Ok(());
+        }
+        self.jobs = parse(&self.path, &source)?;
+        self.source = source;
+        let options: Vec<_> = self
+            .spawned
+            .iter()
+            .map(|(shared, _)| self.options(&shared.name))
+            .collect();
+        for ((shared, applied), options) in self.spawned.iter_mut().zip(options) {
+            if options != *applied {
+                shared
+                    .options
+                    .send_modify(|current| *current = current.clone().rebase(applied, &options));
+                *applied = options;
+            }
+        }
+        Ok(())
+    }
+
+    fn options(&self, name: &str) -> JobOptions {
+        let options = self.jobs.get(name).cloned().unwrap_or_default();
+        match self.env {
+            true => options.merge(env::overrides(name).0),
+            false => options,
+        }
+    }
+}
+
+fn parse(path: &Path, source: &str) -> Result<HashMap<String, JobOptions>, ConfigError> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(source).map_err(ConfigError::Toml),
+        Some("yaml" | "yml") => serde_yaml::from_str(source).map_err(ConfigError::Yaml),
+        _ => Err(ConfigError::UnknownFormat(path.to_path_buf())),
+    }
+}
+
+pub struct ConfigScheduler {
+    scheduler: Scheduler,
+    config: Arc<Mutex<Config>>,
+}
+
+impl ConfigScheduler {
+    pub fn load(scheduler: Scheduler, path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        let path = path.into();
+        let source = fs::read_to_string(&path).map_err(ConfigError::Io)?;
+        let jobs = parse(&path, &source)?;
+        let config = Config {
+            path,
+            source,
+            jobs,
+            spawned: Vec::new(),
+            env: scheduler.env,
+        };
+        Ok(Self {
+            scheduler,
+            config: Arc::new(Mutex::new(config)),
+        })
+    }
+
+    pub fn watch(self, interval: Duration) -> Self {
+        let Ok(runtime) = Handle::try_current() else {
+            #[cfg(feature = "tracing")]
+            tracing::error!("no tokio runtime is available, config file not watched");
+            return self;
+        };
+        let config = self.config.clone();
+        let token = self.scheduler.token.clone();
+        self.scheduler
+            .tracker
+            .spawn_on(async move { poll(config, interval, token).await }, &runtime);
+        self
+    }
+
+    pub fn reload(&self) -> Result<(), ConfigError> {
+        let mut config = self.config.lock().unwrap();
+        let source = fs::read_to_string(&config.path).map_err(ConfigError::Io)?;
+        config.update(source)
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    pub fn spawn(self, job: impl Job) -> Self {
+        self.spawn_with_handle(job);
+        self
+    }
+
+    pub fn spawn_with_handle<J: Job>(&self, job: J) -> JobHandle<J> {
+        let handle = self.scheduler.spawn_with_handle(job);
+        let mut config = self.config.lock().unwrap();
+        let options = config.options(handle.name());
+        handle.shared.options.send_modify(|current| {
+            *current = current.clone().rebase(&JobOptions::default(), &options)
+        });
+        config.spawned.push((handle.shared.clone(), options));
+        handle
+    }
+
+    pub async fn stop(self) -> StopReport {
+        self.scheduler.stop().await
+    }
+
+    pub async fn stop_with_deadline(self, deadline: Duration) -> StopReport {
+        self.scheduler.stop_with_deadline(deadline).await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn wait(self) -> StopReport {
+        self.scheduler.wait().await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn try_wait(self) -> Result<StopReport, Error> {
+        self.scheduler.try_wait().await
+    }
+
+    pub async fn wait_cancel(self, token: Token) -> StopReport {
+        self.scheduler.wait_cancel(token).await
+    }
+}
+
+async fn poll(config: Arc<Mutex<Config>>, interval: Duration, token: Token) {
+    loop {
+        select! {
+            _ = token.cancelled() => return,
+            _ = sleep(interval) => {}
+        }
+        let path = config.lock().unwrap().path.clone();
+        let result = match tokio::fs::read_to_string(&path).await {
+            Ok(source) => config.lock().unwrap().update(source),
+            Err(error) => Err(ConfigError::Io(error)),
+        };
+        #[cfg(feature = "tracing")]
+        if let Err(error) = result {
+            tracing::warn!(path = %path.display(), %error, "failed to reload config");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{JobStatus, Status, Token};
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::select;
+use tokio::time::sleep;
+
+#[derive(Clone)]
+pub struct Console {
+    writer: Arc<Mutex<dyn Write + Send>>,
+    interval: Duration,
+}
+
+impl Console {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+            interval: Duration::from_secs(10),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub(crate) async fn report(self, status: impl Fn() -> Status, token: Token) {
+        loop {
+            select! {
+                _ = token.cancelled() => return,
+                _ = sleep(self.interval) => {}
+            }
+            let table = status().to_string();
+            let mut writer = self.writer.lock().unwrap();
+            let _ = writer
+                .write_all(table.as_bytes())
+                .and_then(|_| writer.flush());
+        }
+    }
+}
+
+const HEADER: [&str; 7] = [
+    "JOB", "STATE", "RUNS", "FAILURES", "LAST RUN", "NEXT RUN", "DURATION",
+];
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let now = SystemTime::now();
+        let rows: Vec<[String; 7]> = self.jobs.iter().map(|job| row(job, now)).collect();
+        let mut widths = HEADER.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let header = HEADER.map(String::from);
+        for row in std::iter::once(&header).chain(&rows) {
+            let mut line = String::new();
+            for (width, cell) in widths.iter().zip(row) {
+                line.push_str(&format!("{cell:<width$}  "));
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn row(job: &JobStatus, now: SystemTime) -> [String; 7] {
+    let stats = &job.stats;
+    let failing = stats.last_run.is_some() && stats.last_success < stats.last_run;
+    let state = if !job.alive {
+        "dead"
+    } else if job.stalled {
+        "stalled"
+    } else if job.quarantined {
+        "quarantined"
+    } else if !job.enabled {
+        "disabled"
+    } else if stats.last_run.is_none() {
+        "pending"
+    } else if failing {
+        "failing"
+    } else {
+        "ok"
+    };
+    let ago = |at: SystemTime| {
+        format!(
+            "{} ago",
+            seconds(now.duration_since(at).unwrap_or_default())
+        )
+    };
+    let until =
+        |at: SystemTime| format!("in {}", seconds(at.duration_since(now).unwrap_or_default()));
+    [
+        job.name.clone(),
+        state.to_string(),
+        stats.runs.to_string(),
+        stats.failures.to_string(),
+        stats.last_run.map(ago).unwrap_or_else(|| "-".into()),
+        stats
+            .next_run
+            .filter(|_| job.alive)
+            .map(until)
+            .unwrap_or_else(|| "-".into()),
+        stats
+            .last_duration
+            .map(|duration| format!("{duration:.1?}"))
+            .unwrap_or_else(|| "-".into()),
+    ]
+}
+
+fn seconds(duration: Duration) -> humantime::FormattedDuration {
+    humantime::format_duration(Duration::from_secs(duration.as_secs()))
+}
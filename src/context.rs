@@ -0,0 +1,202 @@
+use crate::fallible::{ErrorHandlers, Failures};
+use crate::report::Report;
+use crate::status::JobShared;
+use crate::{RunStatus, Token};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
+
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) type States = Arc<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+pub(crate) type Deadline = Arc<OnceLock<Instant>>;
+
+pub struct RunContext {
+    run_id: u64,
+    name: Arc<str>,
+    token: Token,
+    states: States,
+    tick: SystemTime,
+    started: SystemTime,
+    begun: Instant,
+    next_tick: Option<SystemTime>,
+    key: OnceLock<String>,
+    deadline: Option<Deadline>,
+    dry_run: bool,
+    report: Report,
+    abandoned: Arc<AtomicBool>,
+    skipped: Arc<AtomicBool>,
+    error_handlers: ErrorHandlers,
+    shared: Option<Arc<JobShared>>,
+}
+
+impl RunContext {
+    pub(crate) fn new(name: Arc<str>, token: Token, states: States, tick: SystemTime) -> Self {
+        Self {
+            run_id: NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed),
+            key: OnceLock::new(),
+            name,
+            token,
+            states,
+            tick,
+            started: tick,
+            begun: Instant::now(),
+            next_tick: None,
+            deadline: None,
+            dry_run: false,
+            report: Report::default(),
+            abandoned: Arc::default(),
+            skipped: Arc::default(),
+            error_handlers: ErrorHandlers::default(),
+            shared: None,
+        }
+    }
+
+    pub(crate) fn with_deadline(mut self, deadline: Option<Deadline>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub(crate) fn with_started(mut self, started: SystemTime) -> Self {
+        self.started = started;
+        self
+    }
+
+    pub(crate) fn with_next_tick(mut self, next_tick: Option<SystemTime>) -> Self {
+        self.next_tick = next_tick;
+        self
+    }
+
+    pub(crate) fn with_shared(mut self, shared: Arc<JobShared>) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    pub(crate) fn with_error_handlers(mut self, error_handlers: ErrorHandlers) -> Self {
+        self.error_handlers = error_handlers;
+        self
+    }
+
+    pub(crate) fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn run_id(&self) -> u64 {
+        self.run_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tick(&self) -> SystemTime {
+        self.tick
+    }
+
+    pub fn started(&self) -> SystemTime {
+        self.started
+    }
+
+    pub fn lateness(&self) -> Duration {
+        self.started.duration_since(self.tick).unwrap_or_default()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.begun.elapsed()
+    }
+
+    pub fn next_tick(&self) -> Option<SystemTime> {
+        self.next_tick
+    }
+
+    pub fn time_until_next_tick(&self) -> Option<Duration> {
+        let now = self.started + self.elapsed();
+        Some(self.next_tick?.duration_since(now).unwrap_or_default())
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn idempotency_key(&self) -> &str {
+        self.key.get_or_init(|| {
+            let millis = self
+                .tick
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            format!("{}@{millis}", self.name)
+        })
+    }
+
+    pub fn report(&self, status: RunStatus) {
+        *self.report.lock().unwrap() = Some(status);
+    }
+
+    pub fn fail(&self, error: impl Into<Box<dyn Error + Send + Sync>>) {
+        self.failures().fail(&*error.into());
+    }
+
+    pub(crate) fn failures(&self) -> Failures {
+        Failures::new(
+            self.name.clone(),
+            self.report.clone(),
+            self.error_handlers.clone(),
+        )
+    }
+
+    pub(crate) fn reported(&self) -> Report {
+        self.report.clone()
+    }
+
+    pub fn heartbeat(&self) {
+        if let Some(shared) = &self.shared {
+            shared.progressed();
+        }
+    }
+
+    pub fn abandon(&self) {
+        self.abandoned.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn abandoned(&self) -> Arc<AtomicBool> {
+        self.abandoned.clone()
+    }
+
+    pub(crate) fn skip(&self) {
+        self.skipped.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn skipped(&self) -> Arc<AtomicBool> {
+        self.skipped.clone()
+    }
+
+    pub(crate) fn shared(&self) -> Option<Arc<JobShared>> {
+        self.shared.clone()
+    }
+
+    pub fn token(&self) -> Token {
+        self.token.clone()
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline.as_ref()?.get().copied()
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        Some(self.deadline()?.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn state<S: Any + Send + Sync>(&self) -> Option<Arc<S>> {
+        self.states
+            .get(&TypeId::of::<S>())
+            .cloned()
+            .and_then(|state| state.downcast().ok())
+    }
+}
@@ -0,0 +1,561 @@
+use crate::schedule::{at, zone_name};
+use crate::{Schedule, TruncateZone};
+use chrono::{Datelike, NaiveDate, NaiveTime, TimeDelta, Timelike, Weekday};
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+const MAX_YEARS: i32 = 400;
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+#[derive(Debug, Clone)]
+pub struct Cron {
+    expression: String,
+    zone: TruncateZone,
+    spec: Spec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCronError(pub(crate) String);
+
+impl fmt::Display for ParseCronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseCronError {}
+
+#[derive(Debug, Clone)]
+enum Spec {
+    Reboot,
+    Fields(Box<Fields>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Fields {
+    pub(crate) seconds: u64,
+    pub(crate) minutes: u64,
+    pub(crate) hours: u64,
+    pub(crate) months: u64,
+    pub(crate) day_of_month: Option<DayOfMonth>,
+    pub(crate) day_of_week: Option<DayOfWeek>,
+    pub(crate) years: Option<Vec<u32>>,
+    pub(crate) either_day: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DayOfMonth {
+    Days(u64),
+    Last(u32),
+    LastWeekday,
+    NearestWeekday(u32),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DayOfWeek {
+    Days(u64),
+    Last(Weekday),
+    Nth(Weekday, u32),
+}
+
+impl Cron {
+    pub fn parse(expression: &str) -> Result<Self, ParseCronError> {
+        let trimmed = expression.trim();
+        let spec = match trimmed.strip_prefix('@') {
+            Some("reboot") => Spec::Reboot,
+            Some(alias) => Spec::Fields(Box::new(Fields::parse(&expand(alias)?)?)),
+            None => Spec::Fields(Box::new(Fields::parse(trimmed)?)),
+        };
+        Ok(Self {
+            expression: trimmed.to_string(),
+            zone: TruncateZone::Utc,
+            spec,
+        })
+    }
+
+    pub fn zone(mut self, zone: TruncateZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        match &self.spec {
+            Spec::Reboot => "once at startup".into(),
+            Spec::Fields(fields) => fields.describe(),
+        }
+    }
+}
+
+impl FromStr for Cron {
+    type Err = ParseCronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl Schedule for Cron {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        match &self.spec {
+            Spec::Reboot => None,
+            Spec::Fields(fields) => fields.next_after(self.zone, after),
+        }
+    }
+
+    fn fires_at_start(&self, _started: SystemTime) -> bool {
+        matches!(self.spec, Spec::Reboot)
+    }
+
+    fn describe(&self) -> String {
+        match &self.spec {
+            Spec::Reboot => self.summary(),
+            Spec::Fields(_) => format!("{}, in {}", self.summary(), zone_name(self.zone)),
+        }
+    }
+
+    fn explain(&self, now: SystemTime) -> String {
+        match (&self.spec, self.next_after(now)) {
+            (Spec::Reboot, _) => self.describe(),
+            (_, Some(next)) => format!("{}, next at {}", self.describe(), at(self.zone, next)),
+            (_, None) => format!("{}, no upcoming runs", self.describe()),
+        }
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.zone
+    }
+}
+
+fn expand(alias: &str) -> Result<String, ParseCronError> {
+    let fields = match alias {
+        "yearly" | "annually" => "0 0 0 1 1 ?",
+        "monthly" => "0 0 0 1 * ?",
+        "weekly" => "0 0 0 ? * SUN",
+        "daily" | "midnight" => "0 0 0 * * ?",
+        "hourly" => "0 0 * * * ?",
+        _ => return Err(ParseCronError(format!("unknown alias `@{alias}`"))),
+    };
+    Ok(fields.to_string())
+}
+
+impl Fields {
+    fn parse(expression: &str) -> Result<Self, ParseCronError> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (seconds, rest, quartz) = match fields.len() {
+            5 => ("0", &fields[..], false),
+            6 | 7 => (fields[0], &fields[1..], true),
+            found => {
+                return Err(ParseCronError(format!(
+                    "expected 5 to 7 fields, found {found} in `{expression}`"
+                )));
+            }
+        };
+        Ok(Self {
+            seconds: mask(values("second", seconds, 0, 59, &[], 0)?),
+            minutes: mask(values("minute", rest[0], 0, 59, &[], 0)?),
+            hours: mask(values("hour", rest[1], 0, 23, &[], 0)?),
+            day_of_month: DayOfMonth::parse(rest[2])?,
+            months: mask(values("month", rest[3], 1, 12, &MONTHS, 1)?),
+            day_of_week: DayOfWeek::parse(rest[4], quartz)?,
+            years: match rest.get(5) {
+                None | Some(&"*") | Some(&"?") => None,
+                Some(field) => Some(values("year", field, 1970, 2199, &[], 0)?),
+            },
+            either_day: true,
+        })
+    }
+
+    pub(crate) fn next_after(&self, zone: TruncateZone, after: SystemTime) -> Option<SystemTime> {
+        let transition = TimeDelta::from_std(zone.transition_near(after)).ok()?;
+        let start = (zone.local(after) - transition).with_nanosecond(0)? + TimeDelta::seconds(1);
+        let limit = start.year() + MAX_YEARS;
+        let mut best: Option<SystemTime> = None;
+        let mut at = start;
+        while at.year() <= limit {
+            let date = at.date();
+            if let Some(years) = &self.years
+                && !years.contains(&(at.year() as u32))
+            {
+                at = NaiveDate::from_ymd_opt(at.year() + 1, 1, 1)?.and_time(NaiveTime::MIN);
+            } else if !contains(self.months, at.month()) {
+                at = first_of_next_month(date)?.and_time(NaiveTime::MIN);
+            } else if !self.matches_day(date) {
+                at = date.succ_opt()?.and_time(NaiveTime::MIN);
+            } else if !contains(self.hours, at.hour()) {
+                at = date.and_hms_opt(at.hour(), 0, 0)? + TimeDelta::hours(1);
+            } else if !contains(self.minutes, at.minute()) {
+                at = date.and_hms_opt(at.hour(), at.minute(), 0)? + TimeDelta::minutes(1);
+            } else if !contains(self.seconds, at.second()) {
+                at += TimeDelta::seconds(1);
+            } else {
+                let next = zone.resolve_local(at);
+                if next > after && best.is_none_or(|best| next < best) {
+                    best = Some(next);
+                }
+                if let Some(best) = best
+                    && at >= zone.local(best) + transition
+                {
+                    return Some(best);
+                }
+                at += TimeDelta::seconds(1);
+            }
+        }
+        best
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        let mut parts = vec![self.describe_time()];
+        let day_of_month = self.day_of_month.map(DayOfMonth::describe);
+        let day_of_week = self.day_of_week.map(DayOfWeek::describe);
+        match (day_of_month, day_of_week) {
+            (None, None) => {}
+            (Some(day), None) | (None, Some(day)) => parts.push(day),
+            (Some(day_of_month), Some(day_of_week)) => {
+                let join = if self.either_day { "or" } else { "and" };
+                parts.push(format!("{day_of_month} {join} {day_of_week}"));
+            }
+        }
+        if self.months != mask((1..=12).collect()) {
+            let months: Vec<_> = (1..=12u32)
+                .filter(|month| contains(self.months, *month))
+                .map(|month| title(MONTHS[month as usize - 1]))
+                .collect();
+            parts.push(format!("in {}", months.join(", ")));
+        }
+        if let Some(years) = &self.years {
+            parts.push(format!("in {}", list(years)));
+        }
+        parts.join(" ")
+    }
+
+    fn describe_time(&self) -> String {
+        let every_minute = self.minutes == mask((0..=59).collect());
+        let every_hour = self.hours == mask((0..=23).collect());
+        match (
+            single(self.seconds),
+            single(self.minutes),
+            single(self.hours),
+        ) {
+            (Some(0), Some(0), _) if every_hour => return "every hour".into(),
+            (Some(0), Some(minute), _) if every_hour => {
+                return format!("every hour at minute {minute}");
+            }
+            (Some(0), _, _) if every_minute && every_hour => return "every minute".into(),
+            (Some(0), Some(minute), Some(hour)) => return format!("at {hour:02}:{minute:02}"),
+            (Some(second), Some(minute), Some(hour)) => {
+                return format!("at {hour:02}:{minute:02}:{second:02}");
+            }
+            _ => {}
+        }
+        let mut parts = Vec::new();
+        match single(self.seconds) {
+            Some(0) => {}
+            _ => parts.push(field(self.seconds, 0, 59, "second")),
+        }
+        if !every_minute || parts.is_empty() {
+            parts.push(field(self.minutes, 0, 59, "minute"));
+        }
+        if !every_hour {
+            let hours = field(self.hours, 0, 23, "hour");
+            parts.push(hours.replacen("at ", "during ", 1));
+        }
+        parts.join(" ")
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        match (self.day_of_month, self.day_of_week) {
+            (None, None) => true,
+            (Some(day_of_month), None) => day_of_month.matches(date),
+            (None, Some(day_of_week)) => day_of_week.matches(date),
+            (Some(day_of_month), Some(day_of_week)) if self.either_day => {
+                day_of_month.matches(date) || day_of_week.matches(date)
+            }
+            (Some(day_of_month), Some(day_of_week)) => {
+                day_of_month.matches(date) && day_of_week.matches(date)
+            }
+        }
+    }
+}
+
+impl DayOfMonth {
+    fn parse(field: &str) -> Result<Option<Self>, ParseCronError> {
+        let invalid =
+            |reason: &str| ParseCronError(format!("invalid day-of-month `{field}`: {reason}"));
+        let day = match field {
+            "*" | "?" => return Ok(None),
+            "L" => Self::Last(0),
+            "LW" => Self::LastWeekday,
+            _ => {
+                if let Some(before) = field.strip_prefix("L-") {
+                    let before = number(before).ok_or_else(|| invalid("expected `L-<days>`"))?;
+                    if before > 30 {
+                        return Err(invalid("offset must be between 0 and 30"));
+                    }
+                    Self::Last(before)
+                } else if let Some(day) = field.strip_suffix('W') {
+                    let day = number(day).ok_or_else(|| invalid("expected `<day>W`"))?;
+                    if !(1..=31).contains(&day) {
+                        return Err(invalid("day must be between 1 and 31"));
+                    }
+                    Self::NearestWeekday(day)
+                } else {
+                    Self::Days(mask(values("day-of-month", field, 1, 31, &[], 0)?))
+                }
+            }
+        };
+        Ok(Some(day))
+    }
+
+    fn describe(self) -> String {
+        match self {
+            Self::Days(days) => format!("on day {} of the month", list(&bits(days, 1, 31))),
+            Self::Last(0) => "on the last day of the month".into(),
+            Self::Last(1) => "1 day before the last day of the month".into(),
+            Self::Last(before) => format!("{before} days before the last day of the month"),
+            Self::LastWeekday => "on the last weekday of the month".into(),
+            Self::NearestWeekday(day) => format!("on the weekday nearest day {day} of the month"),
+        }
+    }
+
+    fn matches(self, date: NaiveDate) -> bool {
+        let last = last_day(date);
+        match self {
+            Self::Days(days) => contains(days, date.day()),
+            Self::Last(before) => last > before && date.day() == last - before,
+            Self::LastWeekday => date.day() == nearest_weekday(date, last, last),
+            Self::NearestWeekday(day) => date.day() == nearest_weekday(date, day.min(last), last),
+        }
+    }
+}
+
+impl DayOfWeek {
+    fn parse(field: &str, quartz: bool) -> Result<Option<Self>, ParseCronError> {
+        let invalid =
+            |reason: &str| ParseCronError(format!("invalid day-of-week `{field}`: {reason}"));
+        let first = quartz as u32;
+        let days = |value: &str| values("day-of-week", value, first, 7, &DAYS, first);
+        let weekday = |value: &str| -> Result<Weekday, ParseCronError> {
+            match days(value)?.as_slice() {
+                [day] => Ok(to_weekday(*day, quartz)),
+                _ => Err(invalid("expected a single day")),
+            }
+        };
+        let day = match field {
+            "*" | "?" => return Ok(None),
+            _ => {
+                if let Some((day, nth)) = field.split_once('#') {
+                    let nth = number(nth).ok_or_else(|| invalid("expected `<day>#<n>`"))?;
+                    if !(1..=5).contains(&nth) {
+                        return Err(invalid("occurrence must be between 1 and 5"));
+                    }
+                    Self::Nth(weekday(day)?, nth)
+                } else if let Some(day) = field.strip_suffix('L')
+                    && !day.is_empty()
+                {
+                    Self::Last(weekday(day)?)
+                } else {
+                    Self::Days(
+                        days(field)?
+                            .into_iter()
+                            .map(|day| to_weekday(day, quartz).num_days_from_monday())
+                            .fold(0, |days, day| days | 1 << day),
+                    )
+                }
+            }
+        };
+        Ok(Some(day))
+    }
+
+    fn describe(self) -> String {
+        match self {
+            Self::Days(0b0011111) => "on weekdays".into(),
+            Self::Days(0b1100000) => "on weekends".into(),
+            Self::Days(days) => {
+                let days: Vec<_> = bits(days, 0, 6)
+                    .into_iter()
+                    .filter_map(|day| Weekday::try_from(day as u8).ok())
+                    .map(|day| day.to_string())
+                    .collect();
+                format!("on {}", days.join(", "))
+            }
+            Self::Last(day) => format!("on the last {day} of the month"),
+            Self::Nth(day, nth) => format!("on the {} {day} of the month", ordinal(nth)),
+        }
+    }
+
+    fn matches(self, date: NaiveDate) -> bool {
+        match self {
+            Self::Days(days) => contains(days, date.weekday().num_days_from_monday()),
+            Self::Last(day) => date.weekday() == day && date.day() + 7 > last_day(date),
+            Self::Nth(day, nth) => date.weekday() == day && (date.day() - 1) / 7 + 1 == nth,
+        }
+    }
+}
+
+pub(crate) fn values(
+    name: &str,
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    first: u32,
+) -> Result<Vec<u32>, ParseCronError> {
+    let invalid = |reason: String| ParseCronError(format!("invalid {name} `{field}`: {reason}"));
+    let value = |text: &str| -> Result<u32, ParseCronError> {
+        let value = match names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+        {
+            Some(index) => index as u32 + first,
+            None => number(text).ok_or_else(|| invalid(format!("`{text}` is not a number")))?,
+        };
+        match (min..=max).contains(&value) {
+            true => Ok(value),
+            false => Err(invalid(format!("{value} is out of range {min}-{max}"))),
+        }
+    };
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match number(step) {
+                Some(step) if step > 0 => (range, step),
+                _ => return Err(invalid(format!("step `{step}` must be a positive number"))),
+            },
+            None => (part, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            None if part.contains('/') => (value(range)?, max),
+            None => {
+                let value = value(range)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            return Err(invalid(format!("range {start}-{end} is reversed")));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn number(text: &str) -> Option<u32> {
+    match text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => None,
+        false => text.parse().ok(),
+    }
+}
+
+pub(crate) fn mask(values: Vec<u32>) -> u64 {
+    values.into_iter().fold(0, |mask, value| mask | 1 << value)
+}
+
+fn contains(mask: u64, value: u32) -> bool {
+    mask & 1 << value != 0
+}
+
+fn bits(mask: u64, min: u32, max: u32) -> Vec<u32> {
+    (min..=max).filter(|value| contains(mask, *value)).collect()
+}
+
+fn single(mask: u64) -> Option<u32> {
+    (mask.count_ones() == 1).then(|| mask.trailing_zeros())
+}
+
+fn field(mask: u64, min: u32, max: u32, unit: &str) -> String {
+    let values = bits(mask, min, max);
+    if values.len() as u32 == max - min + 1 {
+        return format!("every {unit}");
+    }
+    if let [first, second, ..] = values[..]
+        && first == min
+        && values
+            .windows(2)
+            .all(|pair| pair[1] - pair[0] == second - first)
+        && max - values[values.len() - 1] < second - first
+    {
+        return format!("every {} {unit}s", second - first);
+    }
+    match values.len() {
+        1 => format!("at {unit} {}", values[0]),
+        _ => format!("at {unit}s {}", list(&values)),
+    }
+}
+
+fn list(values: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &value in values {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == value => *end = value,
+            _ => ranges.push((value, value)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| match end - start {
+            0 => start.to_string(),
+            1 => format!("{start}, {end}"),
+            _ => format!("{start}-{end}"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn title(name: &str) -> String {
+    name[..1].to_string() + &name[1..].to_ascii_lowercase()
+}
+
+fn ordinal(nth: u32) -> &'static str {
+    ["first", "second", "third", "fourth", "fifth"][nth as usize - 1]
+}
+
+fn to_weekday(day: u32, quartz: bool) -> Weekday {
+    let from_monday = match quartz {
+        true => (day + 5) % 7,
+        false => (day + 6) % 7,
+    };
+    Weekday::try_from(from_monday as u8).unwrap()
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    match date.month() {
+        12 => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1),
+        month => NaiveDate::from_ymd_opt(date.year(), month + 1, 1),
+    }
+}
+
+fn last_day(date: NaiveDate) -> u32 {
+    first_of_next_month(date)
+        .and_then(|first| first.pred_opt())
+        .map_or(31, |last| last.day())
+}
+
+fn nearest_weekday(date: NaiveDate, day: u32, last: u32) -> u32 {
+    let Some(target) = date.with_day(day) else {
+        return 0;
+    };
+    match target.weekday() {
+        Weekday::Sat if day == 1 => 3,
+        Weekday::Sat => day - 1,
+        Weekday::Sun if day == last => day - 2,
+        Weekday::Sun => day + 1,
+        _ => day,
+    }
+}
@@ -0,0 +1,48 @@
+use crate::Store;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+pub(crate) struct Dedup {
+    store: Arc<dyn Store>,
+    retention: Duration,
+    claims: VecDeque<(SystemTime, String)>,
+}
+
+impl Dedup {
+    pub(crate) fn new(store: Arc<dyn Store>, retention: Duration) -> Self {
+        Self {
+            store,
+            retention,
+            claims: VecDeque::new(),
+        }
+    }
+
+    pub(crate) async fn claim(&mut self, key: &str, tick: SystemTime) -> bool {
+        self.expire(tick).await;
+        let claim = format!("dedup/{key}");
+        match self.store.insert(&claim, Vec::new()).await {
+            Ok(claimed) => {
+                self.claims.push_back((tick, claim));
+                claimed
+            }
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(key = claim, error = %_error, "failed to claim run, running anyway");
+                true
+            }
+        }
+    }
+
+    async fn expire(&mut self, now: SystemTime) {
+        while let Some((tick, _)) = self.claims.front()
+            && now
+                .duration_since(*tick)
+                .is_ok_and(|age| age > self.retention)
+        {
+            if let Some((_, claim)) = self.claims.pop_front() {
+                let _ = self.store.remove(&claim).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    JobOptions, Labels, LoadPolicy, LogLevel, Misfire, Quarantine, SuspendPolicy, TruncateZone,
+};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvError {
+    pub var: String,
+    pub value: String,
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid value for {}: {:?}", self.var, self.value)
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+fn env_prefix(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect();
+    format!("APJ_JOB_{name}_")
+}
+
+impl JobOptions {
+    pub fn from_env(name: &str) -> Result<Self, EnvError> {
+        let (options, errors) = Self::read_env(name);
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(options),
+        }
+    }
+
+    fn read_env(name: &str) -> (Self, Vec<EnvError>) {
+        let mut vars = Vars {
+            prefix: env_prefix(name),
+            errors: Vec::new(),
+        };
+        let options = Self {
+            period: vars.get("PERIOD", parse),
+            truncate_time: vars.get("TRUNCATE_TIME", parse_bool),
+            truncate_zone: vars.get("TRUNCATE_ZONE", |value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "utc" => Some(TruncateZone::Utc),
+                    "local" => Some(TruncateZone::Local),
+                    #[cfg(feature = "tz")]
+                    _ => value.parse().ok().map(TruncateZone::Tz),
+                    #[cfg(not(feature = "tz"))]
+                    _ => None,
+                }
+            }),
+            critical: vars.get("CRITICAL", parse_bool),
+            labels: vars.get("LABELS", parse_labels),
+            enabled: vars.get("ENABLED", parse_bool),
+            dry_run: vars.get("DRY_RUN", parse_bool),
+            stagger: vars.get("STAGGER", parse_bool),
+            finish_by: vars.get("FINISH_BY", parse_bool),
+            max_duty_cycle: vars.get("MAX_DUTY_CYCLE", parse),
+            quarantine: vars.get("QUARANTINE_AFTER", |value| {
+                value.parse().ok().map(Quarantine::after)
+            }),
+            on_suspend: vars.get("ON_SUSPEND", |value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "run_now" => Some(SuspendPolicy::RunNow),
+                    "realign" => Some(SuspendPolicy::Realign),
+                    "skip" => Some(SuspendPolicy::Skip),
+                    _ => None,
+                }
+            }),
+            misfire: vars.get("MISFIRE", |value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "fire_now_once" => Some(Misfire::FireNowOnce),
+                    "fire_all_missed" => Some(Misfire::FireAllMissed),
+                    "skip_to_next" => Some(Misfire::SkipToNext),
+                    "delay" => Some(Misfire::Delay),
+                    _ => None,
+                }
+            }),
+            load_policy: vars.get("LOAD_POLICY", |value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "ignore" => Some(LoadPolicy::Ignore),
+                    "skip" => Some(LoadPolicy::Skip),
+                    "delay" => Some(LoadPolicy::Delay),
+                    _ => None,
+                }
+            }),
+            log_level: vars.get("LOG_LEVEL", |value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "off" => Some(LogLevel::Off),
+                    "trace" => Some(LogLevel::Trace),
+                    "debug" => Some(LogLevel::Debug),
+                    "info" => Some(LogLevel::Info),
+                    "warn" => Some(LogLevel::Warn),
+                    "error" => Some(LogLevel::Error),
+                    _ => None,
+                }
+            }),
+            ..Self::default()
+        };
+        (options, vars.errors)
+    }
+}
+
+pub(crate) fn overrides(name: &str) -> (JobOptions, Vec<EnvError>) {
+    let (options, errors) = JobOptions::read_env(name);
+    #[cfg(feature = "tracing")]
+    for error in &errors {
+        tracing::warn!(job = name, %error, "ignoring environment override");
+    }
+    (options, errors)
+}
+
+struct Vars {
+    prefix: String,
+    errors: Vec<EnvError>,
+}
+
+impl Vars {
+    fn get<T>(&mut self, option: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+        let var = format!("{}{option}", self.prefix);
+        let value = std::env::var(&var).ok()?;
+        let parsed = parse(value.trim());
+        if parsed.is_none() {
+            self.errors.push(EnvError { var, value });
+        }
+        parsed
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> Option<T> {
+    value.parse().ok()
+}
+
+fn parse_labels(value: &str) -> Option<Labels> {
+    value
+        .split(',')
+        .filter(|label| !label.trim().is_empty())
+        .map(|label| {
+            let (key, value) = label.split_once('=')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "config")]
+use crate::ConfigError;
+use crate::{EnvError, JobFailure, ParsePeriodError, SpawnError};
+use std::{fmt, io};
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    Signal(io::Error),
+    Schedule(SpawnError),
+    Period(ParsePeriodError),
+    Env(EnvError),
+    #[cfg(feature = "config")]
+    Config(ConfigError),
+    Store(io::Error),
+    Jobs(Vec<JobFailure>),
+    Aborted(Vec<String>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signal(error) => write!(f, "failed to listen for shutdown signal: {error}"),
+            Self::Schedule(error) => write!(f, "invalid schedule: {error}"),
+            Self::Period(error) => error.fmt(f),
+            Self::Env(error) => error.fmt(f),
+            #[cfg(feature = "config")]
+            Self::Config(error) => error.fmt(f),
+            Self::Store(error) => write!(f, "store error: {error}"),
+            Self::Jobs(failures) => {
+                let names: Vec<_> = failures.iter().map(|failure| &*failure.name).collect();
+                write!(f, "jobs failed: {}", names.join(", "))
+            }
+            Self::Aborted(names) => write!(f, "jobs aborted: {}", names.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Signal(error) | Self::Store(error) => Some(error),
+            Self::Schedule(error) => Some(error),
+            Self::Period(error) => Some(error),
+            Self::Env(error) => Some(error),
+            #[cfg(feature = "config")]
+            Self::Config(error) => Some(error),
+            Self::Jobs(failures) => failures.first().map(|failure| &failure.error as _),
+            Self::Aborted(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Store(error)
+    }
+}
+
+impl From<SpawnError> for Error {
+    fn from(error: SpawnError) -> Self {
+        Self::Schedule(error)
+    }
+}
+
+impl From<ParsePeriodError> for Error {
+    fn from(error: ParsePeriodError) -> Self {
+        Self::Period(error)
+    }
+}
+
+impl From<EnvError> for Error {
+    fn from(error: EnvError) -> Self {
+        Self::Env(error)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<ConfigError> for Error {
+    fn from(error: ConfigError) -> Self {
+        Self::Config(error)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{RunStatus, TriggerDecision};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    Sampled,
+    Budget,
+    Suspended,
+    Disabled,
+    Duplicate,
+    Late,
+    Load,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    Started {
+        run_id: u64,
+        shadow: bool,
+    },
+    Finished {
+        run_id: u64,
+        duration: Duration,
+        panicked: bool,
+        status: RunStatus,
+    },
+    Skipped {
+        reason: SkipReason,
+    },
+    BudgetExhausted {
+        used: Duration,
+    },
+    Suspended {
+        slept: Duration,
+    },
+    PeriodFloored {
+        requested: Duration,
+        floor: Duration,
+    },
+    PeriodLimited {
+        requested: Duration,
+        limited: Duration,
+    },
+    Cancelled {
+        interrupted: bool,
+    },
+    DeadlineMissed {
+        run_id: u64,
+        late: Duration,
+    },
+    RunCancelled {
+        run_id: u64,
+    },
+    LoadDelayed {
+        waited: Duration,
+    },
+    Triggered {
+        decision: TriggerDecision,
+    },
+    Quarantined {
+        strikes: u32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub name: Arc<str>,
+    pub kind: EventKind,
+    pub at: SystemTime,
+}
+
+type Sink = Box<dyn FnMut(&Event) + Send>;
+
+#[derive(Clone)]
+pub(crate) struct Events {
+    sender: broadcast::Sender<Event>,
+    sinks: Arc<Mutex<Vec<Sink>>>,
+}
+
+const CAPACITY: usize = 1024;
+
+impl Default for Events {
+    fn default() -> Self {
+        Self::new(CAPACITY)
+    }
+}
+
+impl Events {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            sender: broadcast::Sender::new(capacity.max(1)),
+            sinks: Arc::default(),
+        }
+    }
+
+    pub(crate) fn resize(&self, capacity: usize) -> Self {
+        Self {
+            sender: broadcast::Sender::new(capacity.max(1)),
+            sinks: self.sinks.clone(),
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn sink(&self, sink: impl FnMut(&Event) + Send + 'static) {
+        self.sinks.lock().unwrap().push(Box::new(sink));
+    }
+
+    pub(crate) fn emit(&self, name: &Arc<str>, kind: EventKind) {
+        let mut sinks = self.sinks.lock().unwrap();
+        if self.sender.receiver_count() == 0 && sinks.is_empty() {
+            return;
+        }
+        let event = Event {
+            name: name.clone(),
+            kind,
+            at: SystemTime::now(),
+        };
+        for sink in sinks.iter_mut() {
+            sink(&event);
+        }
+        let _ = self.sender.send(event);
+    }
+}
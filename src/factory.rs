@@ -0,0 +1,168 @@
+use crate::{
+    AlertRule, Budget, Completion, DstPolicy, Job, Labels, Layer, LoadPolicy, LogLevel, Misfire,
+    PeriodLimit, Quarantine, RunContext, Sampling, Schedule, Supervisor, SuspendPolicy,
+    TriggerPolicy, TruncateZone,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub trait JobFactory: Send + Sync + 'static {
+    type Job: Job;
+
+    fn create(&self) -> Self::Job;
+}
+
+impl<F, J> JobFactory for F
+where
+    F: Fn() -> J + Send + Sync + 'static,
+    J: Job,
+{
+    type Job = J;
+
+    fn create(&self) -> J {
+        self()
+    }
+}
+
+pub struct Factory<F: JobFactory> {
+    factory: F,
+    template: F::Job,
+}
+
+impl<F: JobFactory> Factory<F> {
+    pub fn new(factory: F) -> Self {
+        let template = factory.create();
+        Self { factory, template }
+    }
+}
+
+impl<F: JobFactory> Job for Factory<F> {
+    fn name(&self) -> &str {
+        self.template.name()
+    }
+
+    fn period(&self) -> Duration {
+        self.template.period()
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.template.with_truncate_time()
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        self.template.truncate_zone()
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        self.template.dst_policy()
+    }
+
+    fn run_with_context(&mut self, ctx: RunContext) -> impl Future<Output = ()> + Send {
+        let mut job = self.factory.create();
+        async move { job.run_with_context(ctx).await }
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.template.supervisor()
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        self.template.layers()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        self.template.exclusion_group()
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        self.template.shard_key()
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        self.template.alert_rule()
+    }
+
+    fn critical(&self) -> bool {
+        self.template.critical()
+    }
+
+    fn labels(&self) -> Labels {
+        self.template.labels()
+    }
+
+    fn enabled(&self) -> bool {
+        self.template.enabled()
+    }
+
+    fn abortable(&self) -> bool {
+        self.template.abortable()
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        self.template.trigger_policy()
+    }
+
+    fn log_level(&self) -> LogLevel {
+        self.template.log_level()
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        self.template.schedule()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.template.dry_run()
+    }
+
+    fn stagger(&self) -> bool {
+        self.template.stagger()
+    }
+
+    fn finish_by(&self) -> bool {
+        self.template.finish_by()
+    }
+
+    fn sampling(&self) -> Sampling {
+        self.template.sampling()
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        self.template.max_duty_cycle()
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        self.template.period_limit()
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        self.template.quarantine()
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        self.template.budget()
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        self.template.completion()
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        self.template.on_suspend()
+    }
+
+    fn misfire(&self) -> Misfire {
+        self.template.misfire()
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        self.template.load_policy()
+    }
+
+    fn durable(&self) -> bool {
+        self.template.durable()
+    }
+
+    fn dedup(&self) -> bool {
+        self.template.dedup()
+    }
+}
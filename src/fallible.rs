@@ -0,0 +1,209 @@
+use crate::report::Report;
+use crate::{
+    AlertRule, Budget, Completion, DstPolicy, Job, Labels, Layer, LoadPolicy, LogLevel, Misfire,
+    PeriodLimit, Quarantine, RunContext, RunStatus, Sampling, Schedule, Supervisor, SuspendPolicy,
+    TriggerPolicy, TruncateZone,
+};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub(crate) type ErrorHandler =
+    Arc<dyn Fn(&str, &(dyn Error + Send + Sync + 'static)) + Send + Sync>;
+
+pub(crate) type ErrorHandlers = Arc<Vec<ErrorHandler>>;
+
+type Handler<E> = Box<dyn FnMut(&E) + Send>;
+
+pub trait FallibleJob: Job {
+    type Error: Error + Send + Sync + 'static;
+
+    fn try_run(&mut self, ctx: RunContext) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+pub struct Fallible<J: FallibleJob> {
+    job: J,
+    handlers: Vec<Handler<J::Error>>,
+}
+
+impl<J: FallibleJob> Fallible<J> {
+    pub fn new(job: J) -> Self {
+        Self {
+            job,
+            handlers: Vec::new(),
+        }
+    }
+
+    pub fn on_error(mut self, handler: impl FnMut(&J::Error) + Send + 'static) -> Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    pub fn inner(&self) -> &J {
+        &self.job
+    }
+
+    pub fn inner_mut(&mut self) -> &mut J {
+        &mut self.job
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Failures {
+    name: Arc<str>,
+    report: Report,
+    handlers: ErrorHandlers,
+}
+
+impl Failures {
+    pub(crate) fn new(name: Arc<str>, report: Report, handlers: ErrorHandlers) -> Self {
+        Self {
+            name,
+            report,
+            handlers,
+        }
+    }
+
+    pub(crate) fn fail(&self, error: &(dyn Error + Send + Sync + 'static)) {
+        *self.report.lock().unwrap() = Some(RunStatus::Failed(error.to_string()));
+        for handler in self.handlers.iter() {
+            handler(&self.name, error);
+        }
+    }
+}
+
+impl<J: FallibleJob> Job for Fallible<J> {
+    fn name(&self) -> &str {
+        self.job.name()
+    }
+
+    fn period(&self) -> Duration {
+        self.job.period()
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.job.with_truncate_time()
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        self.job.truncate_zone()
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        self.job.dst_policy()
+    }
+
+    async fn run_with_context(&mut self, ctx: RunContext) {
+        let failures = ctx.failures();
+        if let Err(error) = self.job.try_run(ctx).await {
+            for handler in &mut self.handlers {
+                handler(&error);
+            }
+            failures.fail(&error);
+        }
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.job.supervisor()
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        self.job.layers()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        self.job.exclusion_group()
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        self.job.shard_key()
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        self.job.alert_rule()
+    }
+
+    fn critical(&self) -> bool {
+        self.job.critical()
+    }
+
+    fn labels(&self) -> Labels {
+        self.job.labels()
+    }
+
+    fn enabled(&self) -> bool {
+        self.job.enabled()
+    }
+
+    fn abortable(&self) -> bool {
+        self.job.abortable()
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        self.job.trigger_policy()
+    }
+
+    fn log_level(&self) -> LogLevel {
+        self.job.log_level()
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        self.job.schedule()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.job.dry_run()
+    }
+
+    fn stagger(&self) -> bool {
+        self.job.stagger()
+    }
+
+    fn finish_by(&self) -> bool {
+        self.job.finish_by()
+    }
+
+    fn sampling(&self) -> Sampling {
+        self.job.sampling()
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        self.job.max_duty_cycle()
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        self.job.period_limit()
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        self.job.quarantine()
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        self.job.budget()
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        self.job.completion()
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        self.job.on_suspend()
+    }
+
+    fn misfire(&self) -> Misfire {
+        self.job.misfire()
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        self.job.load_policy()
+    }
+
+    fn durable(&self) -> bool {
+        self.job.durable()
+    }
+
+    fn dedup(&self) -> bool {
+        self.job.dedup()
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+const SMOOTHING: f64 = 0.3;
+const DEVIATIONS: f64 = 2.0;
+
+#[derive(Default)]
+pub(crate) struct FinishBy {
+    average: Option<f64>,
+    deviation: f64,
+}
+
+impl FinishBy {
+    pub(crate) fn record(&mut self, duration: Duration) {
+        let duration = duration.as_secs_f64();
+        let Some(average) = self.average else {
+            self.average = Some(duration);
+            self.deviation = duration / 2.0;
+            return;
+        };
+        self.deviation += SMOOTHING * ((duration - average).abs() - self.deviation);
+        self.average = Some(average + SMOOTHING * (duration - average));
+    }
+
+    pub(crate) fn lead(&self, period: Duration) -> Duration {
+        let Some(average) = self.average else {
+            return Duration::ZERO;
+        };
+        Duration::from_secs_f64(average + DEVIATIONS * self.deviation).min(period)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{Layer, Next, Run};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::select;
+
+#[derive(Clone, Default)]
+pub(crate) struct Groups(Arc<Mutex<HashMap<String, Exclusive>>>);
+
+impl Groups {
+    pub(crate) fn get(&self, name: &str) -> Exclusive {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Exclusive(Arc<tokio::sync::Mutex<()>>);
+
+impl Layer for Exclusive {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let token = next.context().token();
+            select! {
+                biased;
+                _ = token.cancelled() => next.context().skip(),
+                _guard = self.0.lock() => next.run().await,
+            }
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use crate::BackfillReport;
+use crate::message::Post;
+use crate::output::{self, Outputs};
+use crate::status::JobShared;
+use crate::{Job, JobOptions, JobStats, MessageJob, OutputJob, Token, TriggerDecision};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::sync::broadcast;
+
+pub(crate) type Swap<J> = Arc<Mutex<Option<J>>>;
+
+pub struct JobHandle<J> {
+    pub(crate) shared: Arc<JobShared>,
+    swap: Swap<J>,
+    outputs: Option<Outputs>,
+    post: Option<Post>,
+}
+
+impl<J> Clone for JobHandle<J> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            swap: self.swap.clone(),
+            outputs: self.outputs.clone(),
+            post: self.post.clone(),
+        }
+    }
+}
+
+impl<J: Job> JobHandle<J> {
+    pub(crate) fn new(
+        shared: Arc<JobShared>,
+        swap: Swap<J>,
+        outputs: Option<Outputs>,
+        post: Option<Post>,
+    ) -> Self {
+        Self {
+            shared,
+            swap,
+            outputs,
+            post,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    pub fn stats(&self) -> JobStats {
+        self.shared.stats.lock().unwrap().clone()
+    }
+
+    pub fn replace(&self, job: J) {
+        *self.swap.lock().unwrap() = Some(job);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.shared.enabled()
+    }
+
+    pub fn enable(&self) {
+        self.shared.set_enabled(true);
+    }
+
+    pub fn disable(&self) {
+        self.shared.set_enabled(false);
+    }
+
+    pub fn trigger(&self) {
+        self.shared.trigger_now();
+    }
+
+    pub fn trigger_now(&self) -> TriggerDecision {
+        self.shared.trigger_now()
+    }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.shared.is_quarantined()
+    }
+
+    pub fn unquarantine(&self) -> bool {
+        self.shared.unquarantine()
+    }
+
+    pub fn run_token(&self) -> Option<Token> {
+        self.shared.run.lock().unwrap().clone()
+    }
+
+    pub fn cancel_run(&self) -> bool {
+        self.shared.cancel_run()
+    }
+
+    pub fn backfill(
+        &self,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> impl Future<Output = Option<BackfillReport>> + Send + 'static {
+        let report = self.shared.backfills.push(from, to);
+        async move { report.await.ok() }
+    }
+
+    pub fn finished(&self) -> impl Future<Output = JobStats> + Send + 'static {
+        let shared = self.shared.clone();
+        let mut finished = shared.finished.subscribe();
+        async move {
+            let _ = finished.wait_for(|finished| *finished).await;
+            shared.stats.lock().unwrap().clone()
+        }
+    }
+
+    pub fn options(&self) -> JobOptions {
+        self.shared.options.borrow().clone()
+    }
+
+    pub fn configure(&self, options: JobOptions) {
+        self.shared.options.send_replace(options);
+    }
+}
+
+impl<J: OutputJob> JobHandle<J> {
+    pub fn outputs(&self) -> broadcast::Receiver<J::Output> {
+        output::subscribe(self.outputs.as_ref())
+    }
+}
+
+impl<J: MessageJob> JobHandle<J> {
+    pub fn send(&self, message: J::Message) -> Result<(), J::Message> {
+        match &self.post {
+            Some(post) => post.send(message),
+            None => Err(message),
+        }
+    }
+}
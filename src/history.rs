@@ -0,0 +1,107 @@
+use crate::{RunStatus, Store};
+use std::io;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RunRecord {
+    pub run_id: u64,
+    pub tick: SystemTime,
+    pub started: SystemTime,
+    pub duration: Duration,
+    pub status: RunStatus,
+    pub panicked: bool,
+    pub shadow: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    max_runs: Option<usize>,
+    max_age: Option<Duration>,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Retention {
+    pub fn new() -> Self {
+        Self {
+            max_runs: Some(100),
+            max_age: None,
+        }
+    }
+
+    pub fn max_runs(mut self, runs: usize) -> Self {
+        self.max_runs = Some(runs);
+        self
+    }
+
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.max_age = Some(age);
+        self
+    }
+
+    pub fn unbounded(self) -> Self {
+        Self {
+            max_runs: None,
+            max_age: None,
+        }
+    }
+
+    fn apply(&self, runs: &mut Vec<RunRecord>, now: SystemTime) {
+        if let Some(cutoff) = self.max_age.and_then(|age| now.checked_sub(age)) {
+            runs.retain(|run| run.started >= cutoff);
+        }
+        if let Some(max_runs) = self.max_runs {
+            let excess = runs.len().saturating_sub(max_runs);
+            runs.drain(..excess);
+        }
+    }
+}
+
+pub(crate) struct RunHistory {
+    store: Arc<dyn Store>,
+    key: String,
+    retention: Retention,
+}
+
+impl RunHistory {
+    pub(crate) fn new(store: Arc<dyn Store>, name: &str, retention: Retention) -> Self {
+        Self {
+            store,
+            key: key(name),
+            retention,
+        }
+    }
+
+    pub(crate) async fn record(&self, run: RunRecord) {
+        let now = run.started + run.duration;
+        let result = async {
+            let mut runs = load(&*self.store, &self.key).await?;
+            runs.push(run);
+            self.retention.apply(&mut runs, now);
+            self.store.save(&self.key, serde_json::to_vec(&runs)?).await
+        }
+        .await;
+        #[cfg(feature = "tracing")]
+        if let Err(error) = result {
+            tracing::warn!(key = self.key, %error, "failed to record run history");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = result;
+    }
+}
+
+pub(crate) fn key(name: &str) -> String {
+    format!("runs/{name}")
+}
+
+pub(crate) async fn load(store: &dyn Store, key: &str) -> io::Result<Vec<RunRecord>> {
+    match store.load(key).await? {
+        Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        None => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,80 @@
+use crate::Schedule;
+use std::fmt;
+use std::ops::Range;
+use std::time::{Duration, SystemTime};
+
+const NANO: Duration = Duration::from_nanos(1);
+const MAX_FIRES: usize = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    NotAfter {
+        after: SystemTime,
+        next: SystemTime,
+    },
+    Skipped {
+        after: SystemTime,
+        expected: SystemTime,
+        next: Option<SystemTime>,
+    },
+    TooMany {
+        fires: usize,
+    },
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAfter { after, next } => {
+                write!(f, "next fire {next:?} is not after {after:?}")
+            }
+            Self::Skipped {
+                after,
+                expected,
+                next: Some(next),
+            } => write!(
+                f,
+                "next fire after {after:?} is {next:?}, expected {expected:?}"
+            ),
+            Self::Skipped {
+                after, expected, ..
+            } => write!(f, "no fire after {after:?}, expected {expected:?}"),
+            Self::TooMany { fires } => write!(f, "more than {fires} fires in range"),
+        }
+    }
+}
+
+impl std::error::Error for InvariantViolation {}
+
+pub fn check_schedule<S: Schedule + ?Sized>(
+    schedule: &S,
+    range: Range<SystemTime>,
+) -> Result<Vec<SystemTime>, InvariantViolation> {
+    let mut fires = Vec::new();
+    let mut after = range.start;
+    while let Some(next) = schedule.next_after(after) {
+        if next <= after {
+            return Err(InvariantViolation::NotAfter { after, next });
+        }
+        if next >= range.end {
+            break;
+        }
+        let midpoint = after + (next.duration_since(after).unwrap_or_default() / 2);
+        for probe in [midpoint, next - NANO] {
+            let found = schedule.next_after(probe);
+            if found != Some(next) {
+                return Err(InvariantViolation::Skipped {
+                    after: probe,
+                    expected: next,
+                    next: found,
+                });
+            }
+        }
+        if fires.len() == MAX_FIRES {
+            return Err(InvariantViolation::TooMany { fires: MAX_FIRES });
+        }
+        fires.push(next);
+        after = next;
+    }
+    Ok(fires)
+}
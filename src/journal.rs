@@ -0,0 +1,267 @@
+use crate::{Event, EventKind, RunStatus, SkipReason, TriggerDecision};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Value, json};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+type Rotate = Box<dyn FnMut() -> io::Result<Box<dyn Write + Send>> + Send>;
+
+pub struct EventLog {
+    writer: Box<dyn Write + Send>,
+    written: u64,
+    max_bytes: Option<u64>,
+    rotate: Option<Rotate>,
+}
+
+impl EventLog {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+            written: 0,
+            max_bytes: None,
+            rotate: None,
+        }
+    }
+
+    pub fn file(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = append(&path)?;
+        let written = file.metadata()?.len();
+        let rotate = move || {
+            let stamp = DateTime::<Utc>::from(SystemTime::now()).format("%Y%m%dT%H%M%S%.3fZ");
+            let mut rotated = path.clone().into_os_string();
+            rotated.push(format!(".{stamp}"));
+            fs::rename(&path, rotated)?;
+            Ok(Box::new(append(&path)?) as Box<dyn Write + Send>)
+        };
+        Ok(Self {
+            written,
+            ..Self::new(file).on_rotate(rotate)
+        })
+    }
+
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    pub fn on_rotate(
+        mut self,
+        rotate: impl FnMut() -> io::Result<Box<dyn Write + Send>> + Send + 'static,
+    ) -> Self {
+        self.rotate = Some(Box::new(rotate));
+        self
+    }
+
+    pub(crate) fn write(&mut self, event: &Event) {
+        let mut line = line(event).to_string();
+        line.push('\n');
+        let len = line.len() as u64;
+        if self.written > 0
+            && self.max_bytes.is_some_and(|max| self.written + len > max)
+            && let Some(rotate) = &mut self.rotate
+        {
+            let _ = self.writer.flush();
+            match rotate() {
+                Ok(writer) => {
+                    self.writer = writer;
+                    self.written = 0;
+                }
+                Err(_error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_error, "failed to rotate event log");
+                }
+            }
+        }
+        if self
+            .writer
+            .write_all(line.as_bytes())
+            .and_then(|_| self.writer.flush())
+            .is_ok()
+        {
+            self.written += len;
+        }
+    }
+}
+
+fn append(path: &PathBuf) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn line(event: &Event) -> Value {
+    let millis = |duration: &Duration| duration.as_secs_f64() * 1000.0;
+    let timestamp = DateTime::<Utc>::from(event.at).to_rfc3339_opts(SecondsFormat::Millis, true);
+    let (kind, details) = match &event.kind {
+        EventKind::Started { run_id, shadow } => {
+            ("started", json!({ "run_id": run_id, "shadow": shadow }))
+        }
+        EventKind::Finished {
+            run_id,
+            duration,
+            panicked,
+            status,
+        } => (
+            "finished",
+            json!({
+                "run_id": run_id,
+                "duration_ms": millis(duration),
+                "panicked": panicked,
+                "status": status.label(),
+                "detail": status.detail(),
+            }),
+        ),
+        EventKind::Skipped { reason } => ("skipped", json!({ "reason": skip_reason(*reason) })),
+        EventKind::BudgetExhausted { used } => {
+            ("budget_exhausted", json!({ "used_ms": millis(used) }))
+        }
+        EventKind::Suspended { slept } => ("suspended", json!({ "slept_ms": millis(slept) })),
+        EventKind::PeriodFloored { requested, floor } => (
+            "period_floored",
+            json!({ "requested_ms": millis(requested), "floor_ms": millis(floor) }),
+        ),
+        EventKind::PeriodLimited { requested, limited } => (
+            "period_limited",
+            json!({ "requested_ms": millis(requested), "limited_ms": millis(limited) }),
+        ),
+        EventKind::Cancelled { interrupted } => {
+            ("cancelled", json!({ "interrupted": interrupted }))
+        }
+        EventKind::DeadlineMissed { run_id, late } => (
+            "deadline_missed",
+            json!({ "run_id": run_id, "late_ms": millis(late) }),
+        ),
+        EventKind::RunCancelled { run_id } => ("run_cancelled", json!({ "run_id": run_id })),
+        EventKind::LoadDelayed { waited } => {
+            ("load_delayed", json!({ "waited_ms": millis(waited) }))
+        }
+        EventKind::Quarantined { strikes } => ("quarantined", json!({ "strikes": strikes })),
+        EventKind::Triggered { decision } => (
+            "triggered",
+            json!({ "decision": trigger_decision(*decision) }),
+        ),
+    };
+    let mut line = json!({
+        "timestamp": timestamp,
+        "job": &*event.name,
+        "event": kind,
+    });
+    if let (Value::Object(line), Value::Object(details)) = (&mut line, details) {
+        line.extend(details);
+    }
+    line
+}
+
+pub(crate) fn parse(line: &str) -> Option<Event> {
+    let line: Value = serde_json::from_str(line).ok()?;
+    let u64_field = |field: &str| line.get(field)?.as_u64();
+    let bool_field = |field: &str| line.get(field)?.as_bool();
+    let str_field = |field: &str| line.get(field)?.as_str();
+    let millis = |field: &str| Some(Duration::from_secs_f64(line.get(field)?.as_f64()? / 1000.0));
+    let kind = match str_field("event")? {
+        "started" => EventKind::Started {
+            run_id: u64_field("run_id")?,
+            shadow: bool_field("shadow")?,
+        },
+        "finished" => {
+            let detail = str_field("detail").unwrap_or_default().to_string();
+            EventKind::Finished {
+                run_id: u64_field("run_id")?,
+                duration: millis("duration_ms")?,
+                panicked: bool_field("panicked")?,
+                status: match str_field("status")? {
+                    "success" => RunStatus::Success,
+                    "no_work" => RunStatus::NoWork,
+                    "partial_success" => RunStatus::PartialSuccess(detail),
+                    "failed" => RunStatus::Failed(detail),
+                    _ => return None,
+                },
+            }
+        }
+        "skipped" => EventKind::Skipped {
+            reason: parse_skip_reason(str_field("reason")?)?,
+        },
+        "budget_exhausted" => EventKind::BudgetExhausted {
+            used: millis("used_ms")?,
+        },
+        "suspended" => EventKind::Suspended {
+            slept: millis("slept_ms")?,
+        },
+        "period_floored" => EventKind::PeriodFloored {
+            requested: millis("requested_ms")?,
+            floor: millis("floor_ms")?,
+        },
+        "period_limited" => EventKind::PeriodLimited {
+            requested: millis("requested_ms")?,
+            limited: millis("limited_ms")?,
+        },
+        "cancelled" => EventKind::Cancelled {
+            interrupted: bool_field("interrupted")?,
+        },
+        "deadline_missed" => EventKind::DeadlineMissed {
+            run_id: u64_field("run_id")?,
+            late: millis("late_ms")?,
+        },
+        "run_cancelled" => EventKind::RunCancelled {
+            run_id: u64_field("run_id")?,
+        },
+        "load_delayed" => EventKind::LoadDelayed {
+            waited: millis("waited_ms")?,
+        },
+        "quarantined" => EventKind::Quarantined {
+            strikes: u32::try_from(u64_field("strikes")?).ok()?,
+        },
+        "triggered" => EventKind::Triggered {
+            decision: match str_field("decision")? {
+                "accepted" => TriggerDecision::Accepted,
+                "queued" => TriggerDecision::Queued,
+                "coalesced" => TriggerDecision::Coalesced,
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+    Some(Event {
+        name: str_field("job")?.into(),
+        kind,
+        at: DateTime::parse_from_rfc3339(str_field("timestamp")?)
+            .ok()?
+            .to_utc()
+            .into(),
+    })
+}
+
+fn trigger_decision(decision: TriggerDecision) -> &'static str {
+    match decision {
+        TriggerDecision::Accepted => "accepted",
+        TriggerDecision::Queued => "queued",
+        TriggerDecision::Coalesced => "coalesced",
+    }
+}
+
+pub(crate) fn skip_reason(reason: SkipReason) -> &'static str {
+    match reason {
+        SkipReason::Sampled => "sampled",
+        SkipReason::Budget => "budget",
+        SkipReason::Suspended => "suspended",
+        SkipReason::Disabled => "disabled",
+        SkipReason::Duplicate => "duplicate",
+        SkipReason::Late => "late",
+        SkipReason::Load => "load",
+    }
+}
+
+fn parse_skip_reason(reason: &str) -> Option<SkipReason> {
+    let reason = match reason {
+        "sampled" => SkipReason::Sampled,
+        "budget" => SkipReason::Budget,
+        "suspended" => SkipReason::Suspended,
+        "disabled" => SkipReason::Disabled,
+        "duplicate" => SkipReason::Duplicate,
+        "late" => SkipReason::Late,
+        "load" => SkipReason::Load,
+        _ => return None,
+    };
+    Some(reason)
+}
@@ -0,0 +1,52 @@
+use crate::{Job, RunContext};
+use std::pin::Pin;
+use std::sync::Arc;
+
+pub type Run<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+pub trait Layer: Send + Sync + 'static {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a>;
+}
+
+pub(crate) type Layers = Arc<Vec<Arc<dyn Layer>>>;
+
+pub(crate) trait DynJob: Send {
+    fn run_boxed(&mut self, ctx: RunContext) -> Run<'_>;
+}
+
+impl<J: Job> DynJob for J {
+    fn run_boxed(&mut self, ctx: RunContext) -> Run<'_> {
+        Box::pin(self.run_with_context(ctx))
+    }
+}
+
+pub struct Next<'a> {
+    job: &'a mut dyn DynJob,
+    layers: &'a [Arc<dyn Layer>],
+    ctx: RunContext,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        job: &'a mut dyn DynJob,
+        layers: &'a [Arc<dyn Layer>],
+        ctx: RunContext,
+    ) -> Self {
+        Self { job, layers, ctx }
+    }
+
+    pub fn context(&self) -> &RunContext {
+        &self.ctx
+    }
+
+    pub fn run(self) -> Run<'a> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => layer.call(Next {
+                job: self.job,
+                layers,
+                ctx: self.ctx,
+            }),
+            None => self.job.run_boxed(self.ctx),
+        }
+    }
+}
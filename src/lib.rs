@@ -8,7 +8,84 @@
 //! - **Periodic Job Execution**: Define jobs that run at regular intervals
 //! - **Asynchronous Execution**: Based on Tokio, schedule jobs in a non-blocking manner
 //! - **Truncated Run Time**: Jobs can be configured to run at truncated time intervals, ensuring precise timing
-//! - **Graceful Shutdown**: Gracefully stop the scheduler using either a `Ctrl+C` signal or a cancellation token
+//! - **Graceful Shutdown**: Gracefully stop the scheduler on `SIGTERM`/`SIGINT` (`Ctrl+C` off Unix), a cancellation token, or both
+//! - **Shutdown Report**: Jobs that panicked are collected and returned by name when the scheduler stops
+//! - **Supervision**: Panicking jobs can be restarted with backoff, escalating to their group or the whole scheduler when they keep failing
+//! - **Child Schedulers**: Group jobs into child schedulers that stop as a unit and shut down with their parent
+//! - **Drop Guard**: Tie the scheduler lifetime to a guard that cancels and waits for the jobs when dropped
+//! - **Shared State**: Hand shared application state to every job run through the run context
+//! - **Layers**: Wrap job runs with composable middleware, per job or for the whole scheduler
+//! - **Rate Limit**: Token bucket layer limiting runs per time window, optionally shared across jobs
+//! - **Shared Permits**: Hold a permit from a semaphore or custom provider shared with the rest of the application for every run
+//! - **Exclusion Groups**: Jobs sharing an exclusion group never run concurrently
+//! - **Tracing**: Job tasks are instrumented with spans at a per-job level and named for `tokio-console` behind the `tracing` feature
+//! - **Alerting**: Notify on consecutive failures or stale jobs through a pluggable `Notifier`
+//! - **StatsD Metrics**: Export run counters and timings to StatsD/DogStatsD over UDP
+//! - **Metrics Facade**: Record scheduler metrics through the `metrics` crate behind the `metrics` feature
+//! - **Status**: Per-job stats with separate liveness and readiness (critical jobs) for health probes
+//! - **Job Handles**: Inspect a running job and hot-swap its implementation between ticks
+//! - **Sampling**: Run a job only on every nth tick or on a deterministic or random fraction of ticks
+//! - **Adaptive Period**: Automatically back off slow jobs to keep their duty cycle under a limit
+//! - **Runtime Budget**: Cap the cumulative run time of a job per window, skipping ticks once exhausted
+//! - **Events**: Subscribe to job lifecycle events (started, finished, skipped, ...)
+//! - **Suspend Awareness**: Detect host suspension while waiting for a tick and run, realign or skip accordingly
+//! - **Local Time Truncation**: Align truncated run times to local time instead of UTC
+//! - **Period Parsing**: Parse humantime-style periods like `1h30m`, with optional serde support
+//! - **Job Options**: Override job settings from code or, with the `serde` feature, from TOML/YAML/JSON config
+//! - **Config File**: Load job options from a TOML/YAML file and hot reload them without restarting, behind the `config` feature
+//! - **Environment Overrides**: Override job options from `APJ_JOB_<NAME>_<OPTION>` environment variables at spawn
+//! - **Enable/Disable**: Turn jobs off at spawn, at runtime or from config while keeping their registration and stats
+//! - **State Checkpointing**: Persist the state of stateful jobs to a pluggable store after each successful run and restore it on startup
+//! - **Durable Runs**: Record run intents in the store for at-least-once execution across crashes
+//! - **Idempotency Keys**: Per-tick idempotency keys in the run context, with optional store-backed deduplication
+//! - **Misfire Policies**: Fire once, fire all or skip ticks missed during overruns, pauses or downtime
+//! - **DST-Safe Truncation**: Wall-clock truncation in local or IANA time zones with explicit policies for skipped and repeated times
+//! - **Validated Spawn**: `try_spawn` rejects invalid job configurations and duplicate names up front, and reports a missing runtime instead of panicking
+//! - **Error Type**: A crate-wide `Error` enum to match on failure categories
+//! - **Tenants**: Named sub-schedulers with concurrency and rate quotas, torn down individually
+//! - **Job Factories**: Create a fresh job instance for every run
+//! - **Deadline Shutdown**: Abort jobs still running after a stop deadline, with per-job opt-out, exposing the deadline to cancelled runs
+//! - **Scoped Jobs**: Run closure jobs borrowing from the caller's stack, polled inline until a token is cancelled
+//! - **Console Reporter**: Periodically print a table of jobs with their last and next runs to stdout, stderr or any writer
+//! - **Triggers**: Run a job on demand from its handle, with debounce and throttle to absorb event storms, queueing one run behind an active one
+//! - **Typed Outputs**: Publish the output of each successful run on a broadcast channel available from the job handle
+//! - **Cached Values**: Keep the latest output of a job in a watch channel with staleness metadata
+//! - **Startup Jobs**: Run one-off jobs such as migrations before any periodic tick, aborting startup when one fails
+//! - **Dry Runs**: Run jobs in shadow mode, scheduler-wide or per job, to validate them without side effects
+//! - **Schedules**: Fire jobs on aligned, offset and combined schedules instead of a single period, restricted to time windows and weekdays
+//! - **Backfill**: Replay the scheduled ticks of a historical range through a job handle
+//! - **Cron Expressions**: Schedule jobs with classic or Quartz cron expressions, including seconds, aliases and `L`/`W`/`#` modifiers
+//! - **Calendar Expressions**: Schedule jobs with systemd `OnCalendar` expressions
+//! - **Schedule Phrases**: Describe schedules in plain English such as "every weekday at 09:30" behind the `natural` feature
+//! - **Repeating Intervals**: Schedule jobs with ISO 8601 `R[n]/start/duration` repeating intervals
+//! - **Schedule Descriptions**: Explain any schedule in plain text, along with its next fire time
+//! - **Fleet Stagger**: Spread truncated jobs across instances with a stable per-host offset within the period
+//! - **Simulation**: Run a scheduler against a virtual clock and assert exact fire times and outcomes without sleeping, behind the `simulation` feature
+//! - **Test Helpers**: Configurable mock jobs and scenario builders for testing scheduler wiring, behind the `testing` feature
+//! - **Schedule Invariants**: Enumerate fire times and check that a schedule is monotonic, bounded and never skips a fire, ready for property-based tests
+//! - **Runtime Shards**: Spread job tasks across several runtimes by name hash or explicit shard key
+//! - **Spread**: Spread truncated jobs sharing a period evenly across it, by spawn order or name hash
+//! - **Finish-By Truncation**: Start truncated runs early enough to finish by the boundary, learning the lead from past run durations
+//! - **Completion Deadlines**: Report runs finishing too long after their tick and optionally skip runs that would start too late
+//! - **Batched Work**: Accumulate work tokens from anywhere and flush them to a batch job at a coarser interval
+//! - **Stream-Driven Jobs**: Run a job for each item of a stream, spacing runs by a minimum interval and coalescing bursts
+//! - **Server Lifecycle**: Run a web server such as axum next to the scheduler and shut both down in order on one signal or token
+//! - **Job Messages**: Send typed messages to a job through its handle, handled between runs
+//! - **State Snapshots**: Export and import job options, enabled flags and last-run info across restarts or hosts
+//! - **JSON Event Log**: Write every lifecycle event as a JSON line to a writer or rotating file
+//! - **Load Gates**: Skip or delay low-priority jobs while the host is under CPU, memory or custom pressure
+//! - **Job Templates**: Spawn one instance of a parameterized job per parameter and operate on them together
+//! - **Job Labels**: Tag jobs with key/value labels surfaced in status, metrics and job filters
+//! - **Cold-start Ramp-up**: Start jobs gradually over a window after boot instead of all at once
+//! - **Chunked Jobs**: Resume long jobs from a cursor on the next tick or right away, processing bounded slices per run
+//! - **Quarantine**: Stop scheduling a job after repeated panics or missed deadlines until it is released from its handle
+//! - **Abort on Panic**: Shut the whole scheduler down on the first job panic and fail `try_wait`, for tests and batch runs
+//! - **Run History**: Persist finished runs to the store with a retention policy and read them back across restarts
+//! - **Event Log Replay**: Re-derive which ticks ran, were skipped or were missed, and why, from an exported event log
+//! - **Typed Job Errors**: Fallible jobs with their own error type, typed per-job error handlers and scheduler-wide handlers over `dyn Error`
+//! - **Chaos Testing**: Optional layer that randomly delays, fails, panics or cancels runs with seeded, reproducible probabilities
+//! - **Readiness Gate**: Hold every first tick until an external condition such as a migration, config load or leader election resolves
+//! - **Timer Strategy**: Wait on per-job timers or a shared timer driver, picked by job count and wait length or set explicitly, with criterion benchmarks at 1k/10k/100k jobs
 //!
 //! ## Quick Started
 //!
@@ -19,12 +96,19 @@
 //! async-periodic-job = "0.1.3"
 //! ```
 //!
+//! The only default feature is `signal`, which provides `wait`, `try_wait`, `wait_with`, `wait_ctrl_c` and `serve`, the methods that listen for shutdown signals. Embedded and other minimal builds can turn default features off and keep the job loop, cancellation tokens, the task tracker and the schedules, on a tokio without its signal driver. Without `signal`, those methods don't exist and the scheduler is stopped with `wait_cancel`, `wait_until`, `serve_cancel` or `stop`:
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", default-features = false }
+//! ```
+//!
 //! ### Usage
 //!
 //! #### Base Usage
 //!
-//! ```rust
-//! use async_periodic_job::{Job, Scheduler};
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, TruncateZone};
 //! use std::time::Duration;
 //!
 //! // Define a job without options
@@ -48,6 +132,12 @@
 //!         false
 //!     }
 //!
+//!     // Align truncation to UTC (Unix epoch) or to local time, default: `TruncateZone::Utc`
+//!     // e.g. with `TruncateZone::Local`, a 6h period runs at 00:00, 06:00, 12:00 and 18:00 local time
+//!     fn truncate_zone(&self) -> TruncateZone {
+//!         TruncateZone::Utc
+//!     }
+//!
 //!     // Job run
 //!     async fn run(&mut self) {
 //!         // ...
@@ -101,9 +191,34 @@
 //! }
 //! ```
 //!
+//! #### Shutdown signals
+//!
+//! `wait` and `try_wait` stop the scheduler on `SIGTERM` or `SIGINT` on Unix, and on `Ctrl+C` elsewhere, so a container runtime or process supervisor asking the process to exit drains the jobs the same way an interactive `Ctrl+C` does. `wait_with` and `try_wait_with` also take an optional token and stop on whichever comes first, for an application that has its own shutdown path but still wants the signals handled. `wait_ctrl_c` keeps listening for `Ctrl+C` alone. If the signals can't be listened to, `try_wait` and `try_wait_with` return `Error::Signal`, while `wait`, `wait_with` and `wait_ctrl_c` keep running until the scheduler is cancelled. All of them need the default `signal` feature:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, Token};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let token = Token::new();
+//!     let report = Scheduler::new()
+//!         .spawn(JobImpl)
+//!         .wait_with(Some(token))
+//!         .await;
+//!     assert!(report.is_ok());
+//! }
+//! ```
+//!
 //! #### Spawn job with cancel
 //!
-//! ```rust
+//! ```rust,no_run
 //! use async_periodic_job::{Job, Scheduler, Token};
 //!
 //! // Define a job with cancel
@@ -111,7 +226,7 @@
 //! impl Job for JobImpl {
 //!     // If run job with cancel, default: false
 //!     // When this method returned true, job method `run_with_cancel` will be executed instead of `run`
-//!     fn with_with_cancel(&self) -> bool {
+//!     fn with_cancel(&self) -> bool {
 //!         true
 //!     }
 //!
@@ -135,103 +250,5781 @@
 //! }
 //! ```
 //!
-//! ## License
+//! #### Shutdown report
 //!
-//! MIT
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, Token};
+//! use std::time::Duration;
+//! use tokio::time::sleep;
 //!
-//! Contributions and suggestions are welcome!
-
-use std::time::{Duration, SystemTime};
-use tokio::time::sleep;
-use tokio::{select, signal};
-use tokio_util::sync::CancellationToken;
-use tokio_util::task::TaskTracker;
-
-pub type Token = CancellationToken;
-
-pub trait Job: Send + 'static {
-    fn period(&self) -> Duration {
-        Duration::from_secs(1)
-    }
-
-    fn with_truncate_time(&self) -> bool {
-        true
-    }
-
-    fn run(&mut self) -> impl Future<Output = ()> + Send {
-        async {}
-    }
-
-    fn with_cancel(&self) -> bool {
-        false
-    }
-
-    fn run_with_cancel(&mut self, _token: Token) -> impl Future<Output = ()> + Send {
-        async {}
-    }
-}
-
-pub struct Scheduler {
-    tracker: TaskTracker,
-    token: Token,
-}
-
-impl Scheduler {
-    pub fn new() -> Self {
-        Self {
-            tracker: TaskTracker::new(),
-            token: Token::new(),
-        }
-    }
-
-    pub fn spawn(self, mut job: impl Job) -> Self {
-        let period = job.period();
-        let token = self.token.clone();
-        self.tracker.spawn(async move {
-            loop {
-                let period = if job.with_truncate_time() {
-                    Self::truncate_period(period)
-                } else {
-                    period
-                };
-                select! {
-                    _ = token.cancelled() => break,
-                    _ = sleep(period) =>  {
-                        if job.with_cancel() {
-                            job.run_with_cancel(token.child_token()).await;
-                        } else {
-                            job.run().await
-                        }
-                    }
-                };
-            }
-        });
-        self
-    }
-
-    pub async fn stop(self) {
-        self.tracker.close();
-        self.token.cancel();
-        self.tracker.wait().await;
-    }
-
-    pub async fn wait(self) {
-        signal::ctrl_c().await.unwrap();
-        self.stop().await;
-    }
-
-    pub async fn wait_cancel(self, token: CancellationToken) {
-        token.cancelled().await;
-        self.stop().await;
-    }
-
-    fn truncate_period(period: Duration) -> Duration {
-        let period = period.as_nanos();
-        let epoch = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let nanos = period - epoch % period;
-        Duration::from_nanos(nanos as u64)
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Job name used in reports, default: type name of the job
+//!     fn name(&self) -> &str {
+//!         "job-impl"
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         panic!("something went wrong");
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let token = Token::new();
+//!     let token_copy = token.clone();
+//!     tokio::spawn(async move {
+//!         sleep(Duration::from_secs(2)).await;
+//!         token_copy.cancel();
+//!     });
+//!
+//!     // `stop`, `wait` and `wait_cancel` report jobs that panicked or were aborted
+//!     let report = Scheduler::new()
+//!         .spawn(JobImpl)
+//!         .wait_cancel(token)
+//!         .await;
+//!     assert!(!report.is_ok());
+//!     assert_eq!(report.failures[0].name, "job-impl");
+//! }
+//! ```
+//!
+//! The report also lists in `interrupted` the jobs that were mid-run when cancellation arrived. Their run either finished after the token was cancelled or was aborted at the deadline, so that work may be incomplete. Jobs that were waiting for their next tick lose nothing. Each job emits one `EventKind::Cancelled` event when it stops, with `interrupted` telling the two cases apart, and `JobStats::interrupted_runs` counts the runs a cancellation interrupted.
+//!
+//! #### Supervised restarts
+//!
+//! ```rust
+//! use async_periodic_job::{Escalation, Job, Scheduler, Supervisor};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn with_truncate_time(&self) -> bool {
+//!         false
+//!     }
+//!
+//!     // Restart the job loop after a panic, default: None (the job exits on panic)
+//!     // Restarts back off exponentially, and when more than `intensity` restarts happen
+//!     // within the window, the failure escalates (here: the whole scheduler stops)
+//!     fn supervisor(&self) -> Option<Supervisor> {
+//!         Some(
+//!             Supervisor::new()
+//!                 .backoff(Duration::from_millis(100), Duration::from_secs(1))
+//!                 .intensity(2, Duration::from_secs(10))
+//!                 .escalate(Escalation::Scheduler),
+//!         )
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         panic!("something went wrong");
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let report = Scheduler::new()
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//!     assert_eq!(report.failures.len(), 1);
+//! }
+//! ```
+//!
+//! `Escalation` picks how far a failure goes once the restart intensity is exceeded. `Job` only stops the failing job. `Group` cancels the scheduler the job was spawned on, together with its children, so a child scheduler works as a supervision group that fails as a unit while the rest of the tree keeps running. `Scheduler` cancels the whole tree from the root scheduler. A restart reuses the same job instance, so state left behind by the panic carries over into the next run. Jobs that must start clean after a panic should be spawned with `spawn_factory`, which creates a fresh instance for every run and therefore every restart.
+//!
+//! #### Quarantine
+//!
+//! A supervised job that keeps panicking is restarted over and over. `Job::quarantine` (also the `quarantine` job option and `QUARANTINE_AFTER` variable) takes it out of rotation instead: after the given number of consecutive strikes, either panics or runs finishing past their completion deadline, the job stops being scheduled, `JobStatus::quarantined` is set, an `EventKind::Quarantined` event is emitted and the notifiers get an `AlertKind::Quarantined` alert. The panic that quarantines the job is not handed to the supervisor, and the job stays alive until `JobHandle::unquarantine` puts it back on its schedule with a clean slate:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Quarantine, Scheduler, Supervisor};
+//! use std::time::Duration;
+//!
+//! struct Flaky;
+//! impl Job for Flaky {
+//!     fn supervisor(&self) -> Option<Supervisor> {
+//!         Some(Supervisor::new())
+//!     }
+//!
+//!     fn quarantine(&self) -> Option<Quarantine> {
+//!         Some(Quarantine::after(5))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(Flaky);
+//!     // Later, once the cause is fixed
+//!     tokio::time::sleep(Duration::from_secs(3600)).await;
+//!     if handle.is_quarantined() {
+//!         handle.unquarantine();
+//!     }
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! A successful run, or one that fails without panicking, resets the count. Quarantined jobs show up as `quarantined` in the console and pending triggers wait until the job is released.
+//!
+//! #### Abort on panic
+//!
+//! Test suites and batch binaries usually want the opposite of a supervisor: the first panic should stop everything and fail the process. `Scheduler::abort_on_panic` turns any job panic in the scheduler and its children into a full shutdown: the panicking job exits without restarts or quarantine, the other jobs are cancelled, and `try_wait` returns `Error::Jobs` with the failures instead of a report, so `?` makes the binary exit with a non-zero status. `wait` and `wait_with` never panic: they still return the report, with the panics in its `failures`:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Error, Job, Scheduler};
+//!
+//! struct Import;
+//! impl Job for Import {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     Scheduler::new()
+//!         .abort_on_panic()
+//!         .spawn(Import)
+//!         .try_wait()
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Startup jobs already cancel the scheduler when they panic, with or without the option.
+//!
+//! #### Serving alongside a web server
+//!
+//! `serve` runs a web server next to the scheduler and shuts both down on `SIGTERM` or `SIGINT` (`serve_cancel` uses a token instead), in order: the server gets a shutdown future to stop accepting and drain its connections, and once it has returned the scheduler is stopped and drained too. If the server fails, or the scheduler is cancelled on its own, the other side follows. With axum, the closure is `|shutdown| axum::serve(listener, app).with_graceful_shutdown(shutdown).into_future()`; any server taking a shutdown future works the same way:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//! use tokio::net::TcpListener;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {}
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+//!     let token = CancellationToken::new();
+//!     let stop = token.clone();
+//!     tokio::spawn(async move {
+//!         tokio::time::sleep(Duration::from_millis(50)).await;
+//!         stop.cancel();
+//!     });
+//!     let report = Scheduler::new()
+//!         .spawn(JobImpl)
+//!         .serve_cancel(token, |shutdown| async move {
+//!             let mut shutdown = std::pin::pin!(shutdown);
+//!             loop {
+//!                 tokio::select! {
+//!                     _ = &mut shutdown => return Ok::<_, std::io::Error>(()),
+//!                     accepted = listener.accept() => drop(accepted?),
+//!                 }
+//!             }
+//!         })
+//!         .await
+//!         .unwrap();
+//!     assert!(report.is_ok());
+//! }
+//! ```
+//!
+//! #### Shutdown frameworks
+//!
+//! Under a shutdown framework the scheduler runs as one subsystem among others instead of owning the top-level wait loop. `wait_until` stops the scheduler when any future completes, such as the subsystem's shutdown request, or when the scheduler is cancelled on its own. With `tokio-graceful-shutdown`, registering it as a nested subsystem looks like this, with failed or aborted jobs surfacing as a subsystem error through `into_result`:
+//!
+//! ```rust,ignore
+//! use async_periodic_job::{Error, Scheduler};
+//! use tokio_graceful_shutdown::{SubsystemBuilder, SubsystemHandle, Toplevel};
+//!
+//! async fn scheduler(subsys: SubsystemHandle) -> Result<(), Error> {
+//!     Scheduler::new()
+//!         .spawn(JobImpl)
+//!         .wait_until(subsys.on_shutdown_requested())
+//!         .await
+//!         .into_result()
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Toplevel::new(|s| async move {
+//!         s.start(SubsystemBuilder::new("scheduler", scheduler));
+//!     })
+//!     .catch_signals()
+//!     .handle_shutdown_requests(Duration::from_secs(30))
+//!     .await
+//!     .unwrap();
+//! }
+//! ```
+//!
+//! `wait_until` accepts any future, so the same pattern fits other frameworks and hand-rolled shutdown coordinators.
+//!
+//! #### Child schedulers
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct JobImplA;
+//! impl Job for JobImplA {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! struct JobImplB;
+//! impl Job for JobImplB {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(JobImplA);
+//!
+//!     // A child scheduler manages its own set of jobs and can be stopped as a unit,
+//!     // it is also stopped (and waited for) when the parent scheduler stops
+//!     let child = scheduler.child().spawn(JobImplB);
+//!     child.stop().await;
+//!
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Guarded scheduler
+//!
+//! ```rust
+//! use async_periodic_job::{Guard, Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! // A service owning its background jobs
+//! struct Service {
+//!     _jobs: Guard,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let service = Service {
+//!         _jobs: Scheduler::new().spawn_guarded(JobImpl),
+//!     };
+//!
+//!     // Dropping the guard cancels the jobs, and on a multi-thread runtime also waits for them to exit
+//!     drop(service);
+//! }
+//! ```
+//!
+//! Dropping a guard can only wait for the jobs on a multi-thread runtime, where it blocks the current worker with `block_in_place` until they exit. On a `current_thread` runtime (the default of `#[tokio::test]`) or outside any runtime, the drop cancels the jobs and returns right away without waiting, and with the `tracing` feature it logs a warning. A run may then still be in flight when the drop returns. Code that needs the jobs to be finished, or that runs on a `current_thread` runtime, should call `Guard::stop().await` instead, which waits on every runtime and returns the `StopReport`.
+//!
+//! #### Shared state
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::sync::Arc;
+//!
+//! struct Config {
+//!     endpoint: String,
+//! }
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Job run with context: gives access to the run token and the scheduler states
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         let config = ctx.state::<Config>().unwrap();
+//!         let _ = &config.endpoint;
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let config = Arc::new(Config {
+//!         endpoint: "http://localhost".to_string(),
+//!     });
+//!
+//!     // States are shared by all jobs (and child schedulers) spawned afterwards, one state per type
+//!     Scheduler::new()
+//!         .with_state(config)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Layers
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Layer, Next, Run, Scheduler};
+//! use std::time::Instant;
+//!
+//! // A layer wraps every run of a job, it runs the inner layers and the job via `next`
+//! struct Elapsed;
+//! impl Layer for Elapsed {
+//!     fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+//!         Box::pin(async move {
+//!             let name = next.context().name().to_string();
+//!             let start = Instant::now();
+//!             next.run().await;
+//!             println!("{name} took {:?}", start.elapsed());
+//!         })
+//!     }
+//! }
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Layers added to the scheduler wrap all jobs spawned afterwards,
+//!     // jobs can add their own (inner) layers by implementing `Job::layers`
+//!     Scheduler::new()
+//!         .layer(Elapsed)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Rate limit
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Layer, RateLimit, Scheduler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! struct JobImpl {
+//!     limit: RateLimit,
+//! }
+//! impl Job for JobImpl {
+//!     fn layers(&self) -> Vec<Arc<dyn Layer>> {
+//!         vec![Arc::new(self.limit.clone())]
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // call a fragile downstream api
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // At most 10 runs per minute, shared by all jobs holding a clone of the limit
+//!     let limit = RateLimit::new(10, Duration::from_secs(60));
+//!     Scheduler::new()
+//!         .spawn(JobImpl { limit: limit.clone() })
+//!         .spawn(JobImpl { limit })
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! A run waiting for the limit gives up when the scheduler stops or the run is cancelled. It never reaches the job, so it emits `EventKind::RunCancelled` instead of `Finished` and counts as neither a success nor a failure:
+//!
+//! ```rust
+//! use async_periodic_job::{EventKind, Job, Layer, RateLimit, Scheduler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! struct Report {
+//!     limit: RateLimit,
+//! }
+//! impl Job for Report {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn layers(&self) -> Vec<Arc<dyn Layer>> {
+//!         vec![Arc::new(self.limit.clone())]
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let mut events = scheduler.events();
+//!     let limit = RateLimit::new(1, Duration::from_secs(3600));
+//!     let report = scheduler.spawn_with_handle(Report { limit });
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     // The second run waits for the limit until the scheduler stops
+//!     scheduler.stop().await;
+//!     assert_eq!(report.stats().runs, 1);
+//!     let mut cancelled = 0;
+//!     while let Ok(event) = events.try_recv() {
+//!         if let EventKind::RunCancelled { .. } = event.kind {
+//!             cancelled += 1;
+//!         }
+//!     }
+//!     assert_eq!(cancelled, 1);
+//! }
+//! ```
+//!
+//! #### Shared permits
+//!
+//! `Permits` is a layer that makes each run hold a permit from a provider shared with the rest of the application, so scheduled jobs and request handlers hitting the same resource (a database pool, a downstream api) go through the same admission control. An `Arc<Semaphore>` is a provider out of the box, and any other source of permits can implement `PermitProvider`. A run waiting for a permit gives up when the scheduler is cancelled, and a provider returning `None` (such as a closed semaphore) skips the run:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Layer, Permits, Scheduler};
+//! use std::sync::Arc;
+//! use tokio::sync::Semaphore;
+//!
+//! struct JobImpl {
+//!     permits: Permits<Arc<Semaphore>>,
+//! }
+//! impl Job for JobImpl {
+//!     fn layers(&self) -> Vec<Arc<dyn Layer>> {
+//!         vec![Arc::new(self.permits.clone())]
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // query the database
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Also handed to the request handlers using the database
+//!     let pool = Arc::new(Semaphore::new(8));
+//!     let permits = Permits::new(pool.clone());
+//!     Scheduler::new()
+//!         .spawn(JobImpl { permits: permits.clone() })
+//!         .spawn(JobImpl { permits })
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! Either way the job doesn't run, so the run emits `EventKind::RunCancelled` instead of `Finished` and counts as neither a success nor a failure:
+//!
+//! ```rust
+//! use async_periodic_job::{EventKind, Job, Layer, Permits, Scheduler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use tokio::sync::Semaphore;
+//!
+//! struct JobImpl {
+//!     permits: Permits<Arc<Semaphore>>,
+//! }
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn layers(&self) -> Vec<Arc<dyn Layer>> {
+//!         vec![Arc::new(self.permits.clone())]
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // query the database
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let pool = Arc::new(Semaphore::new(8));
+//!     // The pool shuts down, no run gets a permit from now on
+//!     pool.close();
+//!     let scheduler = Scheduler::new();
+//!     let mut events = scheduler.events();
+//!     let job = scheduler.spawn_with_handle(JobImpl { permits: Permits::new(pool) });
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     scheduler.stop().await;
+//!     assert_eq!(job.stats().runs, 0);
+//!     let mut cancelled = 0;
+//!     while let Ok(event) = events.try_recv() {
+//!         if let EventKind::RunCancelled { .. } = event.kind {
+//!             cancelled += 1;
+//!         }
+//!     }
+//!     assert!(cancelled > 0);
+//! }
+//! ```
+//!
+//! #### Chaos testing
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["chaos"] }
+//! ```
+//!
+//! With the `chaos` feature, the `Chaos` layer injects faults into runs so a staging deployment can show that retries, supervisors and alerts react the way they should:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "chaos")]
+//! # mod gated {
+//! # use async_periodic_job::{Chaos, Job, Scheduler};
+//! # use std::time::Duration;
+//! #
+//! # struct JobImpl;
+//! # impl Job for JobImpl {
+//! #     async fn run(&mut self) {}
+//! # }
+//! #
+//! # #[tokio::main]
+//! # pub async fn main() {
+//! let chaos = Chaos::new(42)
+//!     // Hold 10% of the runs back by up to 30 seconds
+//!     .delay(0.1, Duration::from_secs(30))
+//!     // Report 5% as failed without running them, panic 1% and cancel 2% mid-flight
+//!     .fail(0.05)
+//!     .panic(0.01)
+//!     .cancel(0.02);
+//! Scheduler::new()
+//!     .layer(chaos)
+//!     .spawn(JobImpl)
+//!     .wait()
+//!     .await;
+//! # }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "chaos")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! Each fault gets its own roll, in order: delay, panic, failure, cancellation. An injected failure goes through `RunContext::fail`, so it reaches the `on_error` handlers and failure alerts. A panic goes to the supervisor like a real one. A cancellation cancels the run's token, as `JobHandle::cancel_run` does. A run held back by an injected delay gives up when the scheduler stops, and emits `EventKind::RunCancelled` like a run waiting on any other layer. The rolls come from a generator seeded by `Chaos::new`, so a given seed produces the same sequence of faults across runs. Clones share that sequence. As a scheduler layer it hits every job, and `Job::layers` scopes it to a single job. With the `tracing` feature, every injected fault is logged as a warning.
+//!
+//! #### Exclusion groups
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct ExportJob;
+//! impl Job for ExportJob {
+//!     // Jobs in the same exclusion group never run concurrently, default: None
+//!     fn exclusion_group(&self) -> Option<&str> {
+//!         Some("report-file")
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // write the report file
+//!     }
+//! }
+//!
+//! struct CleanupJob;
+//! impl Job for CleanupJob {
+//!     fn exclusion_group(&self) -> Option<&str> {
+//!         Some("report-file")
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // remove the report file
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(ExportJob)
+//!         .spawn(CleanupJob)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! A run waiting for its group gives up when the scheduler stops or the run is cancelled, and emits `EventKind::RunCancelled` instead of `Finished`, since the job never ran:
+//!
+//! ```rust
+//! use async_periodic_job::{EventKind, Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Export;
+//! impl Job for Export {
+//!     fn name(&self) -> &str {
+//!         "export"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     fn exclusion_group(&self) -> Option<&str> {
+//!         Some("report-file")
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         tokio::time::sleep(Duration::from_millis(100)).await;
+//!     }
+//! }
+//!
+//! struct Cleanup;
+//! impl Job for Cleanup {
+//!     fn name(&self) -> &str {
+//!         "cleanup"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn exclusion_group(&self) -> Option<&str> {
+//!         Some("report-file")
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let mut events = scheduler.events();
+//!     let export = scheduler.spawn_with_handle(Export);
+//!     scheduler.spawn_with_handle(Cleanup);
+//!     export.trigger();
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     // The export holds the group, a cleanup run is waiting for it when the scheduler stops
+//!     scheduler.stop().await;
+//!     assert_eq!(export.stats().runs, 1);
+//!     let mut cancelled = Vec::new();
+//!     while let Ok(event) = events.try_recv() {
+//!         if let EventKind::RunCancelled { .. } = event.kind {
+//!             cancelled.push(event.name);
+//!         }
+//!     }
+//!     assert_eq!(cancelled, ["cleanup".into()]);
+//! }
+//! ```
+//!
+//! #### Tracing
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["tracing"] }
+//! ```
+//!
+//! With the `tracing` feature, every job task is instrumented with a `job` span carrying the job name,
+//! and every run with a `run` span carrying its run id (also available as `RunContext::run_id`).
+//! When also built with `RUSTFLAGS="--cfg tokio_unstable"`, job tasks are named `job:<name>`,
+//! so `tokio-console` shows them by job name instead of anonymous tasks.
+//!
+//! Finished runs are logged as `run finished` events with their duration, panicked runs as `run panicked` errors.
+//! The level of a job's spans and events is set with `Job::log_level` or the `log_level` job option, so a chatty
+//! job can log at `debug` while the others stay at `info`, and `off` silences a job. Tracing targets are fixed at
+//! compile time, so filter a single job on its span instead, e.g. `RUST_LOG="info,[job{name=report-sync}]=debug"`.
+//!
+//! #### Alerting
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Alert, AlertRule, Job, Notifier, Scheduler};
+//! use std::pin::Pin;
+//! use std::time::Duration;
+//!
+//! // A notifier receives alerts of all jobs, e.g. to forward them to Slack, PagerDuty or email
+//! struct Printer;
+//! impl Notifier for Printer {
+//!     fn notify(&self, alert: Alert) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+//!         Box::pin(async move {
+//!             eprintln!("job {} alert: {:?}", alert.name, alert.kind);
+//!         })
+//!     }
+//! }
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Alert after 3 failed (panicked) runs in a row, or without a successful run for 1 minute, default: None
+//!     fn alert_rule(&self) -> Option<AlertRule> {
+//!         Some(
+//!             AlertRule::new()
+//!                 .failures(3)
+//!                 .stale_after(Duration::from_secs(60)),
+//!         )
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .notifier(Printer)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Webhook alerts
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["http"] }
+//! ```
+//!
+//! With the `http` feature, `WebhookNotifier` posts every alert as a JSON payload
+//! (`job`, `event`, `error`, `failures`, `last_success`, `timestamp`) to the configured URL:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "http")]
+//! # mod gated {
+//! # use async_periodic_job::{Job, Scheduler, WebhookNotifier};
+//! #
+//! # struct JobImpl;
+//! # impl Job for JobImpl {
+//! #     async fn run(&mut self) {}
+//! # }
+//! #
+//! # #[tokio::main]
+//! # pub async fn main() {
+//! Scheduler::new()
+//!     .notifier(WebhookNotifier::new("https://hooks.example.com/alerts"))
+//!     .spawn(JobImpl)
+//!     .wait()
+//!     .await;
+//! # }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "http")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! #### StatsD metrics
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, Statsd};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Emits `runs`, `failures`, `no_work` and `partial` counters and `duration` timings of every job over UDP,
+//!     // with the job name as a DogStatsD tag (or as part of the metric name for plain StatsD)
+//!     let statsd = Statsd::new("127.0.0.1:8125")
+//!         .unwrap()
+//!         .prefix("my_app.jobs")
+//!         .dogstatsd(true);
+//!     Scheduler::new()
+//!         .layer(statsd)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! `Statsd::new` sends from a socket of the same address family as the agent, IPv4 or IPv6, trying each address a host name resolves to. Job names are sanitized the same way in DogStatsD tags as in metric names: every character other than ASCII letters, digits, `-` and `_` becomes `_`, so `|`, `,` and `:` can't break the packet:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, Statsd};
+//! use std::net::UdpSocket;
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "sync|eu,west:1"
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     for agent in ["127.0.0.1:0", "[::1]:0"] {
+//!         // IPv6 may be unavailable on the host, the agent is then skipped
+//!         let Ok(agent) = UdpSocket::bind(agent) else {
+//!             continue;
+//!         };
+//!         agent.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+//!         let statsd = Statsd::new(agent.local_addr().unwrap()).unwrap().dogstatsd(true);
+//!         let scheduler = Scheduler::new().layer(statsd).spawn(JobImpl);
+//!         let mut packet = [0; 256];
+//!         let len = agent.recv(&mut packet).unwrap();
+//!         let packet = std::str::from_utf8(&packet[..len]).unwrap();
+//!         assert!(packet.ends_with("|#job:sync_eu_west_1"));
+//!         scheduler.stop().await;
+//!     }
+//! }
+//! ```
+//!
+//! #### Metrics facade
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["metrics"] }
+//! ```
+//!
+//! With the `metrics` feature, the scheduler records its metrics through the [`metrics`](https://docs.rs/metrics) crate,
+//! so any installed recorder (Prometheus, StatsD, logs, ...) exports them. All metrics carry a `job` label, plus the job's own labels:
+//!
+//! - `periodic_job_active`: gauge of running job loops
+//! - `periodic_job_runs_total`: counter of finished runs
+//! - `periodic_job_failures_total`: counter of panicked runs and runs reported as `RunStatus::Failed`
+//! - `periodic_job_run_duration_seconds`: histogram of run durations
+//! - `periodic_job_outcomes_total`: counter of finished runs by `status` (`success`, `no_work`, `partial_success`, `failed`)
+//! - `periodic_job_restarts_total`: counter of supervised restarts
+//!
+//! #### Run outcomes
+//!
+//! ```rust
+//! use async_periodic_job::{Job, RunContext, RunStatus, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct DrainQueue {
+//!     queue: Vec<u64>,
+//! }
+//!
+//! impl Job for DrainQueue {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         if self.queue.is_empty() {
+//!             return ctx.report(RunStatus::NoWork);
+//!         }
+//!         let failed = self.queue.drain(..).filter(|item| item % 2 == 0).count();
+//!         if failed > 0 {
+//!             ctx.report(RunStatus::PartialSuccess(format!("{failed} items failed")));
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(DrainQueue { queue: vec![1, 2, 3] });
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     let stats = handle.stats();
+//!     assert_eq!(stats.partial_runs, 1);
+//!     assert!(stats.no_work_runs > 0);
+//!     assert_eq!(stats.last_status, Some(RunStatus::NoWork));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A run that returns normally counts as `RunStatus::Success` and a panic as `RunStatus::Failed`, but a job can classify its own run through `RunContext::report`: `NoWork` when there was nothing to do, `PartialSuccess` with a detail when only part of the work went through, and `Failed` with an error when it failed without panicking. A reported failure counts in `JobStats::failures`, doesn't refresh `last_success` and feeds failure alerts like a panic, but doesn't restart the job. `JobStats` counts `no_work_runs` and `partial_runs` and keeps the `last_status`, `EventKind::Finished` carries the status, and the metrics, the StatsD layer and the JSON event log break runs down by it, so dashboards tell runs that did nothing from runs that did real work.
+//!
+//! #### Typed job errors
+//!
+//! ```rust
+//! use async_periodic_job::{Fallible, FallibleJob, Job, RunContext, RunStatus, Scheduler};
+//! use std::io::{Error, ErrorKind};
+//! use std::time::Duration;
+//!
+//! struct Upload;
+//!
+//! impl Job for Upload {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {}
+//! }
+//!
+//! impl FallibleJob for Upload {
+//!     type Error = Error;
+//!
+//!     async fn try_run(&mut self, _ctx: RunContext) -> Result<(), Error> {
+//!         Err(Error::new(ErrorKind::TimedOut, "bucket unreachable"))
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new()
+//!         // Every failure of every job, as a `dyn Error` that can be downcast
+//!         .on_error(|name, error| eprintln!("{name} failed: {error}"));
+//!     let upload = Fallible::new(Upload).on_error(|error: &Error| {
+//!         if error.kind() == ErrorKind::TimedOut {
+//!             // retry through another endpoint, bump a metric, ...
+//!         }
+//!     });
+//!     let handle = scheduler.spawn_with_handle(upload);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(handle.stats().last_status, Some(RunStatus::Failed("bucket unreachable".into())));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A job that returns errors implements `FallibleJob`: `try_run` replaces `run` and returns a `Result` with the job's own error type. Wrapped in `Fallible`, an `Err` counts the run as `RunStatus::Failed` with the error's message. Before that, the error goes to the handlers registered with `Fallible::on_error`, which receive it typed, so they can match on the variants instead of parsing strings. `Scheduler::on_error` handlers see the failures of every job in the scheduler and its children, with the job name and the error as `&(dyn Error + Send + Sync)`, and can still downcast it to the concrete type. Jobs that aren't `FallibleJob` can feed the same handlers through `RunContext::fail`, which takes anything convertible to `Box<dyn Error + Send + Sync>`, including strings. Panics don't go through the error handlers; they are still reported as before.
+//!
+//! #### Status, liveness and readiness
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct WarmCache;
+//! impl Job for WarmCache {
+//!     // Critical jobs need a successful run before the scheduler reports ready, default: false
+//!     fn critical(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(WarmCache);
+//!
+//!     // `live`: the scheduler is running and no job loop has died or stalled, e.g. for a liveness probe
+//!     // `ready`: all critical jobs had a successful run, e.g. for a readiness probe
+//!     let status = scheduler.status();
+//!     assert!(status.live);
+//!     assert!(!status.ready);
+//!     for job in status.jobs {
+//!         println!("{}: {} runs, {} failures", job.name, job.stats.runs, job.stats.failures);
+//!     }
+//!
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A job stalls when its loop stops making progress for longer than the liveness timeout (`Scheduler::liveness_timeout`, 5 minutes by default): its next tick is overdue by more than the timeout, or its run has gone that long without a heartbeat. Starting a run counts as one, and long runs report progress with `RunContext::heartbeat`. Jobs waiting for their next tick, a trigger, a quarantine release or load to ease owe no progress, so idle jobs never stall. `JobStatus::stalled` flags the job and `live` turns false while any job is stalled, so a liveness probe catches a deadlocked run or loop as well as a dead one:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Import;
+//! impl Job for Import {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         for _batch in 0..5 {
+//!             // import the batch, then report progress
+//!             tokio::time::sleep(Duration::from_millis(20)).await;
+//!             ctx.heartbeat();
+//!         }
+//!         // A deadlock: the run never finishes nor reports progress again
+//!         std::future::pending::<()>().await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().liveness_timeout(Duration::from_millis(50));
+//!     let import = scheduler.spawn_with_handle(Import);
+//!     import.trigger();
+//!     tokio::time::sleep(Duration::from_millis(80)).await;
+//!     // Still beating
+//!     assert!(scheduler.status().live);
+//!     tokio::time::sleep(Duration::from_millis(200)).await;
+//!     let status = scheduler.status();
+//!     assert!(!status.live);
+//!     assert!(status.jobs[0].alive && status.jobs[0].stalled);
+//!     scheduler.stop_with_deadline(Duration::from_millis(10)).await;
+//! }
+//! ```
+//!
+//! #### Job handles
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct Greeter {
+//!     greeting: String,
+//! }
+//! impl Job for Greeter {
+//!     async fn run(&mut self) {
+//!         println!("{}", self.greeting);
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(Greeter {
+//!         greeting: "hello".to_string(),
+//!     });
+//!
+//!     // Swap the job instance before its next tick (after any in-flight run finished),
+//!     // the schedule and stats of the job are preserved
+//!     handle.replace(Greeter {
+//!         greeting: "bonjour".to_string(),
+//!     });
+//!     println!("{:?}", handle.stats());
+//!
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! The replacement is swapped in before the job's next tick, once any run in progress finished. From then on its own layers, exclusion group, alert rule, supervisor, sampling, duty cycle and budget apply, with `JobOptions` overrides still taking precedence. Restart history, sampling and budget state carry over unless the replacement changes that setting. The name, labels, critical flag, shard and abortability stay those of the job that was spawned:
+//!
+//! ```rust
+//! # #[cfg(feature = "testing")]
+//! # mod gated {
+//! use async_periodic_job::test::{MockCounters, MockJob};
+//! use async_periodic_job::{JobFilter, Simulation};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! pub async fn main() {
+//!     let simulation = Simulation::new(UNIX_EPOCH);
+//!     let database = MockCounters::default();
+//!     let export = |name| {
+//!         MockJob::new(name)
+//!             .every(Duration::from_secs(10))
+//!             .taking(Duration::from_secs(8))
+//!     };
+//!     simulation.spawn(export("daily").in_group("reports-db").sharing(&database));
+//!     let weekly = simulation.spawn(export("weekly"));
+//!     simulation.advance(Duration::from_secs(25)).await;
+//!     weekly.replace(export("weekly").in_group("reports-db").sharing(&database));
+//!     simulation.advance(Duration::from_secs(60)).await;
+//!     let group = simulation.scheduler().select(JobFilter::new().group("reports-db"));
+//!     assert_eq!(group.len(), 2);
+//!     assert_eq!(database.max_running(), 1);
+//!     simulation.stop().await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "testing")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! #### Sampled execution
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Sampling, Scheduler};
+//!
+//! struct Diagnostics;
+//! impl Job for Diagnostics {
+//!     // Run on a sample of the ticks only, default: `Sampling::Always`
+//!     // - `EveryNth(n)`: every nth tick, starting with the first one
+//!     // - `Fraction(f)`: deterministically on a fraction of the ticks, evenly spread
+//!     // - `Random(p)`: each tick with probability `p`
+//!     fn sampling(&self) -> Sampling {
+//!         Sampling::Random(0.1)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // expensive diagnostics
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(Diagnostics)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Adaptive period
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct SlowJob;
+//! impl Job for SlowJob {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     // Keep the share of time spent running under 20%, default: None
+//!     // When recent runs get slow, the period is stretched (to a multiple of the period for
+//!     // truncated jobs) and it shrinks back once runs are fast again
+//!     fn max_duty_cycle(&self) -> Option<f64> {
+//!         Some(0.2)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(SlowJob)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! The duty cycle must be in (0, 1]. `try_spawn` rejects other values with `SpawnError::InvalidDutyCycle`. The other spawn methods log the same error with the `tracing` feature and run the job without adaptation. However slow the runs get, the stretched period never exceeds a year.
+//!
+//! #### Period limits
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, PeriodLimit, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct SlowJob;
+//! impl Job for SlowJob {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(1)
+//!     }
+//!
+//!     fn max_duty_cycle(&self) -> Option<f64> {
+//!         Some(0.2)
+//!     }
+//!
+//!     // Shorten the period by at most half per tick, and never below 500ms
+//!     fn period_limit(&self) -> Option<PeriodLimit> {
+//!         Some(PeriodLimit::new().max_shrink(2.0).floor(Duration::from_millis(500)))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(SlowJob)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! A period that changes at runtime, through the adaptive duty cycle or a reconfigured `period` option, can feed back into itself: a job that speeds up puts more load on what it measures, which speeds it up further. A `PeriodLimit` (from `Job::period_limit` or the `period_limit` job option) keeps that in check. With `max_shrink`, each new period is at least the previous one divided by the factor, so it comes down over several ticks instead of at once. With `floor`, it never goes below a minimum of the job's own, on top of the scheduler's `min_period`. Periods are free to grow. Whenever the limit changes the period, an `EventKind::PeriodLimited` event reports the requested and the limited period.
+//!
+//! #### Runtime budget and events
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Budget, EventKind, Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct HeavyJob;
+//! impl Job for HeavyJob {
+//!     // At most 5 minutes of cumulative run time per hour, default: None
+//!     // Once exhausted, further ticks are skipped until the window resets
+//!     fn budget(&self) -> Option<Budget> {
+//!         Some(Budget::new(Duration::from_secs(300), Duration::from_secs(3600)))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(HeavyJob);
+//!
+//!     // Subscribe to the lifecycle events of all jobs
+//!     let mut events = scheduler.events();
+//!     tokio::spawn(async move {
+//!         while let Ok(event) = events.recv().await {
+//!             if let EventKind::BudgetExhausted { used } = event.kind {
+//!                 eprintln!("{} exhausted its budget after {used:?}", event.name);
+//!             }
+//!         }
+//!     });
+//!
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! #### JSON event log
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # mod gated {
+//! use async_periodic_job::{EventLog, Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! pub async fn main() {
+//!     let log = EventLog::file("/var/log/my-app/jobs.jsonl")
+//!         .unwrap()
+//!         .max_bytes(64 * 1024 * 1024);
+//!     Scheduler::new()
+//!         .event_log(log)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "serde")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! With the `serde` feature, an `EventLog` writes every event as one JSON line, with `timestamp`, `job` and `event` plus the event's own fields (`run_id`, `duration_ms`, `reason`, ...), giving an audit trail of every run that doesn't depend on the application's logging setup. Lines are written and flushed as the events are emitted, so unlike an `events()` receiver the log never lags behind or drops events, and the shutdown events are in it once `stop` returns. `EventLog::new` takes any writer. `EventLog::file` appends to a file and, once `max_bytes` would be exceeded, renames it with a timestamp suffix and starts a new one. `on_rotate` replaces that hook with one returning the next writer, to compress, ship or prune the old files.
+//!
+//! #### Replaying an event log
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # mod gated {
+//! use async_periodic_job::{Cron, Replay};
+//! use chrono::{TimeZone, Utc};
+//!
+//! pub fn main() -> std::io::Result<()> {
+//!     let replay = Replay::file("/var/log/my-app/jobs.jsonl")?;
+//!     let schedule = Cron::parse("0 0 2 * * *").unwrap();
+//!     let tick = Utc.with_ymd_and_hms(2026, 10, 15, 2, 0, 0).unwrap().into();
+//!     if let Some(tick) = replay.tick("NightlyReport", &schedule, tick) {
+//!         // "2026-10-15 02:00:00 UTC: missed, run 41 was still in progress"
+//!         println!("{tick}");
+//!     }
+//!     Ok(())
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "serde")]
+//! #     gated::main().unwrap();
+//! # }
+//! ```
+//!
+//! To answer "why didn't my job run at 02:00", `Replay` reads an exported event log back (`Replay::file`, `Replay::read` for any reader, or `Replay::new` for events collected from `events()`) and lays the job's schedule over the recorded timestamps. `Replay::job` walks every tick of a schedule in a time range, and `Replay::tick` looks at the one at or after a given time. Each `ReplayedTick` carries the tick, the events recorded between it and the next tick, and a `TickDecision`:
+//!
+//! - `Ran`: a run started in the window. It includes the run id, the start time and the final status, which is `None` if the run never finished.
+//! - `Skipped`: the tick was skipped, with the `SkipReason`.
+//! - `Overrun`: an earlier run was still in progress at the tick.
+//! - `Quarantined`: the job was quarantined.
+//! - `Stopped`: the scheduler had shut down before the tick.
+//! - `Unrecorded`: nothing explains the tick, usually because the process wasn't running or the log was rotated away.
+//!
+//! The replay only sees what the log recorded. Pass the schedule the job actually ran with, since `Every` ticks line up with truncated jobs but not with jobs anchored on their start time.
+//!
+//! #### Completion deadlines
+//!
+//! A job can declare that each run must finish within some time of its tick with `Job::completion` (also the `completion` job option). Runs finishing past that deadline emit `EventKind::DeadlineMissed` with how late they were, count in `JobStats::deadline_misses` and, with the `metrics` feature, in `periodic_job_deadline_misses_total`. With `skip_late`, a run that would start after its deadline has passed, such as a tick fired late after an overrun, is skipped with `SkipReason::Late` and counted as a miss, since its result would no longer be useful:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Completion, EventKind, Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Snapshot;
+//! impl Job for Snapshot {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     fn with_truncate_time(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     // A snapshot finished after the next minute is useless
+//!     fn completion(&self) -> Option<Completion> {
+//!         Some(Completion::within(Duration::from_secs(60)).skip_late())
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(Snapshot);
+//!     let mut events = scheduler.events();
+//!     tokio::spawn(async move {
+//!         while let Ok(event) = events.recv().await {
+//!             if let EventKind::DeadlineMissed { late, .. } = event.kind {
+//!                 eprintln!("{} finished {late:?} late", event.name);
+//!             }
+//!         }
+//!     });
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! #### Load gates
+//!
+//! ```rust
+//! use async_periodic_job::{CpuLoad, Job, LoadPolicy, MemoryPressure, Scheduler};
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Batch;
+//! impl Job for Batch {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     // Low-priority work waits for the host to calm down, default: `LoadPolicy::Ignore`
+//!     fn load_policy(&self) -> LoadPolicy {
+//!         LoadPolicy::Skip
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let saturated = Arc::new(AtomicBool::new(true));
+//!     let check = saturated.clone();
+//!     let scheduler = Scheduler::new()
+//!         .load_gate(CpuLoad::new(0.9))
+//!         .load_gate(MemoryPressure::new(0.1))
+//!         .load_gate(move || {
+//!             let saturated = check.load(Ordering::Relaxed);
+//!             async move { saturated }
+//!         });
+//!     let handle = scheduler.spawn_with_handle(Batch);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(handle.stats().runs, 0);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! Load gates report whether the host is under pressure, and are checked before every run of the jobs that opt in through `Job::load_policy` (also the `load_policy` job option and `LOAD_POLICY` variable): `Skip` drops the tick with a `Skipped` event and `SkipReason::Load`, and `Delay` holds the run, checking the gates again every second until none of them reports pressure, then emits `EventKind::LoadDelayed` with the time waited. The host is under pressure as soon as one gate says so. `CpuLoad` compares the one-minute load average per CPU to a maximum and `MemoryPressure` the available share of memory to a minimum, both read from `/proc` on Linux and never reporting pressure elsewhere; any async closure returning `bool` is a gate too, for checks like a queue depth or a downstream service's health. Jobs keep the default `Ignore` and always run, so only the batch work gives way.
+//!
+//! #### Period floor
+//!
+//! Periods shorter than the scheduler's floor (1ms by default) are raised to it, so a job with a tiny period can't monopolize a runtime worker. The first tick that gets floored emits an `EventKind::PeriodFloored` event carrying the requested period and the floor. Ticks that are due immediately, such as catch-up, recovered or backfilled ticks, yield to the runtime before running. The floor is set with `Scheduler::min_period`, and `Duration::ZERO` disables it:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{EventKind, Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct TightJob;
+//! impl Job for TightJob {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_micros(100)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().min_period(Duration::from_millis(5));
+//!     let mut events = scheduler.events();
+//!     tokio::spawn(async move {
+//!         while let Ok(event) = events.recv().await {
+//!             if let EventKind::PeriodFloored { requested, floor } = event.kind {
+//!                 eprintln!("{} runs every {floor:?} instead of {requested:?}", event.name);
+//!             }
+//!         }
+//!     });
+//!     scheduler.spawn(TightJob).wait().await;
+//! }
+//! ```
+//!
+//! #### Memory bounds
+//!
+//! Per-job bookkeeping doesn't grow with uptime:
+//!
+//! - Stats, the adaptive period's moving average, budget counters and alert failure counts are fixed-size.
+//! - Supervisor restart history holds at most `intensity` instants.
+//! - Deduplication keeps the claims of the ticks within `dedup_retention` (one hour by default) in the store, and drops older ones as new ticks are claimed.
+//! - The store holds at most one intent, one last tick and one checkpoint per job, plus the run history within its `Retention`.
+//! - Typed outputs keep the last `output_capacity` values per job (16 by default). Slower receivers lag instead of buffering more.
+//! - Backfill keeps at most `max_pending_backfills` waiting requests per job (16 by default), plus the one in progress. Further requests resolve to `None` right away.
+//! - Job mailboxes hold at most `mailbox_capacity` undelivered messages per job (64 by default). `send` returns the message when the mailbox is full.
+//! - Events go through a single scheduler-wide ring of `event_capacity` events (1024 by default). Slow subscribers see `RecvError::Lagged` rather than holding memory.
+//!
+//! The caps are set on the scheduler before spawning jobs or creating children:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::Scheduler;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .event_capacity(64)
+//!         .output_capacity(4)
+//!         .max_pending_backfills(2)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! The only record that grows with the number of runs is the fire log of a `Simulation`, which exists for tests.
+//!
+//! #### Suspend awareness
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, SuspendPolicy};
+//! use std::time::Duration;
+//!
+//! struct HourlyJob;
+//! impl Job for HourlyJob {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     // What to do when the host was suspended (laptop sleep, VM pause) while the job waited
+//!     // for its tick, default: `SuspendPolicy::RunNow`
+//!     // - `RunNow`: run immediately on wake up
+//!     // - `Realign`: wait for the next aligned tick (truncated jobs) before running
+//!     // - `Skip`: skip the late tick and wait for the next one
+//!     fn on_suspend(&self) -> SuspendPolicy {
+//!         SuspendPolicy::Realign
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(HourlyJob)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Period parsing
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Period, Scheduler};
+//! use std::time::Duration;
+//!
+//! // Periods parse from humantime-style strings, e.g. from environment variables or config files,
+//! // and (de)serialize as such strings with the `serde` feature
+//! struct JobImpl {
+//!     period: Period,
+//! }
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         self.period.into()
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let period = Period::parse("1h 30m").unwrap();
+//!     assert_eq!(period.as_duration(), Duration::from_secs(5400));
+//!     assert_eq!(period.to_string(), "1h 30m");
+//!
+//!     Scheduler::new()
+//!         .spawn(JobImpl { period })
+//!         .stop()
+//!         .await;
+//! }
+//! ```
+//!
+//! #### Job options
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, JobOptions, Period, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Options override what the job itself defines, fields left as `None` keep the job's own setting
+//!     let options = JobOptions {
+//!         name: Some("report-sync".to_string()),
+//!         period: Some(Period::parse("30s").unwrap()),
+//!         critical: Some(true),
+//!         ..Default::default()
+//!     };
+//!     Scheduler::new()
+//!         .spawn(JobImpl.configure(options))
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! With the `serde` feature, `JobOptions` and all option types (`Period`, `Supervisor`, `AlertRule`,
+//! `Sampling`, `Budget`, ...) are `Serialize`/`Deserialize`, so job layouts can live in config files:
+//!
+//! ```toml
+//! [report-sync]
+//! period = "30s"
+//! critical = true
+//! sampling = { every_nth = 2 }
+//! supervisor = { backoff = "1s", max_backoff = "1m", intensity = 3, window = "10s", escalation = "job" }
+//! budget = { limit = "5m", window = "1h" }
+//! ```
+//!
+//! #### Config file with hot reload
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["config"] }
+//! ```
+//!
+//! With the `config` feature, `ConfigScheduler` loads `JobOptions` keyed by job name from a TOML or YAML
+//! file (picked by extension) and applies them to the jobs it spawns. `watch` polls the file and applies
+//! changes on the fly: a new period takes effect immediately, other options on the next tick. Jobs removed
+//! from the file fall back to their own settings, and an invalid file keeps the last good config.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "config")]
+//! # mod gated {
+//! # use async_periodic_job::{ConfigError, ConfigScheduler, Job, Scheduler};
+//! # use std::time::Duration;
+//! #
+//! # struct JobImpl;
+//! # impl Job for JobImpl {
+//! #     async fn run(&mut self) {}
+//! # }
+//! #
+//! # #[tokio::main]
+//! # pub async fn main() -> Result<(), ConfigError> {
+//! ConfigScheduler::load(Scheduler::new(), "jobs.toml")?
+//!     .watch(Duration::from_secs(5))
+//!     .spawn(JobImpl)
+//!     .wait()
+//!     .await;
+//! # Ok(())
+//! # }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "config")]
+//! #     gated::main().unwrap();
+//! # }
+//! ```
+//!
+//! Runtime options can also be set by hand with `JobHandle::configure`. `name` is fixed at spawn; every
+//! other field takes effect from the next run. A new `exclusion_group` moves the job to that group, and a
+//! new `alert_rule` starts its failure count and staleness window over:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, JobFilter, JobOptions, Scheduler};
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Report {
+//!     name: &'static str,
+//!     running: Arc<AtomicUsize>,
+//!     overlap: Arc<AtomicUsize>,
+//! }
+//!
+//! impl Job for Report {
+//!     fn name(&self) -> &str {
+//!         self.name
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(5)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         if self.running.fetch_add(1, Ordering::SeqCst) > 0 {
+//!             self.overlap.fetch_add(1, Ordering::SeqCst);
+//!         }
+//!         tokio::time::sleep(Duration::from_millis(10)).await;
+//!         self.running.fetch_sub(1, Ordering::SeqCst);
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let running = Arc::new(AtomicUsize::new(0));
+//!     let overlap = Arc::new(AtomicUsize::new(0));
+//!     let scheduler = Scheduler::new();
+//!     let handles = ["daily", "weekly"].map(|name| {
+//!         scheduler.spawn_with_handle(Report {
+//!             name,
+//!             running: running.clone(),
+//!             overlap: overlap.clone(),
+//!         })
+//!     });
+//!     for handle in &handles {
+//!         handle.configure(JobOptions {
+//!             exclusion_group: Some("reports-db".to_string()),
+//!             ..Default::default()
+//!         });
+//!     }
+//!     tokio::time::sleep(Duration::from_millis(200)).await;
+//!     let group = scheduler.select(JobFilter::new().group("reports-db"));
+//!     assert_eq!(group.len(), 2);
+//!     assert_eq!(overlap.load(Ordering::SeqCst), 0);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A reload only touches the jobs whose file entry changed, and only the fields that changed, so
+//! anything set through the handle in the meantime (such as `disable`) is kept. Restart history,
+//! budget spending and sampling state are kept unless their own option changed.
+//!
+//! #### Environment overrides
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "report-sync"
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // APJ_JOB_REPORT_SYNC_PERIOD=30s overrides the period of `report-sync` when it is spawned
+//!     Scheduler::new()
+//!         .with_env_overrides()
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! Variables are named `APJ_JOB_<NAME>_<OPTION>`, with the job name upper-cased and every other
+//! character than ASCII letters and digits replaced by `_`. Supported options are `PERIOD`,
+//! `TRUNCATE_TIME`, `TRUNCATE_ZONE` (`utc`, `local`), `CRITICAL`, `LABELS`, `ENABLED`, `DRY_RUN`, `STAGGER`, `FINISH_BY`, `MAX_DUTY_CYCLE`, `QUARANTINE_AFTER`, `ON_SUSPEND`
+//! (`run_now`, `realign`, `skip`), `MISFIRE`, `LOAD_POLICY` (`ignore`, `skip`, `delay`) and `LOG_LEVEL` (`off`, `trace`, `debug`, `info`, `warn`, `error`).
+//! An invalid value only drops its own variable: `spawn` applies the other overrides and logs the invalid one
+//! with the `tracing` feature, `try_spawn` rejects the job with `SpawnError::InvalidEnv`, and `JobOptions::from_env`
+//! returns it as an `EnvError`. With a `ConfigScheduler`, environment overrides win over the file.
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Period, Scheduler, SpawnError};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "report-sync"
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() {
+//!     // SAFETY: the runtime is single threaded, nothing else reads the environment
+//!     unsafe {
+//!         std::env::set_var("APJ_JOB_REPORT_SYNC_PERIOD", "30s");
+//!         std::env::set_var("APJ_JOB_REPORT_SYNC_CRITICAL", "maybe");
+//!     }
+//!     let scheduler = Scheduler::new().with_env_overrides();
+//!     let rejected = scheduler.try_spawn(JobImpl);
+//!     assert!(matches!(rejected, Err(SpawnError::InvalidEnv { .. })));
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     assert_eq!(handle.options().period, Some(Period::parse("30s").unwrap()));
+//!     assert_eq!(handle.options().critical, None);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Enabling and disabling jobs
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Spawned disabled, the job is registered but skips every tick until enabled
+//!     fn enabled(&self) -> bool {
+//!         false
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     handle.enable();
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! Disabled jobs keep their registration and stats, emit a `Skipped` event with `SkipReason::Disabled`
+//! on every tick and don't count against readiness. Unlike a short pause, the flag is meant as long-lived
+//! configuration: it is part of `JobOptions` (`enabled = false` in a config file, `APJ_JOB_<NAME>_ENABLED`
+//! in the environment).
+//!
+//! #### Exporting and importing state
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     scheduler.spawn_with_handle(JobImpl).disable();
+//!     let state = scheduler.export_state();
+//!     scheduler.stop().await;
+//!
+//!     // A new process, or another host, picks up where the old one left off
+//!     let scheduler = Scheduler::new().import_state(state);
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     assert!(!handle.is_enabled());
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! `export_state` captures every job of the scheduler and its children as a `SchedulerState`: its name, critical and enabled flags, its `JobOptions` overrides and its stats, including the last run, its duration and the last success. With the `serde` feature the state serializes, so ops tooling can back it up or carry it to another host. `import_state` restores it by job name: jobs already spawned take it right away and the rest is kept until a job with that name is spawned. The snapshot's options apply over the job's own (environment overrides still win), a disabled job stays disabled, and the last run and success move forward but never back. Counters and the next run aren't restored; they start over with the new process.
+//!
+//! #### Stateful jobs
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # mod gated {
+//! use async_periodic_job::{FileStore, Job, Scheduler, StatefulJob};
+//!
+//! struct IncrementalSync {
+//!     cursor: u64,
+//! }
+//!
+//! impl Job for IncrementalSync {
+//!     async fn run(&mut self) {
+//!         // fetch everything after `self.cursor`, then advance it
+//!     }
+//! }
+//!
+//! impl StatefulJob for IncrementalSync {
+//!     type State = u64;
+//!
+//!     fn snapshot(&self) -> u64 {
+//!         self.cursor
+//!     }
+//!
+//!     fn restore(&mut self, cursor: u64) {
+//!         self.cursor = cursor;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! pub async fn main() {
+//!     Scheduler::new()
+//!         .store(FileStore::new("/var/lib/my-app/jobs"))
+//!         .spawn_stateful(IncrementalSync { cursor: 0 })
+//!         .wait()
+//!         .await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "serde")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! With the `serde` feature, jobs implementing `StatefulJob` can be spawned with `spawn_stateful`. Their state
+//! is serialized as JSON to the scheduler `Store` after every successful run, and restored before the
+//! first run (and into replacement jobs swapped in through a handle). `FileStore` keeps one file per key in a
+//! directory, `MemoryStore` lives as long as the process, and custom backends implement `Store`. Without a
+//! store, stateful jobs simply run without checkpoints.
+//!
+//! #### Durable runs
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{FileStore, Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Every tick records a run intent in the store, removed only once the run succeeded
+//!     fn durable(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .store(FileStore::new("/var/lib/my-app/jobs"))
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! Durable jobs get at-least-once semantics: if the process dies mid-run (or the run panics), the intent stays
+//! in the store and the job runs again right away on the next start instead of waiting for its next tick. A
+//! supervised job restarted after a panic likewise retries the tick that failed. Runs may therefore repeat, so
+//! durable jobs should be idempotent. A run reported as `RunStatus::Failed` keeps its intent too and the same
+//! tick is retried with the supervisor's backoff (1s doubling up to 60s for unsupervised jobs) until a run
+//! succeeds; calling `RunContext::abandon` before failing drops the intent and moves on to the next tick.
+//! Without a store the flag has no effect.
+//!
+//! #### Run history
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # mod gated {
+//! use async_periodic_job::{FileStore, Retention, Scheduler};
+//! use std::time::Duration;
+//! # use async_periodic_job::Job;
+//! #
+//! # struct NightlyReport;
+//! # impl Job for NightlyReport {
+//! #     async fn run(&mut self) {}
+//! # }
+//!
+//! #[tokio::main]
+//! pub async fn main() {
+//!     let scheduler = Scheduler::new()
+//!         .store(FileStore::new("/var/lib/my-app/jobs"))
+//!         // Keep at most 500 runs per job, none older than a week
+//!         .run_history(Retention::new().max_runs(500).max_age(Duration::from_secs(7 * 86_400)))
+//!         .spawn(NightlyReport);
+//!     for run in scheduler.history("NightlyReport").await.unwrap() {
+//!         println!("#{} at {:?}: {:?} in {:?}", run.run_id, run.started, run.status, run.duration);
+//!     }
+//!     scheduler.wait().await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "serde")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! Stats only keep the last run, and they start over with the process. With the `serde` feature, `run_history` also appends a `RunRecord` to the scheduler `Store` after every finished run. The record holds the run id, the scheduled tick, the start time, the duration, the `RunStatus`, whether the run panicked and whether it was a shadow run. Records are kept under one `runs/<job name>` key per job and pruned on every write according to the `Retention`: the most recent 100 runs by default, adjustable with `max_runs` and `max_age`, or `unbounded`. `Scheduler::history` reads a job's records back, oldest first, so an admin endpoint or an audit script can still see what ran last night after a redeploy. A store that can't be read fails it with `Error::Store`. Runs cancelled mid-flight are not recorded. A failed write is logged and the job carries on, and without a store the history stays empty.
+//!
+//! #### Idempotency keys
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, MemoryStore, RunContext, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Claim every tick in the store, a tick already claimed (by another replica sharing the store) is skipped
+//!     fn dedup(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         // `job-name@<tick unix millis>`, stable for a given scheduled tick
+//!         let _key = ctx.idempotency_key();
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .store(MemoryStore::new())
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! `RunContext::tick` is the scheduled tick the run belongs to (the truncated boundary for truncated jobs, or the
+//! original tick for a recovered durable run), and `idempotency_key` combines it with the job name. Keys only
+//! line up across replicas for truncated jobs, whose ticks are aligned to the clock. Duplicate ticks are skipped
+//! with `SkipReason::Duplicate`; claims use `Store::insert`, which must be atomic for the check to hold.
+//! Claims stay in the store until their tick is older than `Scheduler::dedup_retention` (one hour by
+//! default), so a replica lagging behind by less than that can't claim a tick again. Each replica removes the
+//! expired claims it tried, won or lost, so keys don't pile up; claims of a replica that stops stay behind.
+//!
+//! The tick is the run's logical time, distinct from the wall time it actually started at (`RunContext::started`).
+//! Catch-up runs, backfills and retries of durable runs all carry the tick they stand for, however late they
+//! execute, so jobs partitioning work by time bucket should derive the bucket from `tick` and can use `lateness`
+//! to tell how far behind a run is.
+//!
+//! #### Misfire policies
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{FileStore, Job, Misfire, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     // Run every tick missed while the process was down, or while the job was held up
+//!     fn misfire(&self) -> Misfire {
+//!         Misfire::FireAllMissed
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .store(FileStore::new("/var/lib/my-app/jobs"))
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! A tick misfires when it passes without the job getting to run it, because a run overran, the host was
+//! suspended or the process was down. `Misfire::SkipToNext` (the default) waits for the next tick,
+//! `FireNowOnce` runs once right away for the latest missed tick and `FireAllMissed` runs every missed tick back
+//! to back, each with its own `RunContext::tick`. `Delay` runs right away and restarts the schedule from there.
+//! These match tokio's `MissedTickBehavior`: `Burst` is `FireAllMissed`, `Skip` is `SkipToNext` and `Delay` is
+//! `Delay`. With a store, the last tick is persisted so misfires are also detected across restarts.
+//!
+//! Jobs without truncation are scheduled from their previous tick rather than from the end of their previous
+//! run, so run durations don't accumulate as drift.
+//!
+//! #### Fleet stagger
+//!
+//! A truncated job deployed on many instances fires everywhere at the same boundary. Returning `true` from `Job::stagger` (also the `stagger` job option) shifts its ticks by a stable offset within the period, hashed from the scheduler's stagger key and the job name, so each instance keeps a fixed slot while the fleet spreads out. The key defaults to the host name (`HOSTNAME`, `COMPUTERNAME` or `/etc/hostname`) and can be set with `Scheduler::stagger_key`:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     // Fires once a minute, at a second that depends on the instance
+//!     fn stagger(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let pod = std::env::var("POD_NAME").unwrap_or_default();
+//!     Scheduler::new()
+//!         .stagger_key(pod)
+//!         .spawn(JobImpl)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! Staggered ticks differ between instances, so they no longer share idempotency keys.
+//!
+//! #### Spreading truncated jobs
+//!
+//! Truncated jobs sharing a period all fire at the same boundary, which can swamp a shared database every minute. `Scheduler::spread` shifts them evenly across the period instead: with four one-minute jobs, the first keeps the minute boundary and the others fire at 15, 30 and 45 seconds past it. `Spread::Registration` orders the jobs of each period by spawn order and `Spread::NameHash` by a hash of their names, so the slots don't depend on the order in which the code spawns the jobs. The spread covers the scheduler with its children and tenants, adds up with the fleet stagger, and is recomputed when a job's period changes:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, Spread};
+//! use std::time::Duration;
+//!
+//! struct Export(&'static str);
+//! impl Job for Export {
+//!     fn name(&self) -> &str {
+//!         self.0
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     fn with_truncate_time(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spread(Spread::NameHash)
+//!         .spawn(Export("orders"))
+//!         .spawn(Export("invoices"))
+//!         .spawn(Export("customers"))
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! Jobs with a `schedule` are not spread, and spread ticks differ from the unspread boundary, so they no longer share idempotency keys with it.
+//!
+//! #### Timer strategy
+//!
+//! By default every job waits for its next tick on its own tokio timer. `Scheduler::timer_mode` can move those waits to a single shared driver instead. `TimerMode::Shared` rounds each job's deadline up to the millisecond and files it in one ordered set of buckets served by a single timer task, so jobs due on the same tick are woken together by one timer. `TimerMode::PerJob` keeps a timer per job. `TimerMode::Auto`, the default, uses the shared driver for waits of 10ms or more once the scheduler and its children run at least 1000 jobs, and per-job timers otherwise, so short periods keep their precision. The mode covers the scheduler with its children and tenants; debounce and throttle windows, deadlines and backoffs keep their own timers:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler, TimerMode};
+//! use std::time::Duration;
+//!
+//! struct Poll(usize);
+//! impl Job for Poll {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(1 + self.0 as u64 % 60)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().timer_mode(TimerMode::Shared);
+//!     for device in 0..10_000 {
+//!         scheduler.spawn_with_handle(Poll(device));
+//!     }
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! The shared driver is a sorted map of millisecond buckets rather than a hashed timing wheel, so it sleeps until the earliest bucket instead of ticking at a fixed rate. It runs as a task of the scheduler that set the mode, on the runtime that spawned its first job rather than on a shard, and it stops with that scheduler. Waits too long to express as a deadline, such as the one of a schedule with no fire time left, stay on a per-job timer:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Repeating, Schedule, Scheduler, TimerMode};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! struct Migration;
+//! impl Job for Migration {
+//!     // A single run in the past, so the schedule is already exhausted
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         Some(Arc::new(Repeating::parse("R1/2020-01-01T00:00:00Z/PT1H").unwrap()))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().timer_mode(TimerMode::Shared);
+//!     scheduler.spawn_with_handle(Migration);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert!(scheduler.stop().await.failures.is_empty());
+//! }
+//! ```
+//!
+//! `benches/timers.rs` compares the modes with criterion at 1k, 10k and 100k jobs with periods from 100ms to 1s. It runs on a paused clock, so it measures the scheduling work rather than the waiting, and it prints each mode's peak memory per job. Run it with `cargo bench --bench timers`. Each job's own task dominates the memory either way, at around 5.5KB per job.
+//!
+//! #### Cold-start ramp-up
+//!
+//! When a service with hundreds of jobs restarts, every job starts its loop at once and the first runs pile up on the same few seconds. `Scheduler::ramp_up` starts the jobs gradually over a window after the scheduler is built instead: each spawned job waits for its own slot before its first tick, and the slots fill the window evenly whatever the number of jobs, the first job starting right away, the second halfway through the window, then a quarter and three quarters in, and so on. Jobs spawned once the window has passed start immediately, and the ramp covers the children of the scheduler too:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Refresh(&'static str);
+//! impl Job for Refresh {
+//!     fn name(&self) -> &str {
+//!         self.0
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new().ramp_up(Duration::from_secs(30));
+//!     for name in ["orders", "invoices", "customers", "products"] {
+//!         scheduler = scheduler.spawn(Refresh(name));
+//!     }
+//!     scheduler.wait().await;
+//! }
+//! ```
+//!
+//! The ramp only delays when a job starts; truncated jobs still fire on their boundaries once started, so the first boundary inside a job's slot is skipped rather than run late.
+//!
+//! #### Finish-by truncation
+//!
+//! Some truncated jobs have to be *done* at the boundary, like a report that must be ready at the top of the hour. Returning `true` from `Job::finish_by` (also the `finish_by` job option and `FINISH_BY` variable) starts each run early by a lead learned from previous runs: a moving average of the run duration plus twice its mean deviation, capped at the period. The first run starts at the boundary, and the lead follows the durations as they change. The run context keeps the boundary as its `tick`, so idempotency keys and misfire handling are unchanged:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct HourlyReport;
+//! impl Job for HourlyReport {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     fn with_truncate_time(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     fn finish_by(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         // Starts a few minutes before ctx.tick(), the hour the report is for
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new().spawn(HourlyReport).wait().await;
+//! }
+//! ```
+//!
+//! #### Time zones and DST
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["tz"] }
+//! ```
+//!
+//! With the `tz` feature, truncation can also follow any IANA time zone through `TruncateZone::Tz`. Local and
+//! `tz` truncation is computed on wall-clock time, so a daily job keeps running at local midnight across DST
+//! changes. `Job::dst_policy` decides what happens to times that don't exist (spring forward, `DstGap::NextValid`
+//! runs right after the jump, `DstGap::Skip` waits for the next time) or exist twice (fall back,
+//! `DstOverlap::Once` runs the first occurrence only, `DstOverlap::Both` runs both):
+//!
+//! ```rust
+//! # #[cfg(feature = "tz")]
+//! # fn main() {
+//! use async_periodic_job::{DstGap, DstOverlap, DstPolicy, TruncateZone};
+//! use chrono::{TimeZone, Utc};
+//! use chrono_tz::America::New_York;
+//! use std::time::{Duration, SystemTime};
+//!
+//! let zone = TruncateZone::Tz(New_York);
+//! let utc = |month, day, hour, min| -> SystemTime {
+//!     Utc.with_ymd_and_hms(2024, month, day, hour, min, 0).unwrap().into()
+//! };
+//! let minutes = |min: u64| Duration::from_secs(min * 60);
+//! let policy = DstPolicy::new();
+//!
+//! // Daily at local midnight: 00:00 EDT right after 00:00 EST, 23 hours later
+//! let after = utc(3, 10, 5, 0);
+//! assert_eq!(zone.next_tick(minutes(24 * 60), after, policy), Some(utc(3, 11, 4, 0)));
+//!
+//! // Every 40 minutes, 02:00 doesn't exist on March 10th: run at 03:00 EDT or skip to 03:20 EDT
+//! let after = utc(3, 10, 6, 20);
+//! assert_eq!(zone.next_tick(minutes(40), after, policy), Some(utc(3, 10, 7, 0)));
+//! let skip = policy.gap(DstGap::Skip);
+//! assert_eq!(zone.next_tick(minutes(40), after, skip), Some(utc(3, 10, 7, 20)));
+//!
+//! // Every 250ms, the boundary after 01:59:59.9 EST is 02:00, which doesn't exist either: run at 03:00 EDT
+//! let after = utc(3, 10, 6, 59) + Duration::from_millis(59_900);
+//! assert_eq!(zone.next_tick(Duration::from_millis(250), after, policy), Some(utc(3, 10, 7, 0)));
+//!
+//! // Hourly, 01:00 happens twice on November 3rd: once in EDT, then in EST as well
+//! let after = utc(11, 3, 5, 0);
+//! assert_eq!(zone.next_tick(minutes(60), after, policy), Some(utc(11, 3, 7, 0)));
+//! let both = policy.overlap(DstOverlap::Both);
+//! assert_eq!(zone.next_tick(minutes(60), after, both), Some(utc(11, 3, 6, 0)));
+//! # }
+//! # #[cfg(not(feature = "tz"))]
+//! # fn main() {}
+//! ```
+//!
+//! Every combination of gap and overlap policies, over both transitions of a year in Berlin. A period too long to
+//! count in nanoseconds since the epoch (about 292 years) has no tick:
+//!
+//! ```rust
+//! # #[cfg(feature = "tz")]
+//! # fn main() {
+//! use async_periodic_job::{DstGap, DstOverlap, DstPolicy, TruncateZone};
+//! use chrono::{TimeZone, Utc};
+//! use chrono_tz::Europe::Berlin;
+//! use std::time::{Duration, SystemTime};
+//!
+//! let zone = TruncateZone::Tz(Berlin);
+//! let utc = |month, day, hour, min| -> SystemTime {
+//!     Utc.with_ymd_and_hms(2024, month, day, hour, min, 0).unwrap().into()
+//! };
+//! let minutes = |min: u64| Duration::from_secs(min * 60);
+//!
+//! // 02:00 to 03:00 doesn't exist on March 31st, 02:00 to 03:00 happens twice on October 27th
+//! for (gap, overlap, jumped, repeated) in [
+//!     (DstGap::NextValid, DstOverlap::Once, utc(3, 31, 1, 0), utc(10, 27, 2, 0)),
+//!     (DstGap::NextValid, DstOverlap::Both, utc(3, 31, 1, 0), utc(10, 27, 1, 0)),
+//!     (DstGap::Skip, DstOverlap::Once, utc(3, 31, 1, 20), utc(10, 27, 2, 0)),
+//!     (DstGap::Skip, DstOverlap::Both, utc(3, 31, 1, 20), utc(10, 27, 1, 0)),
+//! ] {
+//!     let policy = DstPolicy::new().gap(gap).overlap(overlap);
+//!     // Every 40 minutes after 01:20 CET, the next boundaries are 02:00 and 02:40, both in the gap
+//!     assert_eq!(zone.next_tick(minutes(40), utc(3, 31, 0, 20), policy), Some(jumped));
+//!     // Hourly after 02:00 CEST, the next boundaries are 02:00 CET, then 03:00 CET
+//!     assert_eq!(zone.next_tick(minutes(60), utc(10, 27, 0, 0), policy), Some(repeated));
+//!     // Daily at local midnight: the spring forward day lasts 23 hours, the fall back day 25
+//!     let day = minutes(24 * 60);
+//!     assert_eq!(zone.next_tick(day, utc(3, 30, 23, 0), policy), Some(utc(3, 31, 22, 0)));
+//!     assert_eq!(zone.next_tick(day, utc(10, 26, 22, 0), policy), Some(utc(10, 27, 23, 0)));
+//! }
+//!
+//! assert_eq!(zone.next_tick(Duration::MAX, utc(1, 1, 0, 0), DstPolicy::new()), None);
+//! # }
+//! # #[cfg(not(feature = "tz"))]
+//! # fn main() {}
+//! ```
+//!
+//! #### Validated spawn
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, SpawnError};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "report-sync"
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! struct Broken;
+//! impl Job for Broken {
+//!     fn period(&self) -> Duration {
+//!         Duration::ZERO
+//!     }
+//! }
+//!
+//! struct Endless;
+//! impl Job for Endless {
+//!     fn period(&self) -> Duration {
+//!         Duration::MAX
+//!     }
+//!
+//!     fn with_truncate_time(&self) -> bool {
+//!         true
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     assert!(scheduler.try_spawn(JobImpl).is_ok());
+//!     assert!(matches!(scheduler.try_spawn(JobImpl), Err(SpawnError::DuplicateName { .. })));
+//!     assert!(matches!(scheduler.try_spawn(Broken), Err(SpawnError::ZeroPeriod { .. })));
+//!     assert!(matches!(scheduler.try_spawn(Endless), Err(SpawnError::PeriodOutOfRange { .. })));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! `try_spawn` checks the effective job options (including environment overrides) before spawning and rejects
+//! zero periods, truncated periods too long to compute boundaries for, duplicate names within the scheduler and
+//! its children, out of range sampling or duty cycles, and budgets that never run (zero limit) or never bind (limit
+//! not below the window). A job spawned anyway with such a period never runs.
+//!
+//! Spawning needs a tokio runtime: the job's shard, or the runtime the caller runs in. Outside of one, or once
+//! the runtime has gone away, `try_spawn` (and `try_spawn_with_outputs` and `try_spawn_with_mailbox`) returns
+//! `SpawnError::RuntimeUnavailable` instead of panicking. The infallible `spawn` variants log the error and return a
+//! handle whose job is already finished, stream and console tasks are not started, and a startup job that can't be
+//! spawned cancels the scheduler like a failed one. Jobs spawned onto a runtime that is shutting down are dropped with
+//! it and their handles report them finished. `ConfigScheduler::watch` logs the error and leaves the file unwatched, and
+//! failure or quarantine alerts raised without a runtime are logged instead of sent:
+//!
+//! ```rust
+//! # #[cfg(feature = "config")]
+//! # mod gated {
+//! use async_periodic_job::{ConfigScheduler, Job, Scheduler, SpawnError};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! pub fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let spawned = scheduler.try_spawn(JobImpl);
+//!     assert!(matches!(spawned, Err(SpawnError::RuntimeUnavailable { .. })));
+//!     let scheduler = scheduler.spawn(JobImpl);
+//!     assert!(scheduler.status().jobs.is_empty());
+//!
+//!     let path = std::env::temp_dir().join("jobs-outside-runtime.toml");
+//!     std::fs::write(&path, "").unwrap();
+//!     let _config = ConfigScheduler::load(Scheduler::new(), &path)
+//!         .unwrap()
+//!         .watch(Duration::from_secs(5));
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "config")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! #### Errors
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Error, Job, Scheduler};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let scheduler = Scheduler::new();
+//!     scheduler.try_spawn(JobImpl)?;
+//!     // Fails if the shutdown signal can't be listened to, then turns job failures into `Error::Jobs`
+//!     scheduler.try_wait().await?.into_result()
+//! }
+//! ```
+//!
+//! `Error` gathers the crate's error categories (signal registration, invalid schedules and periods, environment
+//! and config errors, store errors and failed jobs) with `From` conversions from the specific error types, so
+//! they compose with `?`. Store I/O errors, from `Scheduler::history` or a custom `Store`, become `Error::Store`.
+//!
+//! #### Tenants
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Quota, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct CustomerSync {
+//!     customer: String,
+//! }
+//!
+//! impl Job for CustomerSync {
+//!     fn name(&self) -> &str {
+//!         &self.customer
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     // At most 2 concurrent runs and 10 runs per minute across all jobs of the tenant
+//!     let quota = Quota::new().concurrency(2).rate_limit(10, Duration::from_secs(60));
+//!     let acme = scheduler.tenant("acme", quota);
+//!     acme.spawn_with_handle(CustomerSync { customer: "acme".to_string() });
+//!     assert_eq!(scheduler.tenants(), ["acme"]);
+//!     // Tear down a single tenant, the others keep running
+//!     let report = scheduler.stop_tenant("acme").await.unwrap();
+//!     assert!(report.is_ok());
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A tenant is a named child scheduler registered under its parent, with its own cancellation token and
+//! quota layers shared by all of its jobs. `tenant` returns the existing tenant when the name is already taken,
+//! and `max_concurrency` applies the same concurrency limit to any scheduler.
+//!
+//! A run waiting for the tenant's quota gives up when the tenant or the scheduler stops, and emits `EventKind::RunCancelled` instead of `Finished`, since the job never ran:
+//!
+//! ```rust
+//! use async_periodic_job::{EventKind, Job, Quota, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct CustomerSync {
+//!     customer: String,
+//!     period: Duration,
+//!     takes: Duration,
+//! }
+//!
+//! impl Job for CustomerSync {
+//!     fn name(&self) -> &str {
+//!         &self.customer
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         self.period
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         tokio::time::sleep(self.takes).await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let acme = scheduler.tenant("acme", Quota::new().concurrency(1));
+//!     let mut events = acme.events();
+//!     let slow = CustomerSync {
+//!         customer: "acme-archive".to_string(),
+//!         period: Duration::from_secs(3600),
+//!         takes: Duration::from_millis(100),
+//!     };
+//!     let fast = CustomerSync {
+//!         customer: "acme-orders".to_string(),
+//!         period: Duration::from_millis(10),
+//!         takes: Duration::ZERO,
+//!     };
+//!     let archive = acme.spawn_with_handle(slow);
+//!     acme.spawn_with_handle(fast);
+//!     archive.trigger();
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     // The archive holds the tenant's only slot, an orders run is waiting for it
+//!     scheduler.stop_tenant("acme").await.unwrap();
+//!     assert_eq!(archive.stats().runs, 1);
+//!     let mut cancelled = Vec::new();
+//!     while let Ok(event) = events.try_recv() {
+//!         if let EventKind::RunCancelled { .. } = event.kind {
+//!             cancelled.push(event.name);
+//!         }
+//!     }
+//!     assert_eq!(cancelled, ["acme-orders".into()]);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Job factories
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, Scheduler};
+//!
+//! #[derive(Default)]
+//! struct Cleanup {
+//!     removed: Vec<String>,
+//! }
+//!
+//! impl Job for Cleanup {
+//!     async fn run(&mut self) {
+//!         // `removed` always starts empty, nothing leaks from one run to the next
+//!         self.removed.push("...".to_string());
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn_factory(Cleanup::default)
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! `spawn_factory` takes a `JobFactory` (any `Fn() -> impl Job` closure is one) and creates a fresh job instance
+//! for every run. The schedule settings (name, period, ...) are read from an instance created at spawn time.
+//!
+//! #### Job templates
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, Template};
+//! use std::time::Duration;
+//!
+//! struct SyncTenant {
+//!     tenant: u64,
+//! }
+//!
+//! impl Job for SyncTenant {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // sync `self.tenant`
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let sync = scheduler.register_template("sync-tenant", |tenant: &u64| SyncTenant { tenant: *tenant });
+//!     for tenant in 1..=100 {
+//!         sync.spawn(tenant);
+//!     }
+//!
+//!     // Elsewhere, the template is looked up by name and its instances by parameter
+//!     let sync: Template<u64, SyncTenant> = scheduler.template("sync-tenant").unwrap();
+//!     assert_eq!(sync.get(&42).unwrap().name(), "sync-tenant/42");
+//!     sync.disable_all();
+//!     assert!(sync.handles().iter().all(|handle| !handle.is_enabled()));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! `register_template` registers a closure building a job from a parameter, and `spawn` on the returned `Template` starts one instance per parameter, named `<template>/<parameter>`; spawning a parameter twice returns the running instance's handle. `Scheduler::template` finds a template again by name and parameter types, `get` finds an instance by parameter, and `params` and `handles` list them. `enable_all`, `disable_all`, `trigger_all`, `cancel_runs` and `configure_all` act on every instance at once. Instances are ordinary jobs wrapped in `Configured`, so they show up in `status`, events and metrics under their own names.
+//!
+//! #### Job sets
+//!
+//! ```rust
+//! use async_periodic_job::{Job, JobFilter, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct SyncTenant(u64);
+//! impl Job for SyncTenant {
+//!     fn name(&self) -> &str {
+//!         if self.0 % 2 == 0 { "sync-even" } else { "sync-odd" }
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn exclusion_group(&self) -> Option<&str> {
+//!         Some("tenant-db")
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     for tenant in 0..10 {
+//!         scheduler = scheduler.spawn(SyncTenant(tenant));
+//!     }
+//!     let odd = scheduler.select(JobFilter::new().name("sync-o*"));
+//!     assert_eq!(odd.len(), 5);
+//!     odd.disable();
+//!     let all = scheduler.select(JobFilter::new().group("tenant-db"));
+//!     assert_eq!(all.len(), 10);
+//!     all.trigger();
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! `select` gathers the jobs of the scheduler and its children matching a `JobFilter` into a `JobSet`, to operate on many jobs at once without holding a handle to each. A filter matches names against a pattern where `*` stands for any run of characters and `?` for one character, and jobs by exclusion group; combined conditions must all hold, and an empty filter selects every job. `disable` pauses the set and `enable` resumes it, `trigger` runs every job now, and `cancel_runs` cancels the runs in progress and returns how many there were. The set is taken when `select` is called, so jobs spawned afterwards aren't in it.
+//!
+//! #### Job labels
+//!
+//! ```rust
+//! use async_periodic_job::{Job, JobFilter, Labels, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct SyncTenant {
+//!     tenant: u64,
+//!     name: String,
+//! }
+//!
+//! impl Job for SyncTenant {
+//!     fn name(&self) -> &str {
+//!         &self.name
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn labels(&self) -> Labels {
+//!         Labels::from([
+//!             ("team".into(), "billing".into()),
+//!             ("tenant".into(), self.tenant.to_string()),
+//!         ])
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut scheduler = Scheduler::new();
+//!     for tenant in 0..10 {
+//!         let name = format!("sync-{tenant}");
+//!         scheduler = scheduler.spawn(SyncTenant { tenant, name });
+//!     }
+//!     let status = scheduler.status();
+//!     assert!(status.jobs.iter().all(|job| job.labels["team"] == "billing"));
+//!     let tenant = scheduler.select(JobFilter::new().label("tenant", "7"));
+//!     assert_eq!(tenant.names(), ["sync-7"]);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! Jobs can carry key/value labels from `Job::labels`, replaced as a whole by the `labels` job option, or by `APJ_JOB_<NAME>_LABELS` as `key=value` pairs separated by commas. Labels describe a job for operators, such as its team, tenant or tier, without encoding all of it in the name. They are listed in each `JobStatus`, added to every series of the metrics facade next to `job`, and matched by `JobFilter::label`, which requires every given label to be present with the same value.
+//!
+//! #### Awaiting a job
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let child = scheduler.child();
+//!     let handle = child.spawn_with_handle(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     child.stop().await;
+//!     // Resolves once the job loop has exited, with its final stats
+//!     let stats = handle.finished().await;
+//!     assert!(stats.runs > 0);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Deadline shutdown
+//!
+//! `stop_with_deadline` cancels the scheduler, waits up to the deadline for jobs to finish and then aborts the stragglers, reporting them by name. Jobs returning `false` from `abortable` are always awaited to completion:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Stuck;
+//! impl Job for Stuck {
+//!     fn name(&self) -> &str {
+//!         "stuck"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // Ignores cancellation
+//!         tokio::time::sleep(Duration::from_secs(3600)).await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(Stuck);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     let report = scheduler.stop_with_deadline(Duration::from_millis(100)).await;
+//!     assert_eq!(report.aborted, ["stuck"]);
+//! }
+//! ```
+//!
+//! #### Scoped jobs
+//!
+//! Jobs spawned on a scheduler run on their own tasks, so they must be `'static` and own their data. `scope` runs closure jobs that borrow from the caller's stack instead, like `std::thread::scope` does for threads. Each job added with `Scope::every` runs its closure every period, the first time one period in. The job loops aren't spawned: the future returned by `scope` polls them all inline, so they can borrow anything that outlives the call. It returns once the token is cancelled and the runs in flight have finished. The trade-off is that scoped jobs get none of the scheduler's machinery. There are no handles, events, layers or supervision, and a panicking run unwinds through the `scope` call:
+//!
+//! ```rust
+//! use async_periodic_job::{Token, scope};
+//! use std::sync::Mutex;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Config {
+//!     endpoint: String,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Plain locals on the caller's stack, no `Arc`, no `'static`
+//!     let config = Config { endpoint: "https://example.com".to_string() };
+//!     let polls = AtomicU64::new(0);
+//!     let seen = Mutex::new(Vec::new());
+//!     let token = Token::new();
+//!     let stop = token.clone();
+//!     tokio::spawn(async move {
+//!         tokio::time::sleep(Duration::from_millis(100)).await;
+//!         stop.cancel();
+//!     });
+//!     scope(token, |scope| {
+//!         scope
+//!             .every(Duration::from_millis(10), || async {
+//!                 polls.fetch_add(1, Ordering::Relaxed);
+//!             })
+//!             .every(Duration::from_millis(30), || async {
+//!                 seen.lock().unwrap().push(config.endpoint.clone());
+//!             });
+//!     })
+//!     .await;
+//!     // The scope returns once the token is cancelled and the runs in flight finished
+//!     assert!(polls.into_inner() >= 5);
+//!     assert!(!seen.into_inner().unwrap().is_empty());
+//! }
+//! ```
+//!
+//! #### Runtime shards
+//!
+//! With very large job counts, a single runtime can become the bottleneck. `shards` spreads the job tasks of a scheduler, its children and its tenants across a set of runtime handles, for example one runtime per NUMA node. Each job goes to the shard picked by hashing its name, or its `shard_key` when it returns one, so jobs sharing a key always land on the same runtime. Startup jobs and the shutdown itself stay on the calling runtime:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//! use tokio::runtime::{Builder, Runtime};
+//!
+//! struct JobImpl(usize);
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn shard_key(&self) -> Option<&str> {
+//!         // Keep the jobs of a tenant together
+//!         Some(if self.0 % 2 == 0 { "even" } else { "odd" })
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // sync one tenant
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let workers: Vec<Runtime> = (0..4)
+//!         .map(|_| Builder::new_multi_thread().worker_threads(1).enable_all().build().unwrap())
+//!         .collect();
+//!     let main = Builder::new_current_thread().enable_all().build().unwrap();
+//!     main.block_on(async {
+//!         let mut scheduler = Scheduler::new().shards(workers.iter().map(|rt| rt.handle().clone()));
+//!         for tenant in 0..100 {
+//!             scheduler = scheduler.spawn(JobImpl(tenant));
+//!         }
+//!         tokio::time::sleep(Duration::from_millis(50)).await;
+//!         assert!(scheduler.stop().await.is_ok());
+//!     });
+//! }
+//! ```
+//!
+//! #### Console reporter
+//!
+//! `Status` renders as a compact table of jobs. A `Console` prints it periodically to stdout, stderr or any writer:
+//!
+//! ```rust
+//! use async_periodic_job::{Console, Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "refresh"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new()
+//!         .console(Console::stderr().interval(Duration::from_secs(60)))
+//!         .spawn(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     // JOB      STATE  RUNS  FAILURES  LAST RUN  NEXT RUN  DURATION
+//!     // refresh  ok     5     0         0s ago    in 0s     1.2µs
+//!     let table = scheduler.status().to_string();
+//!     assert!(table.lines().nth(1).unwrap().starts_with("refresh  ok"));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Cancelling a single run
+//!
+//! `JobHandle::run_token` returns the cancellation token of the run in flight, and `cancel_run` cancels it, stopping that run without touching the job or the scheduler, e.g. when a user cancels a big export from the UI. Jobs returning `true` from `with_cancel` see the token cancelled and wind down on their own terms. For every other job, including ones that only watch `RunContext::token` from `run_with_context` (which can return `true` from `with_cancel` to opt in), the run is dropped at its next await point. A dropped run emits `EventKind::RunCancelled` instead of `Finished`, counts as neither a success nor a failure, and the job carries on with its next tick:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Export;
+//! impl Job for Export {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         tokio::time::sleep(Duration::from_secs(3600)).await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let export = scheduler.spawn_with_handle(Export);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert!(export.cancel_run());
+//!     tokio::time::sleep(Duration::from_millis(5)).await;
+//!     assert!(export.stats().last_success.is_none());
+//!     scheduler.stop_with_deadline(Duration::from_millis(50)).await;
+//! }
+//! ```
+//!
+//! #### Job messages
+//!
+//! A `MessageJob` receives typed messages between runs, so commands and configuration tweaks reach long-lived job state without globals or locks. Spawn it with `spawn_with_mailbox` and send through the handle: messages are handled in order by `handle_message`, right away when the job is waiting for its next tick and otherwise as soon as the current run finishes, and always before the next run starts. The mailbox holds up to 64 messages per job (`Scheduler::mailbox_capacity`), and `send` hands the message back when it is full:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, MessageJob, Scheduler};
+//! use std::time::Duration;
+//!
+//! enum Command {
+//!     SetBatchSize(usize),
+//! }
+//!
+//! struct Exporter {
+//!     batch_size: usize,
+//! }
+//! impl Job for Exporter {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // export self.batch_size rows at a time
+//!     }
+//! }
+//!
+//! impl MessageJob for Exporter {
+//!     type Message = Command;
+//!
+//!     async fn handle_message(&mut self, message: Command) {
+//!         match message {
+//!             Command::SetBatchSize(size) => self.batch_size = size,
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_mailbox(Exporter { batch_size: 100 });
+//!     assert!(handle.send(Command::SetBatchSize(500)).is_ok());
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Triggers, debounce and throttle
+//!
+//! `JobHandle::trigger` runs a job ahead of its next tick, e.g. from a channel or a notification. A `TriggerPolicy` collapses bursts of triggers into one run after a quiet time (debounce) and/or allows at most one triggered run per window (throttle):
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, TriggerPolicy};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     fn trigger_policy(&self) -> TriggerPolicy {
+//!         TriggerPolicy::new()
+//!             .debounce(Duration::from_millis(20))
+//!             .throttle(Duration::from_secs(1))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     for _ in 0..100 {
+//!         handle.trigger();
+//!     }
+//!     tokio::time::sleep(Duration::from_millis(100)).await;
+//!     assert_eq!(handle.stats().runs, 1);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Triggering during a run
+//!
+//! Triggers never overlap runs and never pile up. `JobHandle::trigger_now` triggers the job like `trigger` and tells what became of the request: `TriggerDecision::Accepted` when the job is idle and runs right away, `Queued` when a run is in flight and exactly one more run follows it, and `Coalesced` when a trigger is already pending, so the request is merged into it. Each decision is also emitted as `EventKind::Triggered`, so an operator's trigger shows up in the event stream even when it doesn't start a run of its own:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, TriggerDecision};
+//! use std::time::Duration;
+//!
+//! struct Rebuild;
+//! impl Job for Rebuild {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         tokio::time::sleep(Duration::from_millis(200)).await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(Rebuild);
+//!     tokio::time::sleep(Duration::from_millis(10)).await;
+//!     assert_eq!(handle.trigger_now(), TriggerDecision::Accepted);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert_eq!(handle.trigger_now(), TriggerDecision::Queued);
+//!     assert_eq!(handle.trigger_now(), TriggerDecision::Coalesced);
+//!     tokio::time::sleep(Duration::from_millis(500)).await;
+//!     assert_eq!(handle.stats().runs, 2);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! A trigger is pending from the request until the triggered run wakes up, debounce and throttle included, so triggers absorbed by a `TriggerPolicy` are reported as `Coalesced` too.
+//!
+//! #### Stream-driven jobs
+//!
+//! `spawn_stream` runs a job for the items of a stream instead of on a schedule, under the same cancellation, tracking, layers and status as periodic jobs. Each item triggers a run, but runs start at least `min_interval` apart: items arriving while the job is running or waiting out the interval are coalesced into a single next run. The item values themselves are not passed to the job, and the job stays idle once the stream ends:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::time::Duration;
+//! use tokio::sync::mpsc;
+//!
+//! struct Reindex {
+//!     runs: Arc<AtomicU64>,
+//! }
+//! impl Job for Reindex {
+//!     async fn run(&mut self) {
+//!         self.runs.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (changes, rx) = mpsc::channel(64);
+//!     let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+//!         rx.recv().await.map(|change| (change, rx))
+//!     });
+//!     let runs = Arc::new(AtomicU64::new(0));
+//!     let job = Reindex { runs: runs.clone() };
+//!     let scheduler = Scheduler::new().spawn_stream(stream, Duration::from_millis(100), job);
+//!     changes.send(0).await.unwrap();
+//!     tokio::time::sleep(Duration::from_millis(20)).await;
+//!     // The burst is coalesced into one run, 100ms after the first
+//!     for change in 1..10 {
+//!         changes.send(change).await.unwrap();
+//!     }
+//!     tokio::time::sleep(Duration::from_millis(250)).await;
+//!     assert_eq!(runs.load(Ordering::Relaxed), 2);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Typed outputs
+//!
+//! Jobs implementing `OutputJob` declare an `Output` type. After each successful run the scheduler takes the job's output and publishes it on a broadcast channel, which is available from the handle of a job spawned with `spawn_with_outputs`:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, OutputJob, Scheduler};
+//! use std::time::Duration;
+//!
+//! #[derive(Default)]
+//! struct Rates {
+//!     latest: Option<f64>,
+//! }
+//!
+//! impl Job for Rates {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         self.latest = Some(1.08);
+//!     }
+//! }
+//!
+//! impl OutputJob for Rates {
+//!     type Output = f64;
+//!
+//!     fn output(&mut self) -> Option<f64> {
+//!         self.latest.take()
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_outputs(Rates::default());
+//!     let mut outputs = handle.outputs();
+//!     assert_eq!(outputs.recv().await.unwrap(), 1.08);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Cached values
+//!
+//! `CachedJob` wraps an `OutputJob` and keeps its most recent output in a `watch` channel, together with the tick that produced it and when it was updated, turning the job into a periodic cache refresher:
+//!
+//! ```rust
+//! use async_periodic_job::{CachedJob, Job, OutputJob, Scheduler};
+//! use std::time::Duration;
+//!
+//! #[derive(Default)]
+//! struct Rates {
+//!     latest: Option<f64>,
+//! }
+//!
+//! impl Job for Rates {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         self.latest = Some(1.08);
+//!     }
+//! }
+//!
+//! impl OutputJob for Rates {
+//!     type Output = f64;
+//!
+//!     fn output(&mut self) -> Option<f64> {
+//!         self.latest.take()
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let job = CachedJob::new(Rates::default());
+//!     let mut rates = job.subscribe();
+//!     let scheduler = Scheduler::new().spawn(job);
+//!     let cached = rates.wait_for(Option::is_some).await.unwrap().clone().unwrap();
+//!     assert_eq!(cached.value, 1.08);
+//!     assert!(!cached.is_stale(Duration::from_secs(1)));
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Batched work
+//!
+//! `Batched` turns a `BatchJob` into a "collect, then flush" job. Producers anywhere in the application record units of work on its `WorkTokens`, and at each tick of the job's (coarser) period the accumulated count is handed to `run_batch` in one go. Ticks with no pending work are skipped without calling the job:
+//!
+//! ```rust
+//! use async_periodic_job::{BatchJob, Batched, Job, Scheduler};
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::time::Duration;
+//!
+//! struct Flush {
+//!     flushed: Arc<AtomicU64>,
+//! }
+//! impl Job for Flush {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(50)
+//!     }
+//! }
+//!
+//! impl BatchJob for Flush {
+//!     async fn run_batch(&mut self, count: u64) {
+//!         // Send `count` buffered events to the batch api in one call
+//!         self.flushed.fetch_add(count, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let flushed = Arc::new(AtomicU64::new(0));
+//!     let job = Batched::new(Flush { flushed: flushed.clone() });
+//!     let tokens = job.tokens();
+//!     let scheduler = Scheduler::new().spawn(job);
+//!     for _ in 0..100 {
+//!         tokens.add(1);
+//!         tokio::time::sleep(Duration::from_millis(1)).await;
+//!     }
+//!     tokio::time::sleep(Duration::from_millis(100)).await;
+//!     assert_eq!(flushed.load(Ordering::Relaxed), 100);
+//!     assert_eq!(tokens.pending(), 0);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Chunked work across ticks
+//!
+//! A long job that processes data in bounded slices implements `ChunkedJob` and is spawned wrapped in `Chunked`. Each run receives the cursor returned by the previous one (`None` at the start) and returns `Chunk::Incomplete(cursor)` to continue from there, or `Chunk::Complete` to start over on the following run. `ChunkedJob::continuation` picks when the next slice runs: `Continuation::NextTick` (the default) waits for the next tick, so the job handles one slice per period, and `Continuation::Immediately` queues a run right behind the current one, like a trigger, until the work is complete:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Chunk, Chunked, ChunkedJob, Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Archive;
+//! impl Job for Archive {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(300)
+//!     }
+//! }
+//!
+//! impl ChunkedJob for Archive {
+//!     type Cursor = u64;
+//!
+//!     async fn run_chunk(&mut self, cursor: Option<u64>, _ctx: RunContext) -> Chunk<u64> {
+//!         let from = cursor.unwrap_or(0);
+//!         // Archive up to 10 000 rows after `from` ...
+//!         let last = from + 10_000;
+//!         match last >= 1_000_000 {
+//!             true => Chunk::Complete,
+//!             false => Chunk::Incomplete(last),
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(Chunked::new(Archive))
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! The cursor is kept until a run returns, so a slice that panics is retried from the same cursor. `Chunked::resume` starts from a saved cursor and `Chunked::cursor` reads the current one, e.g. to persist it.
+//!
+//! #### Pacing a run against the next tick
+//!
+//! A run working through a backlog in batches can check how far it has come and how much time is left before the job is due again: `RunContext::elapsed` is the time since the run started, `next_tick` the tick that follows this run's tick on the job's schedule, and `time_until_next_tick` the time left until it (zero once it has passed, `None` when the schedule has no further tick). The job can stop between batches while the next one still fits and leave the rest of the backlog to the following run, instead of overrunning its tick:
+//!
+//! ```rust,no_run
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct Reindex {
+//!     cursor: u64,
+//! }
+//!
+//! impl Job for Reindex {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         let mut batches = 0;
+//!         while self.cursor < 1_000_000 {
+//!             // Reindex the next batch ...
+//!             self.cursor += 1_000;
+//!             batches += 1;
+//!             let per_batch = ctx.elapsed() / batches;
+//!             if ctx.time_until_next_tick().is_some_and(|left| left < per_batch * 2) {
+//!                 // Yield the rest of the work to the next run
+//!                 break;
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     Scheduler::new()
+//!         .spawn(Reindex { cursor: 0 })
+//!         .wait()
+//!         .await;
+//! }
+//! ```
+//!
+//! For truncated jobs the next tick is the next boundary, so a run that starts late has less time left. A triggered run sees the tick that was pending when it was triggered.
+//!
+//! #### Shutdown deadline in the run context
+//!
+//! When the scheduler is stopped with `stop_with_deadline`, the run context of abortable jobs exposes the deadline, so a cancelled run can decide to checkpoint early instead of being aborted at an unknown moment:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         ctx.token().cancelled().await;
+//!         // None when stopped without a deadline
+//!         let remaining = ctx.remaining().unwrap();
+//!         assert!(remaining <= Duration::from_secs(5));
+//!         // Save progress within the remaining time ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().spawn(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     let report = scheduler.stop_with_deadline(Duration::from_secs(5)).await;
+//!     assert!(report.is_ok());
+//! }
+//! ```
+//!
+//! #### Cancellation grace
+//!
+//! A cancel-aware job that ignores its token can hang `stop` forever. `cancel_grace` bounds how long the scheduler waits for each abortable job once it has been cancelled: jobs still running after the grace period are aborted, listed in `aborted` and flagged in `unresponsive`. The grace period also bounds `remaining` in the run context, and `stop_with_deadline` uses whichever limit comes first:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, Token};
+//! use std::time::Duration;
+//!
+//! struct Stubborn;
+//! impl Job for Stubborn {
+//!     fn name(&self) -> &str {
+//!         "stubborn"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     fn with_cancel(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run_with_cancel(&mut self, _token: Token) {
+//!         // Never looks at the token
+//!         tokio::time::sleep(Duration::from_secs(3600)).await;
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new()
+//!         .cancel_grace(Duration::from_millis(100))
+//!         .spawn(Stubborn);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     let report = scheduler.stop().await;
+//!     assert_eq!(report.unresponsive, ["stubborn"]);
+//!     assert_eq!(report.aborted, ["stubborn"]);
+//! }
+//! ```
+//!
+//! #### Startup jobs
+//!
+//! A `StartupJob` runs once when it is registered with `Scheduler::startup`. Periodic jobs of the scheduler and its children wait for all startup jobs to complete before their first tick, and the scheduler reports not ready until then. A panicking startup job cancels the scheduler and is reported as a failure, so periodic jobs never run against, e.g., a half-migrated schema. Register startup jobs before spawning the periodic jobs:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler, StartupJob};
+//! use std::time::Duration;
+//!
+//! struct Migrate;
+//! impl StartupJob for Migrate {
+//!     async fn run(&mut self) {
+//!         // Apply schema migrations, warm caches, ...
+//!     }
+//! }
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // Runs only once the migrations are applied
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().startup(Migrate).spawn(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert!(scheduler.status().ready);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Waiting for external readiness
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Scheduler};
+//! use std::time::Duration;
+//! use tokio::sync::watch;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // Runs only once this replica leads
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (elected, mut leader) = watch::channel(false);
+//!     let scheduler = Scheduler::new()
+//!         .wait_ready(async move {
+//!             let _ = leader.wait_for(|leader| *leader).await;
+//!         })
+//!         .spawn(JobImpl);
+//!     assert!(!scheduler.status().ready);
+//!     elected.send_replace(true);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     assert!(scheduler.status().ready);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! Some preconditions aren't work the scheduler should do itself: another service migrates the database, the config arrives from a watcher, or a leader election decides which replica runs. `Scheduler::wait_ready` takes a future for that condition. The first ticks of the scheduler and its children are held until the future resolves, through the same gate as startup jobs, so the scheduler reports not ready in the meantime. Jobs don't need to sleep or retry at boot. The future is dropped if the scheduler is stopped first, so `stop` never hangs on a condition that never comes. If it panics, the scheduler is cancelled and the panic is reported as a `wait_ready` failure. Several calls, and any startup jobs, must all finish before the first tick.
+//!
+//! #### Dry runs
+//!
+//! A scheduler built `with_dry_run`, or a job returning `true` from `dry_run` (also the `dry_run` job option), runs in shadow mode: `RunContext::is_dry_run` tells the job to compute but skip its side effects. Shadow runs are flagged in `EventKind::Started`, counted in `JobStats::shadow_runs` and the `periodic_job_shadow_runs_total` metric, and carry a `shadow` field on their `run` span:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_millis(10)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         // Compute the report ...
+//!         if !ctx.is_dry_run() {
+//!             // ... and publish it
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new().with_dry_run();
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(50)).await;
+//!     let stats = handle.stats();
+//!     assert_eq!(stats.shadow_runs, stats.runs);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Schedules
+//!
+//! Instead of a period, a job can return a `Schedule` computing its next fire time. `Every` fires on wall-clock aligned boundaries, optionally shifted by an offset, in any time zone. Several schedules combine into a `Union` firing at the earliest next time of any of them:
+//!
+//! ```rust
+//! use async_periodic_job::{Every, Job, Schedule, Scheduler, TruncateZone};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         const HOUR: Duration = Duration::from_secs(3600);
+//!         // Every 6 hours, plus daily at 02:30 local time
+//!         let every_6h = Every::new(6 * HOUR);
+//!         let daily = Every::new(24 * HOUR)
+//!             .offset(2 * HOUR + HOUR / 2)
+//!             .zone(TruncateZone::Local);
+//!         Some(Arc::new(every_6h.or(daily)))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     tokio::time::sleep(Duration::from_millis(10)).await;
+//!     assert!(handle.stats().next_run.is_some());
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! Schedules take precedence over `period` and `with_truncate_time`. Misfire and suspend policies apply to them as well.
+//!
+//! #### Schedule filters
+//!
+//! Schedules can be restricted to a daily time window (`between`, with times since midnight, wrapping past midnight if the end is before the start), to some days of the week (`on_days`), or have a time range excluded (`except`). Windows and days are evaluated in the time zone of the schedule:
+//!
+//! ```rust
+//! use async_periodic_job::{Every, Schedule, TruncateZone, Weekday};
+//! use std::time::{Duration, SystemTime};
+//!
+//! const HOUR: Duration = Duration::from_secs(3600);
+//!
+//! // Every 5 minutes, only 09:00-17:00 local time on weekdays
+//! let schedule = Every::new(Duration::from_secs(300))
+//!     .zone(TruncateZone::Local)
+//!     .between(9 * HOUR, 17 * HOUR)
+//!     .on_days(&[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]);
+//! assert!(schedule.next_after(SystemTime::now()).is_some());
+//!
+//! // Every hour, except during a maintenance window
+//! let now = SystemTime::now();
+//! let schedule = Every::new(HOUR).except(now..now + 24 * HOUR);
+//! assert!(schedule.next_after(now).unwrap() >= now + 24 * HOUR);
+//! ```
+//!
+//! #### Cron expressions
+//!
+//! `Cron` parses cron expressions into a `Schedule`. Five fields (`min hour day-of-month month day-of-week`) follow the classic syntax with Sunday as `0` or `7`; six or seven fields follow Quartz, with a leading seconds field, an optional trailing year, and Sunday as `1`. Fields take lists, ranges, steps, `*`/`?` and month or day names, plus the Quartz modifiers `L` (last day, or `L-3` three days before it), `W` (nearest weekday, `LW` for the last one), `5L` (last Thursday) and `2#1` (first Monday). The `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` aliases are supported, as well as `@reboot`, which fires once when the job starts:
+//!
+//! ```rust
+//! use async_periodic_job::{Cron, Job, Schedule, Scheduler, TruncateZone};
+//! use std::sync::Arc;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // At 06:30:15 on the last weekday of every month, local time
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         let cron = Cron::parse("15 30 6 LW * ?").unwrap();
+//!         Some(Arc::new(cron.zone(TruncateZone::Local)))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     assert_eq!(
+//!         Cron::parse("61 * * * *").unwrap_err().to_string(),
+//!         "invalid cron expression: invalid minute `61`: 61 is out of range 0-59",
+//!     );
+//!     let scheduler = Scheduler::new().spawn(JobImpl);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! When both day-of-month and day-of-week are restricted, a day matching either one fires.
+//!
+//! `@reboot` depends on when the job starts rather than on the time, so `next_after` never returns it and previews such as `explain` and `fire_times` leave it out. `Schedule::fires_at_start` tells the scheduler instead, which runs the job as it starts: once per job and process, even when one `Cron` is shared between jobs or combined with other schedules through `or`. Custom schedules can implement it the same way:
+//!
+//! ```rust
+//! use async_periodic_job::{Cron, Every, Job, Schedule, Scheduler};
+//! use std::sync::Arc;
+//! use std::time::{Duration, SystemTime};
+//!
+//! struct Warmup {
+//!     name: &'static str,
+//!     schedule: Arc<dyn Schedule>,
+//! }
+//!
+//! impl Job for Warmup {
+//!     fn name(&self) -> &str {
+//!         self.name
+//!     }
+//!
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         Some(self.schedule.clone())
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let reboot = Arc::new(Cron::parse("@reboot").unwrap());
+//!     let now = SystemTime::now();
+//!     assert_eq!(reboot.explain(now), "once at startup");
+//!     assert_eq!(reboot.fire_times(now).count(), 0);
+//!     assert!(reboot.fires_at_start(now));
+//!
+//!     let scheduler = Scheduler::new();
+//!     let caches = scheduler.spawn_with_handle(Warmup {
+//!         name: "caches",
+//!         schedule: reboot.clone(),
+//!     });
+//!     let indexes = scheduler.spawn_with_handle(Warmup {
+//!         name: "indexes",
+//!         schedule: reboot.clone(),
+//!     });
+//!     let refresh = scheduler.spawn_with_handle(Warmup {
+//!         name: "refresh",
+//!         schedule: Arc::new(reboot.or(Every::new(Duration::from_millis(20)))),
+//!     });
+//!     tokio::time::sleep(Duration::from_millis(100)).await;
+//!     assert_eq!(caches.stats().runs, 1);
+//!     assert_eq!(indexes.stats().runs, 1);
+//!     assert!(refresh.stats().runs > 1);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Calendar expressions
+//!
+//! `Calendar` parses systemd `OnCalendar` expressions, so existing timers can move into the application unchanged: an optional weekday list (`Mon..Fri`, `Sat,Sun`), a `year-month-day` date (`~` counts days from the end of the month), an `hour:minute[:second]` time and an optional time zone (`UTC`, or any IANA zone with the `tz` feature). Components take lists, `..` ranges and `/` repetitions, and the `minutely`, `hourly`, `daily`, `weekly`, `monthly`, `quarterly`, `semiannually` and `yearly` shorthands are supported. Like systemd, expressions without a time zone follow local time:
+//!
+//! ```rust
+//! use async_periodic_job::{Calendar, Job, Schedule, Scheduler};
+//! use std::sync::Arc;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         Some(Arc::new(Calendar::parse("Mon..Fri *-*-* 10:00:00").unwrap()))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // The last day of every month at noon UTC
+//!     assert!(Calendar::parse("*-*~01 12:00 UTC").is_ok());
+//!     let scheduler = Scheduler::new().spawn(JobImpl);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Schedule phrases
+//!
+//! ```toml
+//! [dependencies]
+//! async-periodic-job = { version = "0.1.3", features = ["natural"] }
+//! ```
+//!
+//! With the `natural` feature, `Phrase` turns plain English schedules, e.g. from a config file, into a `Schedule`: intervals (`every 15 minutes`, `every other day`, `hourly`), days (`daily`, `every weekday`, `every monday and friday`, `weekly on thursday`, `every month on the 15th`, `on the last day of the month`) and times of day (`at 3am`, `at 09:30`, `at noon`, `at 9am and 5:30pm`). Unknown words are rejected with the offending word in the error:
+//!
+//! ```rust
+//! # #[cfg(feature = "natural")]
+//! # mod gated {
+//! use async_periodic_job::{Job, Phrase, Schedule, Scheduler, TruncateZone};
+//! use std::sync::Arc;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         let phrase = Phrase::parse("every weekday at 09:30").unwrap();
+//!         Some(Arc::new(phrase.zone(TruncateZone::Local)))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! pub async fn main() {
+//!     assert!(Phrase::parse("every blue moon").is_err());
+//!     let scheduler = Scheduler::new().spawn(JobImpl);
+//!     scheduler.stop().await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "natural")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! #### Repeating intervals
+//!
+//! `Repeating` parses ISO 8601 repeating intervals, `R[n]/start/duration` (or `R[n]/start/end`), as exchanged by other schedulers: the job fires at the start time and then every duration, `n` times in total, or forever without a count. Durations take years, months, weeks, days, hours, minutes and (fractional) seconds; months and years are added on the calendar, so `P1M` from January 31 fires on the last day of shorter months:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, Repeating, Schedule, Scheduler};
+//! use std::sync::Arc;
+//! use std::time::SystemTime;
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     // Every 6 hours from the start of 2025
+//!     fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+//!         Some(Arc::new(Repeating::parse("R/2025-01-01T00:00:00Z/PT6H").unwrap()))
+//!     }
+//!
+//!     async fn run(&mut self) {
+//!         // ...
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Three runs, 30 minutes apart
+//!     assert_eq!(Repeating::parse("R3/2025-01-01T09:00:00Z/PT30M").unwrap().count(), Some(3));
+//!     // Out of range durations are rejected, and unbounded schedules keep going however many ticks they pass
+//!     assert!(Repeating::parse("R/2025-01-01T00:00:00Z/PT99999999999999999999S").is_err());
+//!     assert!(Repeating::parse("R/2025-01-01T00:00:00Z/P99999999999Y").is_err());
+//!     let fast = Repeating::parse("R/2020-01-01T00:00:00Z/PT0.01S").unwrap();
+//!     assert!(fast.next_after(SystemTime::now()).is_some());
+//!     let scheduler = Scheduler::new().spawn(JobImpl);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Describing schedules
+//!
+//! Every schedule describes itself in plain text through `Schedule::describe`, also used by its `Display` implementation, and `Schedule::explain` adds the next fire time, so admin pages and logs can say what a job will do without decoding cron strings. Parsed schedules keep their source text in `expression` (or `phrase`). Custom schedules can override `describe`, which defaults to `custom schedule`:
+//!
+//! ```rust
+//! use async_periodic_job::{Cron, Every, Schedule};
+//! use std::time::{Duration, SystemTime};
+//!
+//! let every = Every::new(Duration::from_secs(6 * 3600));
+//! assert_eq!(every.to_string(), "every 6 hours, aligned to UTC");
+//!
+//! let cron = Cron::parse("0 30 9 * * MON-FRI").unwrap();
+//! assert_eq!(cron.describe(), "at 09:30 on weekdays, in UTC");
+//! println!("{}", cron.explain(SystemTime::now())); // at 09:30 on weekdays, in UTC, next at ...
+//! ```
+//!
+//! #### Schedule invariants
+//!
+//! `Schedule::fire_times` iterates the fire times after an instant, and `check_schedule` enumerates a schedule over a range while verifying its invariants: every fire is strictly after the instant it was computed from (so fires are monotonic and never repeat), all of them fall inside the range, and asking again from anywhere between two fires (including just before a fire) returns that same fire, so none is skipped across DST gaps or overlaps. It returns the fires on success and an `InvariantViolation` otherwise, which makes it a natural property for `proptest` or `quickcheck` in downstream code:
+//!
+//! ```rust
+//! use async_periodic_job::{Cron, Every, Schedule, TruncateZone, check_schedule};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+//! let end = start + Duration::from_secs(7 * 86_400);
+//! for zone in [TruncateZone::Utc, TruncateZone::Local] {
+//!     let fires = check_schedule(&Cron::parse("0 30 2 * * ?").unwrap().zone(zone), start..end).unwrap();
+//!     assert_eq!(fires.len(), 7);
+//!     let hourly = Every::new(Duration::from_secs(3600)).zone(zone);
+//!     assert_eq!(check_schedule(&hourly, start..end).unwrap().len(), 7 * 24 - 1);
+//! }
+//! let first: Vec<_> = Every::new(Duration::from_secs(60)).fire_times(start).take(3).collect();
+//! assert_eq!(first[2], start + Duration::from_secs(180));
+//! ```
+//!
+//! #### Backfill
+//!
+//! `JobHandle::backfill` replays the ticks a job would have fired over a historical range, one after the other, passing each tick to `RunContext::tick`. Backfilled runs bypass sampling and deduplication, don't move the job's live schedule and resolve to a `BackfillReport` once the range is exhausted, or `None` if the job stops first:
+//!
+//! ```rust
+//! use async_periodic_job::{Job, RunContext, Scheduler};
+//! use std::time::{Duration, SystemTime};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(3600)
+//!     }
+//!
+//!     async fn run_with_context(&mut self, ctx: RunContext) {
+//!         println!("Aggregating the hour ending at {:?}", ctx.tick());
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let scheduler = Scheduler::new();
+//!     let handle = scheduler.spawn_with_handle(JobImpl);
+//!     let to = SystemTime::now();
+//!     let from = to - Duration::from_secs(24 * 3600);
+//!     let report = handle.backfill(from, to).await.unwrap();
+//!     assert_eq!(report.runs, 24);
+//!     assert_eq!(report.failures, 0);
+//!     scheduler.stop().await;
+//! }
+//! ```
+//!
+//! #### Simulation
+//!
+//! Behind the `simulation` feature, `Simulation` drives a scheduler against a virtual clock instead of the wall clock. It pauses tokio time, so it needs a current-thread runtime that isn't already paused. `advance` moves time forward, firing every timer due along the way, and each run is recorded as a `Fire` with its tick, virtual start and finish times and its `Outcome`, so schedules can be asserted exactly without sleeping:
+//!
+//! ```rust
+//! # #[cfg(feature = "simulation")]
+//! # mod gated {
+//! use async_periodic_job::{Job, Outcome, Simulation};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! struct JobImpl;
+//! impl Job for JobImpl {
+//!     fn name(&self) -> &str {
+//!         "report"
+//!     }
+//!
+//!     fn period(&self) -> Duration {
+//!         Duration::from_secs(60)
+//!     }
+//!
+//!     fn with_truncate_time(&self) -> bool {
+//!         true
+//!     }
+//!
+//!     async fn run(&mut self) {}
+//! }
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! pub async fn main() {
+//!     let simulation = Simulation::new(UNIX_EPOCH + Duration::from_secs(1_700_000_040));
+//!     simulation.spawn(JobImpl);
+//!     simulation.advance(Duration::from_secs(190)).await;
+//!     let secs = |secs| Duration::from_secs(secs);
+//!     assert_eq!(simulation.fire_times("report"), [secs(60), secs(120), secs(180)]);
+//!     assert!(simulation.fires().iter().all(|fire| fire.outcome == Outcome::Succeeded));
+//!     simulation.stop().await;
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "simulation")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! `Simulation::from_scheduler` wraps a scheduler that was already configured with layers, stores or notifiers, and `scheduler()` exposes it for status checks and handles.
+//!
+//! #### Mock jobs and scenarios
+//!
+//! The `testing` feature (which enables `simulation`) adds a `test` module for exercising scheduler wiring without bespoke fakes. `MockJob` is a configurable job: `every`, `truncated`, `on_schedule`, `supervised`, `in_group` and `abortable` set its options, `taking` or `taking_each` make runs take virtual time and `panicking_on` panics on the given run numbers. Its `MockCounters` track runs, completions, panics, aborted runs and the highest number of overlapping runs, and can be shared between mocks with `sharing` to check exclusion groups and concurrency limits. `Scenario` spawns a set of mocks on a `Simulation` and runs them for a stretch of virtual time:
+//!
+//! ```rust
+//! # #[cfg(feature = "testing")]
+//! # mod gated {
+//! use async_periodic_job::Supervisor;
+//! use async_periodic_job::test::{MockCounters, MockJob, Scenario};
+//! use std::time::Duration;
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! pub async fn main() {
+//!     let database = MockCounters::default();
+//!     let report = Scenario::new()
+//!         .job(
+//!             MockJob::new("flaky")
+//!                 .every(Duration::from_secs(10))
+//!                 .panicking_on([2])
+//!                 .supervised(Supervisor::new()),
+//!         )
+//!         .job(MockJob::new("a").taking(Duration::from_secs(3)).in_group("db").sharing(&database))
+//!         .job(MockJob::new("b").taking(Duration::from_secs(3)).in_group("db").sharing(&database))
+//!         .run_for(Duration::from_secs(60))
+//!         .await;
+//!     assert_eq!(report.counters("flaky").panics(), 1);
+//!     assert_eq!(report.counters("flaky").runs(), 6);
+//!     assert_eq!(database.max_running(), 1);
+//!     assert!(report.stop.is_ok());
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "testing")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! Scenarios start at the Unix epoch unless given `starting_at`, and `Scenario::with_scheduler` runs the mocks on a scheduler that was configured beforehand.
+//!
+//! #### Cancellation races
+//!
+//! Once the scheduler is cancelled a job never starts another run, even when a catch-up tick, a backfill tick or a manual trigger is already due as the cancelled run finishes. The driver and the exclusion group, concurrency and rate limit layers check cancellation before anything else that is ready, so on a `Simulation` these races play out the same way every time and can be asserted directly:
+//!
+//! ```rust
+//! # #[cfg(feature = "testing")]
+//! # mod gated {
+//! use async_periodic_job::test::MockJob;
+//! use async_periodic_job::{Job, JobOptions, Misfire, Simulation};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! pub async fn main() {
+//!     let simulation = Simulation::new(UNIX_EPOCH);
+//!     let job = MockJob::new("slow")
+//!         .every(Duration::from_secs(10))
+//!         .taking(Duration::from_secs(15));
+//!     let counters = job.counters();
+//!     let handle = simulation.spawn(job.configure(JobOptions {
+//!         misfire: Some(Misfire::FireNowOnce),
+//!         ..JobOptions::default()
+//!     }));
+//!     simulation.advance(Duration::from_secs(22)).await;
+//!     handle.trigger();
+//!     let runs = counters.runs();
+//!     simulation.stop().await;
+//!     assert_eq!(counters.runs(), runs);
+//!     assert_eq!(counters.aborted(), 0);
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "testing")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! Pausing and resuming race shutdown the same way. A run in progress when the job is disabled finishes, and a job enabled and triggered as the scheduler stops doesn't start again. A trigger while the job is disabled is skipped like any other tick, and isn't kept for when it's enabled again:
+//!
+//! ```rust
+//! # #[cfg(feature = "testing")]
+//! # mod gated {
+//! use async_periodic_job::Simulation;
+//! use async_periodic_job::test::MockJob;
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! pub async fn main() {
+//!     let simulation = Simulation::new(UNIX_EPOCH);
+//!     let slow = MockJob::new("slow")
+//!         .every(Duration::from_secs(10))
+//!         .taking(Duration::from_secs(15));
+//!     let idle = MockJob::new("idle").every(Duration::from_secs(10));
+//!     let (slow_counters, idle_counters) = (slow.counters(), idle.counters());
+//!     let slow = simulation.spawn(slow);
+//!     let idle = simulation.spawn(idle);
+//!     idle.disable();
+//!     simulation.advance(Duration::from_secs(22)).await;
+//!     idle.trigger();
+//!     simulation.advance(Duration::from_secs(1)).await;
+//!     idle.enable();
+//!     simulation.advance(Duration::from_millis(100)).await;
+//!     assert_eq!(idle_counters.runs(), 0);
+//!     assert_eq!(slow_counters.running(), 1);
+//!     slow.disable();
+//!     idle.trigger();
+//!     let report = simulation.stop().await;
+//!     assert_eq!(slow_counters.runs(), 1);
+//!     assert_eq!(slow_counters.completed(), 1);
+//!     assert_eq!(idle_counters.runs(), 0);
+//!     assert!(report.failures.is_empty());
+//! }
+//! # }
+//! # fn main() {
+//! #     #[cfg(feature = "testing")]
+//! #     gated::main();
+//! # }
+//! ```
+//!
+//! ## License
+//!
+//! MIT
+//!
+//! Contributions and suggestions are welcome!
+
+mod adaptive;
+mod backfill;
+mod batch;
+mod budget;
+mod cache;
+mod calendar;
+#[cfg(feature = "chaos")]
+mod chaos;
+mod checkpoint;
+mod chunk;
+mod clock;
+mod completion;
+#[cfg(feature = "config")]
+mod config;
+mod console;
+mod context;
+mod cron;
+mod dedup;
+mod env;
+mod error;
+mod event;
+mod factory;
+mod fallible;
+mod finish;
+mod group;
+mod handle;
+#[cfg(feature = "serde")]
+mod history;
+mod invariants;
+#[cfg(feature = "serde")]
+mod journal;
+mod layer;
+mod limit;
+mod load;
+mod message;
+mod misfire;
+#[cfg(feature = "natural")]
+mod natural;
+mod notify;
+mod options;
+mod output;
+mod period;
+mod permit;
+mod quarantine;
+mod ramp;
+mod rate_limit;
+mod repeating;
+#[cfg(feature = "serde")]
+mod replay;
+mod report;
+mod sampling;
+mod schedule;
+mod scope;
+mod selection;
+mod shard;
+#[cfg(feature = "simulation")]
+mod simulation;
+mod snapshot;
+mod spread;
+mod stagger;
+mod startup;
+mod statsd;
+mod status;
+mod store;
+mod stream;
+mod supervisor;
+mod suspend;
+mod telemetry;
+mod template;
+mod tenant;
+#[cfg(feature = "signal")]
+mod terminate;
+#[cfg(feature = "testing")]
+pub mod test;
+mod tick;
+mod timer;
+mod trigger;
+mod truncate;
+mod validate;
+#[cfg(feature = "http")]
+mod webhook;
+
+pub use backfill::BackfillReport;
+pub use batch::{BatchJob, Batched, WorkTokens};
+pub use budget::Budget;
+pub use cache::{Cached, CachedJob};
+pub use calendar::{Calendar, ParseCalendarError};
+#[cfg(feature = "chaos")]
+pub use chaos::Chaos;
+#[cfg(feature = "serde")]
+pub use checkpoint::StatefulJob;
+pub use chrono::Weekday;
+pub use chunk::{Chunk, Chunked, ChunkedJob, Continuation};
+pub use completion::Completion;
+#[cfg(feature = "config")]
+pub use config::{ConfigError, ConfigScheduler};
+pub use console::Console;
+pub use context::RunContext;
+pub use cron::{Cron, ParseCronError};
+pub use env::EnvError;
+pub use error::Error;
+pub use event::{Event, EventKind, SkipReason};
+pub use factory::{Factory, JobFactory};
+pub use fallible::{Fallible, FallibleJob};
+pub use handle::JobHandle;
+#[cfg(feature = "serde")]
+pub use history::{Retention, RunRecord};
+pub use invariants::{InvariantViolation, check_schedule};
+#[cfg(feature = "serde")]
+pub use journal::EventLog;
+pub use layer::{Layer, Next, Run};
+pub use limit::PeriodLimit;
+pub use load::{CpuLoad, LoadGate, LoadPolicy, MemoryPressure};
+pub use message::MessageJob;
+pub use misfire::Misfire;
+#[cfg(feature = "natural")]
+pub use natural::{ParsePhraseError, Phrase};
+pub use notify::{Alert, AlertKind, AlertRule, Notifier};
+pub use options::{Configured, JobOptions, Labels};
+pub use output::OutputJob;
+pub use period::{ParsePeriodError, Period};
+pub use permit::{PermitProvider, Permits};
+pub use quarantine::Quarantine;
+pub use rate_limit::RateLimit;
+pub use repeating::{ParseRepeatingError, Repeating};
+#[cfg(feature = "serde")]
+pub use replay::{Replay, ReplayedTick, TickDecision};
+pub use report::RunStatus;
+pub use sampling::Sampling;
+pub use schedule::{Every, Filtered, FireTimes, Schedule, Union};
+pub use scope::{Scope, scope};
+pub use selection::{JobFilter, JobSet};
+#[cfg(feature = "simulation")]
+pub use simulation::{Fire, Outcome, Simulation};
+pub use snapshot::{JobState, SchedulerState};
+pub use spread::Spread;
+pub use startup::StartupJob;
+pub use statsd::Statsd;
+pub use status::{JobStats, JobStatus, Status};
+pub use store::{FileStore, MemoryStore, Store, StoreFuture};
+pub use supervisor::{Escalation, Supervisor};
+pub use suspend::SuspendPolicy;
+pub use telemetry::LogLevel;
+pub use template::Template;
+pub use tenant::Quota;
+pub use timer::TimerMode;
+pub use trigger::{TriggerDecision, TriggerPolicy};
+pub use truncate::{DstGap, DstOverlap, DstPolicy, TruncateZone};
+pub use validate::SpawnError;
+#[cfg(feature = "http")]
+pub use webhook::WebhookNotifier;
+
+use adaptive::Adaptive;
+use backfill::Backfill;
+use budget::Spending;
+use checkpoint::Checkpoint;
+use clock::Clock;
+use context::{Deadline, States};
+use dedup::Dedup;
+use event::Events;
+use fallible::ErrorHandlers;
+use finish::FinishBy;
+use futures_core::Stream;
+use group::Groups;
+use handle::Swap;
+#[cfg(feature = "serde")]
+use history::RunHistory;
+use layer::Layers;
+use load::LoadGates;
+use message::Mailbox;
+use notify::{Alerting, Notifiers};
+use output::Publisher;
+use quarantine::Strikes;
+use ramp::Ramp;
+use sampling::Sampler;
+use shard::Shards;
+use snapshot::Imported;
+use spread::Spreader;
+use startup::{Gate, Startups};
+use status::{Entry, Finished, Interruption, JobShared};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::{pending, poll_fn};
+use std::hash::Hash;
+use std::io;
+use std::panic::resume_unwind;
+use std::pin::pin;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use stream::Streamed;
+use supervisor::{CatchUnwind, Restarts};
+use telemetry::Active;
+use template::{Registered, Templates};
+use tenant::Concurrency;
+use tick::TickStore;
+use timer::Timers;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::select;
+use tokio::sync::broadcast;
+use tokio::task::{JoinError, JoinHandle, block_in_place};
+use tokio::time::{Instant, sleep, sleep_until, timeout_at};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+use tokio_util::task::TaskTracker;
+use trigger::Triggers;
+use truncate::truncate_period;
+
+const MIN_PERIOD: Duration = Duration::from_millis(1);
+const OUTPUT_CAPACITY: usize = 16;
+const MAX_BACKFILLS: usize = 16;
+const MAILBOX_CAPACITY: usize = 64;
+const DEDUP_RETENTION: Duration = Duration::from_secs(60 * 60);
+const LIVENESS_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+pub type Token = CancellationToken;
+
+pub trait Job: Send + 'static {
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        true
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        TruncateZone::Utc
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        DstPolicy::default()
+    }
+
+    fn run(&mut self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    fn with_cancel(&self) -> bool {
+        false
+    }
+
+    fn run_with_cancel(&mut self, _token: Token) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    fn run_with_context(&mut self, ctx: RunContext) -> impl Future<Output = ()> + Send {
+        async move {
+            if self.with_cancel() {
+                self.run_with_cancel(ctx.token()).await;
+            } else {
+                self.run().await
+            }
+        }
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        None
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        Vec::new()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        None
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        None
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        None
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    fn labels(&self) -> Labels {
+        Labels::new()
+    }
+
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn abortable(&self) -> bool {
+        true
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        TriggerPolicy::default()
+    }
+
+    fn log_level(&self) -> LogLevel {
+        LogLevel::default()
+    }
+
+    fn dry_run(&self) -> bool {
+        false
+    }
+
+    fn stagger(&self) -> bool {
+        false
+    }
+
+    fn finish_by(&self) -> bool {
+        false
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        None
+    }
+
+    fn sampling(&self) -> Sampling {
+        Sampling::Always
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        None
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        None
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        None
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        None
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        None
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        SuspendPolicy::RunNow
+    }
+
+    fn misfire(&self) -> Misfire {
+        Misfire::SkipToNext
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        LoadPolicy::Ignore
+    }
+
+    fn durable(&self) -> bool {
+        false
+    }
+
+    fn dedup(&self) -> bool {
+        false
+    }
+
+    fn configure(self, options: JobOptions) -> Configured<Self>
+    where
+        Self: Sized,
+    {
+        Configured::new(self, options)
+    }
+}
+
+#[derive(Debug)]
+pub struct JobFailure {
+    pub name: String,
+    pub error: JoinError,
+}
+
+#[derive(Debug, Default)]
+pub struct StopReport {
+    pub failures: Vec<JobFailure>,
+    pub aborted: Vec<String>,
+    pub interrupted: Vec<String>,
+    pub unresponsive: Vec<String>,
+}
+
+impl StopReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty() && self.aborted.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), Error> {
+        if !self.failures.is_empty() {
+            return Err(Error::Jobs(self.failures));
+        }
+        match self.aborted.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Aborted(self.aborted)),
+        }
+    }
+}
+
+pub struct Scheduler {
+    tracker: TaskTracker,
+    token: Token,
+    root: Token,
+    handles: Arc<Mutex<Vec<Entry>>>,
+    children: Arc<Mutex<Vec<Scheduler>>>,
+    tenants: Arc<Mutex<HashMap<String, Scheduler>>>,
+    templates: Templates,
+    states: States,
+    layers: Layers,
+    groups: Groups,
+    notifiers: Notifiers,
+    error_handlers: ErrorHandlers,
+    load_gates: LoadGates,
+    events: Events,
+    env: bool,
+    store: Option<Arc<dyn Store>>,
+    deadline: Deadline,
+    gate: Gate,
+    startups: Arc<Mutex<Startups>>,
+    dry_run: bool,
+    stagger_key: Arc<str>,
+    min_period: Duration,
+    output_capacity: usize,
+    max_backfills: usize,
+    mailbox_capacity: usize,
+    dedup_retention: Duration,
+    liveness_timeout: Duration,
+    cancel_grace: Option<Duration>,
+    shards: Shards,
+    spread: Spreader,
+    timers: Timers,
+    ramp: Option<Ramp>,
+    abort_on_panic: Option<Token>,
+    #[cfg(feature = "serde")]
+    history: Option<Retention>,
+    imported: Imported,
+    clock: Clock,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        let token = Token::new();
+        let tracker = TaskTracker::new();
+        Self {
+            timers: Timers::new(TimerMode::Auto, tracker.clone(), token.clone()),
+            tracker,
+            root: token.clone(),
+            token,
+            handles: Arc::default(),
+            children: Arc::default(),
+            tenants: Arc::default(),
+            templates: Templates::default(),
+            states: States::default(),
+            layers: Layers::default(),
+            groups: Groups::default(),
+            notifiers: Notifiers::default(),
+            error_handlers: ErrorHandlers::default(),
+            load_gates: LoadGates::default(),
+            events: Events::default(),
+            env: false,
+            store: None,
+            deadline: Deadline::default(),
+            gate: Gate::default(),
+            startups: Arc::default(),
+            dry_run: false,
+            stagger_key: stagger::hostname().into(),
+            min_period: MIN_PERIOD,
+            output_capacity: OUTPUT_CAPACITY,
+            max_backfills: MAX_BACKFILLS,
+            mailbox_capacity: MAILBOX_CAPACITY,
+            dedup_retention: DEDUP_RETENTION,
+            liveness_timeout: LIVENESS_TIMEOUT,
+            cancel_grace: None,
+            shards: Shards::default(),
+            spread: Spreader::default(),
+            ramp: None,
+            abort_on_panic: None,
+            #[cfg(feature = "serde")]
+            history: None,
+            imported: Imported::default(),
+            clock: Clock::System,
+        }
+    }
+
+    fn share(&self) -> Self {
+        Self {
+            tracker: self.tracker.clone(),
+            token: self.token.clone(),
+            root: self.root.clone(),
+            handles: self.handles.clone(),
+            children: self.children.clone(),
+            tenants: self.tenants.clone(),
+            templates: self.templates.clone(),
+            states: self.states.clone(),
+            layers: self.layers.clone(),
+            groups: self.groups.clone(),
+            notifiers: self.notifiers.clone(),
+            error_handlers: self.error_handlers.clone(),
+            load_gates: self.load_gates.clone(),
+            events: self.events.clone(),
+            env: self.env,
+            store: self.store.clone(),
+            deadline: self.deadline.clone(),
+            gate: self.gate.clone(),
+            startups: self.startups.clone(),
+            dry_run: self.dry_run,
+            stagger_key: self.stagger_key.clone(),
+            min_period: self.min_period,
+            output_capacity: self.output_capacity,
+            max_backfills: self.max_backfills,
+            mailbox_capacity: self.mailbox_capacity,
+            dedup_retention: self.dedup_retention,
+            liveness_timeout: self.liveness_timeout,
+            cancel_grace: self.cancel_grace,
+            shards: self.shards.clone(),
+            spread: self.spread.clone(),
+            timers: self.timers.clone(),
+            ramp: self.ramp.clone(),
+            abort_on_panic: self.abort_on_panic.clone(),
+            #[cfg(feature = "serde")]
+            history: self.history,
+            imported: self.imported.clone(),
+            clock: self.clock,
+        }
+    }
+
+    pub fn with_state<S: Any + Send + Sync>(mut self, state: Arc<S>) -> Self {
+        Arc::make_mut(&mut self.states).insert(TypeId::of::<S>(), state);
+        self
+    }
+
+    pub fn layer(mut self, layer: impl Layer) -> Self {
+        Arc::make_mut(&mut self.layers).push(Arc::new(layer));
+        self
+    }
+
+    pub fn notifier(mut self, notifier: impl Notifier) -> Self {
+        Arc::make_mut(&mut self.notifiers).push(Arc::new(notifier));
+        self
+    }
+
+    pub fn on_error(
+        mut self,
+        handler: impl Fn(&str, &(dyn std::error::Error + Send + Sync + 'static)) + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.error_handlers).push(Arc::new(handler));
+        self
+    }
+
+    pub fn load_gate(mut self, gate: impl LoadGate) -> Self {
+        Arc::make_mut(&mut self.load_gates).push(Arc::new(gate));
+        self
+    }
+
+    pub fn store(mut self, store: impl Store) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn run_history(mut self, retention: Retention) -> Self {
+        self.history = Some(retention);
+        self
+    }
+
+    pub fn console(self, console: Console) -> Self {
+        let scheduler = self.share();
+        let token = self.token.clone();
+        if let Ok(runtime) = Handle::try_current() {
+            self.tracker
+                .spawn_on(console.report(move || scheduler.status(), token), &runtime);
+        }
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn event_log(self, mut log: EventLog) -> Self {
+        self.events.sink(move |event| log.write(event));
+        self
+    }
+
+    pub fn with_env_overrides(mut self) -> Self {
+        self.env = true;
+        self
+    }
+
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    pub fn stagger_key(mut self, key: impl Into<String>) -> Self {
+        self.stagger_key = key.into().into();
+        self
+    }
+
+    pub fn min_period(mut self, floor: Duration) -> Self {
+        self.min_period = floor;
+        self
+    }
+
+    pub fn event_capacity(mut self, capacity: usize) -> Self {
+        self.events = self.events.resize(capacity);
+        self
+    }
+
+    pub fn output_capacity(mut self, capacity: usize) -> Self {
+        self.output_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn max_pending_backfills(mut self, requests: usize) -> Self {
+        self.max_backfills = requests;
+        self
+    }
+
+    pub fn mailbox_capacity(mut self, messages: usize) -> Self {
+        self.mailbox_capacity = messages.max(1);
+        self
+    }
+
+    pub fn liveness_timeout(mut self, timeout: Duration) -> Self {
+        self.liveness_timeout = timeout;
+        self
+    }
+
+    pub fn dedup_retention(mut self, retention: Duration) -> Self {
+        self.dedup_retention = retention;
+        self
+    }
+
+    pub fn cancel_grace(mut self, grace: Duration) -> Self {
+        self.cancel_grace = Some(grace);
+        self
+    }
+
+    pub fn shards(mut self, runtimes: impl IntoIterator<Item = Handle>) -> Self {
+        self.shards = Shards::new(runtimes);
+        self
+    }
+
+    pub fn abort_on_panic(mut self) -> Self {
+        self.abort_on_panic = Some(self.token.clone());
+        self
+    }
+
+    pub fn ramp_up(mut self, window: Duration) -> Self {
+        self.ramp = Some(Ramp::new(window));
+        self
+    }
+
+    pub fn spread(mut self, spread: Spread) -> Self {
+        self.spread = Spreader::new(spread);
+        self
+    }
+
+    pub fn timer_mode(mut self, mode: TimerMode) -> Self {
+        self.timers = Timers::new(mode, self.tracker.clone(), self.token.clone());
+        self
+    }
+
+    pub fn child(&self) -> Scheduler {
+        let child = Self {
+            tracker: TaskTracker::new(),
+            token: self.token.child_token(),
+            handles: Arc::default(),
+            children: Arc::default(),
+            tenants: Arc::default(),
+            templates: Templates::default(),
+            deadline: Deadline::default(),
+            startups: Arc::default(),
+            ..self.share()
+        };
+        self.children.lock().unwrap().push(child.share());
+        child
+    }
+
+    pub fn startup(self, mut job: impl StartupJob) -> Self {
+        let name = job.name().to_string();
+        self.hold(name, async move { job.run().await })
+    }
+
+    pub fn wait_ready(self, ready: impl Future<Output = ()> + Send + 'static) -> Self {
+        let token = self.token.clone();
+        self.hold("wait_ready".into(), async move {
+            select! {
+                _ = token.cancelled() => {}
+                _ = ready => {}
+            }
+        })
+    }
+
+    fn hold(self, name: String, future: impl Future<Output = ()> + Send + 'static) -> Self {
+        let pass = self.gate.enter(self.token.clone());
+        let future = async move {
+            let _pass = pass;
+            future.await;
+        };
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, tracing::info_span!("startup", name));
+        let Ok(runtime) = Handle::try_current() else {
+            #[cfg(feature = "tracing")]
+            tracing::error!(
+                name,
+                "no tokio runtime is available, cancelling the scheduler"
+            );
+            self.token.cancel();
+            return self;
+        };
+        match self.spawn_task(&name, &runtime, future) {
+            Ok(handle) => self.startups.lock().unwrap().push((name, handle)),
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(name, error = %_error, "failed to spawn, cancelling the scheduler");
+                self.token.cancel();
+            }
+        }
+        self
+    }
+
+    pub fn max_concurrency(self, runs: usize) -> Self {
+        self.layer(Concurrency::new(runs))
+    }
+
+    pub fn tenant(&self, name: &str, quota: Quota) -> Scheduler {
+        let mut tenants = self.tenants.lock().unwrap();
+        if let Some(tenant) = tenants.get(name) {
+            return tenant.share();
+        }
+        let mut tenant = self.child();
+        if let Some(runs) = quota.concurrency {
+            tenant = tenant.max_concurrency(runs);
+        }
+        if let Some(rate_limit) = quota.rate_limit {
+            tenant = tenant.layer(rate_limit);
+        }
+        tenants.insert(name.to_string(), tenant.share());
+        tenant
+    }
+
+    pub fn register_template<P, J>(
+        &self,
+        name: &str,
+        create: impl Fn(&P) -> J + Send + Sync + 'static,
+    ) -> Template<P, J>
+    where
+        P: Clone + Eq + Hash + Display + Send + 'static,
+        J: Job,
+    {
+        let registered = Arc::new(Registered::new(name, create));
+        self.templates
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), registered.clone());
+        Template::new(registered, self.share())
+    }
+
+    pub fn template<P, J>(&self, name: &str) -> Option<Template<P, J>>
+    where
+        P: Clone + Eq + Hash + Display + Send + 'static,
+        J: Job,
+    {
+        let registered = self.templates.lock().unwrap().get(name)?.clone();
+        let registered = registered.downcast::<Registered<P, J>>().ok()?;
+        Some(Template::new(registered, self.share()))
+    }
+
+    pub fn tenants(&self) -> Vec<String> {
+        self.tenants.lock().unwrap().keys().cloned().collect()
+    }
+
+    pub async fn stop_tenant(&self, name: &str) -> Option<StopReport> {
+        let tenant = self.tenants.lock().unwrap().remove(name)?;
+        self.children
+            .lock()
+            .unwrap()
+            .retain(|child| !Arc::ptr_eq(&child.handles, &tenant.handles));
+        Some(tenant.stop().await)
+    }
+
+    pub fn spawn(self, job: impl Job) -> Self {
+        self.spawn_with_handle(job);
+        self
+    }
+
+    pub fn spawn_with_handle<J: Job>(&self, job: J) -> JobHandle<J> {
+        self.spawn_job(job, None, None, None)
+    }
+
+    pub fn spawn_factory(self, factory: impl JobFactory) -> Self {
+        self.spawn(Factory::new(factory))
+    }
+
+    pub fn spawn_stream<S>(self, stream: S, min_interval: Duration, job: impl Job) -> Self
+    where
+        S: Stream + Send + 'static,
+    {
+        let handle = self.spawn_with_handle(Streamed::new(job, min_interval));
+        let Ok(runtime) = Handle::try_current() else {
+            return self;
+        };
+        let token = self.token.clone();
+        self.tracker.spawn_on(
+            async move {
+                let mut stream = pin!(stream);
+                loop {
+                    select! {
+                        biased;
+                        _ = token.cancelled() => break,
+                        item = poll_fn(|cx| stream.as_mut().poll_next(cx)) => match item {
+                            Some(_) => handle.trigger(),
+                            None => break,
+                        },
+                    }
+                }
+            },
+            &runtime,
+        );
+        self
+    }
+
+    pub fn try_spawn<J: Job>(&self, job: J) -> Result<JobHandle<J>, SpawnError> {
+        self.check(&job)?;
+        let (handle, spawned) = self.start_job(job, None, None, None);
+        spawned.map(|()| handle)
+    }
+
+    pub fn try_spawn_with_outputs<J: OutputJob>(&self, job: J) -> Result<JobHandle<J>, SpawnError> {
+        self.check(&job)?;
+        let publisher = Publisher::new(self.output_capacity);
+        let (handle, spawned) = self.start_job(job, None, Some(publisher), None);
+        spawned.map(|()| handle)
+    }
+
+    pub fn try_spawn_with_mailbox<J: MessageJob>(
+        &self,
+        job: J,
+    ) -> Result<JobHandle<J>, SpawnError> {
+        self.check(&job)?;
+        let mailbox = Mailbox::new(self.mailbox_capacity);
+        let (handle, spawned) = self.start_job(job, None, None, Some(mailbox));
+        spawned.map(|()| handle)
+    }
+
+    fn check<J: Job>(&self, job: &J) -> Result<(), SpawnError> {
+        let options = match self.env {
+            true => JobOptions::from_env(job.name()).map_err(|error| SpawnError::InvalidEnv {
+                name: job.name().to_string(),
+                error,
+            })?,
+            false => JobOptions::default(),
+        };
+        validate::validate(job, &options)?;
+        if self
+            .status()
+            .jobs
+            .iter()
+            .any(|status| status.name == job.name())
+        {
+            let name = job.name().to_string();
+            return Err(SpawnError::DuplicateName { name });
+        }
+        let shard = self.shards.pick(job.shard_key().unwrap_or(job.name()));
+        if shard::runtime(shard).is_none() {
+            let name = job.name().to_string();
+            return Err(SpawnError::RuntimeUnavailable { name });
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn spawn_stateful(self, job: impl StatefulJob) -> Self {
+        self.spawn_stateful_with_handle(job);
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn spawn_stateful_with_handle<J: StatefulJob>(&self, job: J) -> JobHandle<J> {
+        let checkpoint = self
+            .store
+            .clone()
+            .map(|store| Checkpoint::new(store, job.name()));
+        self.spawn_job(job, checkpoint, None, None)
+    }
+
+    pub fn spawn_with_outputs<J: OutputJob>(&self, job: J) -> JobHandle<J> {
+        let publisher = Publisher::new(self.output_capacity);
+        self.spawn_job(job, None, Some(publisher), None)
+    }
+
+    pub fn spawn_with_mailbox<J: MessageJob>(&self, job: J) -> JobHandle<J> {
+        let mailbox = Mailbox::new(self.mailbox_capacity);
+        self.spawn_job(job, None, None, Some(mailbox))
+    }
+
+    fn spawn_job<J: Job>(
+        &self,
+        job: J,
+        checkpoint: Option<Checkpoint<J>>,
+        publisher: Option<Publisher<J>>,
+        mailbox: Option<Mailbox<J>>,
+    ) -> JobHandle<J> {
+        self.start_job(job, checkpoint, publisher, mailbox).0
+    }
+
+    fn start_job<J: Job>(
+        &self,
+        job: J,
+        checkpoint: Option<Checkpoint<J>>,
+        publisher: Option<Publisher<J>>,
+        mailbox: Option<Mailbox<J>>,
+    ) -> (JobHandle<J>, Result<(), SpawnError>) {
+        let shared = Arc::new(JobShared::new(
+            &job,
+            self.max_backfills,
+            self.events.clone(),
+        ));
+        if let Some(state) = self.imported.lock().unwrap().remove(&*shared.name) {
+            state.restore(&shared);
+        }
+        if self.env {
+            let (overrides, _) = env::overrides(&shared.name);
+            shared
+                .options
+                .send_modify(|options| *options = options.clone().merge(overrides));
+        }
+        if let Err(_error) = validate::validate(&job, &shared.options.borrow()) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %_error, "spawning a job with an invalid configuration");
+        }
+        let swap = Swap::default();
+        let name = shared.name.clone();
+        let outputs = publisher.as_ref().map(Publisher::outputs);
+        let post = mailbox.as_ref().map(Mailbox::post);
+        #[cfg(feature = "tracing")]
+        let span = shared
+            .options
+            .borrow()
+            .log_level
+            .unwrap_or(job.log_level())
+            .job_span(&name);
+        let shard = self.shards.pick(job.shard_key().unwrap_or(&name));
+        let Some(runtime) = shard::runtime(shard) else {
+            #[cfg(feature = "tracing")]
+            tracing::error!(
+                job = &*name,
+                "no tokio runtime is available, job not spawned"
+            );
+            drop(Finished(shared.clone()));
+            let name = name.to_string();
+            let handle = JobHandle::new(shared, swap, outputs, post);
+            return (handle, Err(SpawnError::RuntimeUnavailable { name }));
+        };
+        self.timers
+            .start(&Handle::try_current().unwrap_or_else(|_| runtime.clone()));
+        let future = self.drive(
+            job,
+            shared.clone(),
+            swap.clone(),
+            checkpoint,
+            publisher,
+            mailbox,
+        );
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        let spawned = match self.spawn_task(&name, &runtime, future) {
+            Ok(handle) => {
+                self.handles.lock().unwrap().push(Entry {
+                    shared: shared.clone(),
+                    handle,
+                });
+                Ok(())
+            }
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(job = &*name, error = %_error, "failed to spawn the job");
+                drop(Finished(shared.clone()));
+                let name = name.to_string();
+                Err(SpawnError::RuntimeUnavailable { name })
+            }
+        };
+        (JobHandle::new(shared, swap, outputs, post), spawned)
+    }
+
+    #[cfg(all(feature = "tracing", tokio_unstable))]
+    fn spawn_task<F>(&self, name: &str, runtime: &Handle, future: F) -> io::Result<JoinHandle<()>>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let name = format!("job:{name}");
+        let builder = tokio::task::Builder::new().name(&name);
+        let future = self.tracker.track_future(future);
+        builder.spawn_on(future, runtime)
+    }
+
+    #[cfg(not(all(feature = "tracing", tokio_unstable)))]
+    fn spawn_task<F>(&self, _name: &str, runtime: &Handle, future: F) -> io::Result<JoinHandle<()>>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        Ok(self.tracker.spawn_on(future, runtime))
+    }
+
+    fn drive<J: Job>(
+        &self,
+        mut job: J,
+        shared: Arc<JobShared>,
+        swap: Swap<J>,
+        checkpoint: Option<Checkpoint<J>>,
+        publisher: Option<Publisher<J>>,
+        mailbox: Option<Mailbox<J>>,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let name = shared.name.clone();
+        let mut options = shared.options.subscribe();
+        let token = self.token.clone();
+        let root = self.root.clone();
+        let states = self.states.clone();
+        let scheduler_layers = self.layers.clone();
+        let groups = self.groups.clone();
+        let mut restarts = Restarts::new(job.supervisor());
+        let mut sampler = Sampler::new(job.sampling());
+        let mut adaptive = Adaptive::new(job.max_duty_cycle());
+        let mut finish_by = FinishBy::default();
+        let mut spending = Spending::new(job.budget());
+        let mut strikes = Strikes::default();
+        let mut triggers = Triggers::new(job.trigger_policy(), self.timers.clone());
+        let mut backfill = Backfill::default();
+        let deadline = shared.abortable.then(|| self.deadline.clone());
+        let gate = self.gate.clone();
+        let ramp = self.ramp.as_ref().map(Ramp::start);
+        let abort_on_panic = self.abort_on_panic.clone();
+        let dry_run = self.dry_run;
+        let stagger_key = self.stagger_key.clone();
+        let spread = self.spread.clone();
+        let member = {
+            let options = shared.options.borrow();
+            let truncate = options
+                .truncate_time
+                .unwrap_or_else(|| job.with_truncate_time());
+            let period = options.period.map_or_else(|| job.period(), Duration::from);
+            let truncated = truncate && job.schedule().is_none();
+            spread.register(&name, truncated.then_some(period.max(self.min_period)))
+        };
+        let clock = self.clock;
+        let min_period = self.min_period;
+        let intents = self
+            .store
+            .clone()
+            .filter(|_| job.durable())
+            .map(|store| TickStore::new(store, format!("intent/{name}")));
+        let ticks = self
+            .store
+            .clone()
+            .map(|store| TickStore::new(store, format!("tick/{name}")));
+        let mut dedup = self
+            .store
+            .clone()
+            .filter(|_| job.dedup())
+            .map(|store| Dedup::new(store, self.dedup_retention));
+        #[cfg(feature = "serde")]
+        let history = self
+            .store
+            .clone()
+            .zip(self.history)
+            .map(|(store, retention)| RunHistory::new(store, &name, retention));
+        let events = self.events.clone();
+        let labels = shared.labels();
+        let load_gates = self.load_gates.clone();
+        let error_handlers = self.error_handlers.clone();
+        let notifiers = self.notifiers.clone();
+        let tracker = self.tracker.clone();
+        let finished = Finished(shared.clone());
+        async move {
+            let _finished = finished;
+            select! {
+                biased;
+                _ = token.cancelled() => {
+                    events.emit(&name, EventKind::Cancelled { interrupted: false });
+                    return;
+                },
+                _ = gate.opened() => {}
+            }
+            if let Some(start) = ramp {
+                select! {
+                    biased;
+                    _ = token.cancelled() => {
+                        events.emit(&name, EventKind::Cancelled { interrupted: false });
+                        return;
+                    },
+                    _ = sleep_until(start) => {}
+                }
+            }
+            shared.progressed();
+            let _active = Active::new(&name, &labels);
+            if let Some(checkpoint) = &checkpoint {
+                checkpoint.restore(&mut job).await;
+            }
+            let mut recovered = match &intents {
+                Some(intents) => intents.load().await,
+                None => None,
+            };
+            let misfire = options.borrow().misfire.unwrap_or_else(|| job.misfire());
+            let mut last = match &ticks {
+                Some(ticks) if misfire != Misfire::SkipToNext => ticks.load().await,
+                _ => None,
+            };
+            let mut overrides = options.borrow_and_update().clone();
+            let stack = |job: &J, overrides: &JobOptions| {
+                let mut layers = scheduler_layers.to_vec();
+                layers.extend(job.layers());
+                let group = overrides
+                    .exclusion_group
+                    .as_deref()
+                    .or(job.exclusion_group());
+                if let Some(group) = group {
+                    layers.push(Arc::new(groups.get(group)) as Arc<dyn Layer>);
+                }
+                layers
+            };
+            let alert = |job: &J, overrides: &JobOptions| {
+                let watching = token.child_token();
+                let alerting = Alerting::new(
+                    name.clone(),
+                    overrides.alert_rule.or_else(|| job.alert_rule()),
+                    notifiers.clone(),
+                    tracker.clone(),
+                );
+                alerting.watch(watching.clone());
+                (alerting, watching.drop_guard())
+            };
+            let mut layers = stack(&job, &overrides);
+            let (mut alerting, mut _watching) = alert(&job, &overrides);
+            let mut floored = None;
+            let mut limited = None;
+            let mut previous = None;
+            let mut started = false;
+            loop {
+                if shared.is_quarantined() {
+                    shared.schedule(None);
+                    shared.waiting(Duration::MAX);
+                    select! {
+                        biased;
+                        _ = token.cancelled() => {
+                            events.emit(&name, EventKind::Cancelled { interrupted: shared.interrupted() });
+                            break;
+                        },
+                        _ = shared.released() => {}
+                    }
+                }
+                if options.has_changed().unwrap_or(false) {
+                    let previous =
+                        std::mem::replace(&mut overrides, options.borrow_and_update().clone());
+                    if previous.supervisor != overrides.supervisor {
+                        restarts = Restarts::new(overrides.supervisor.or_else(|| job.supervisor()));
+                    }
+                    if previous.sampling != overrides.sampling {
+                        sampler =
+                            Sampler::new(overrides.sampling.unwrap_or_else(|| job.sampling()));
+                    }
+                    if previous.max_duty_cycle != overrides.max_duty_cycle {
+                        adaptive = Adaptive::new(
+                            overrides.max_duty_cycle.or_else(|| job.max_duty_cycle()),
+                        );
+                    }
+                    if previous.budget != overrides.budget {
+                        spending = Spending::new(overrides.budget.or_else(|| job.budget()));
+                    }
+                    if previous.exclusion_group != overrides.exclusion_group {
+                        layers = stack(&job, &overrides);
+                    }
+                    if previous.alert_rule != overrides.alert_rule {
+                        (alerting, _watching) = alert(&job, &overrides);
+                    }
+                }
+                let truncate = overrides
+                    .truncate_time
+                    .unwrap_or_else(|| job.with_truncate_time());
+                let zone = overrides
+                    .truncate_zone
+                    .unwrap_or_else(|| job.truncate_zone());
+                let dst = overrides.dst_policy.unwrap_or_else(|| job.dst_policy());
+                let period = overrides
+                    .period
+                    .map_or_else(|| job.period(), Duration::from);
+                let adapted = adaptive.period(period, truncate);
+                let requested = match overrides.period_limit.or_else(|| job.period_limit()) {
+                    Some(limit) => limit.apply(adapted, previous),
+                    None => adapted,
+                };
+                if requested != adapted && limited != Some((adapted, requested)) {
+                    events.emit(
+                        &name,
+                        EventKind::PeriodLimited {
+                            requested: adapted,
+                            limited: requested,
+                        },
+                    );
+                }
+                limited = (requested != adapted).then_some((adapted, requested));
+                previous = Some(requested);
+                let period = requested.max(min_period);
+                if period != requested && floored != Some(requested) {
+                    events.emit(
+                        &name,
+                        EventKind::PeriodFloored {
+                            requested,
+                            floor: min_period,
+                        },
+                    );
+                }
+                floored = (period != requested).then_some(requested);
+                let misfire = overrides.misfire.unwrap_or_else(|| job.misfire());
+                let schedule = job.schedule();
+                let spread_offset =
+                    spread.offset(member, (truncate && schedule.is_none()).then_some(period));
+                let offset = match overrides.stagger.unwrap_or_else(|| job.stagger()) {
+                    true => stagger::offset(&stagger_key, &name, period) + spread_offset,
+                    false => spread_offset,
+                };
+                let lead = match overrides.finish_by.unwrap_or_else(|| job.finish_by()) {
+                    true if truncate && schedule.is_none() => finish_by.lead(period),
+                    _ => Duration::ZERO,
+                };
+                let backfilled = match recovered {
+                    Some(_) => None,
+                    None => backfill.next(&shared.backfills, |from, after| match &schedule {
+                        Some(schedule) => schedule.next_after(after),
+                        None if truncate => truncate_period(after, period, offset, zone, dst)
+                            .map(|delay| after + delay),
+                        None => backfill::anchored(last.unwrap_or(from), period, after),
+                    }),
+                };
+                let tick = recovered.take().or(backfilled);
+                let since = clock.now();
+                let missed = match (tick, &schedule) {
+                    (Some(_), _) => None,
+                    (None, Some(schedule)) => misfire::missed_in(misfire, schedule, last, since),
+                    (None, None) => misfire::missed(misfire, last, period, since),
+                };
+                let next = if let Some(tick) = tick.or(missed) {
+                    Some(tick)
+                } else if let Some(schedule) = &schedule {
+                    let starting = !std::mem::replace(&mut started, true);
+                    match starting && schedule.fires_at_start(since) {
+                        true => Some(since),
+                        false => schedule.next_after(last.map_or(since, |last| last.max(since))),
+                    }
+                } else if truncate {
+                    let from = match lead.is_zero() {
+                        true => since,
+                        false => (since + lead).max(last.unwrap_or(since)),
+                    };
+                    truncate_period(from, period, offset, zone, dst).map(|delay| from + delay)
+                } else {
+                    Some(since + misfire::anchored(last, period, since))
+                };
+                let delay = match next {
+                    _ if tick.is_some() || missed.is_some() => Duration::ZERO,
+                    Some(next) => (next - lead).duration_since(since).unwrap_or_default(),
+                    None => Duration::MAX,
+                };
+                let mut scheduled = next.unwrap_or(since);
+                if backfilled.is_none() {
+                    shared.schedule(next);
+                }
+                shared.waiting(delay);
+                select! {
+                    biased;
+                    _ = token.cancelled() => {
+                        events.emit(&name, EventKind::Cancelled { interrupted: shared.interrupted() });
+                        break;
+                    },
+                    _ = options.changed() => {
+                        options.mark_changed();
+                        match backfilled {
+                            Some(_) => backfill.retry(),
+                            None => recovered = tick,
+                        }
+                        continue;
+                    },
+                    _ = shared.backfills.requested() => {
+                        match backfilled {
+                            Some(_) => backfill.retry(),
+                            None => recovered = tick,
+                        }
+                        continue;
+                    },
+                    _ = async {
+                        match &mailbox {
+                            Some(mailbox) => mailbox.arrived().await,
+                            None => pending().await,
+                        }
+                    } => {
+                        if let Some(mailbox) = &mailbox {
+                            mailbox.deliver(&mut job).await;
+                        }
+                        match backfilled {
+                            Some(_) => backfill.retry(),
+                            None => recovered = tick,
+                        }
+                        continue;
+                    },
+                    triggered = triggers.wait(&shared.trigger, delay) =>  {
+                        shared.progressed();
+                        let woke = clock.now();
+                        match triggered {
+                            true => {
+                                shared.triggered();
+                                scheduled = woke;
+                            }
+                            false if backfilled.is_none() => last = Some(scheduled),
+                            false => {}
+                        }
+                        if let Some(slept) = suspend::suspended(since, woke, delay) {
+                            events.emit(&name, EventKind::Suspended { slept });
+                            match overrides.on_suspend.unwrap_or_else(|| job.on_suspend()) {
+                                SuspendPolicy::RunNow => {}
+                                SuspendPolicy::Realign if truncate || schedule.is_some() => {
+                                    let next = match &schedule {
+                                        Some(schedule) => schedule.next_after(woke),
+                                        None => truncate_period(woke, period, offset, zone, dst).map(|delay| woke + delay),
+                                    };
+                                    shared.schedule(next);
+                                    let Some(next) = next else {
+                                        continue;
+                                    };
+                                    let delay = next.duration_since(woke).unwrap_or_default();
+                                    scheduled = next;
+                                    last = Some(scheduled);
+                                    shared.waiting(delay);
+                                    select! {
+                                        biased;
+                                        _ = token.cancelled() => {
+                                            events.emit(&name, EventKind::Cancelled { interrupted: shared.interrupted() });
+                                            break;
+                                        },
+                                        _ = sleep(delay) => shared.progressed(),
+                                    }
+                                }
+                                SuspendPolicy::Realign => {}
+                                SuspendPolicy::Skip => {
+                                    events.emit(&name, EventKind::Skipped { reason: SkipReason::Suspended });
+                                    continue;
+                                }
+                            }
+                        }
+                        let replacement = swap.lock().unwrap().take();
+                        if let Some(replacement) = replacement {
+                            let replaced = std::mem::replace(&mut job, replacement);
+                            layers = stack(&job, &overrides);
+                            shared.set_group(job.exclusion_group());
+                            if replaced.alert_rule() != job.alert_rule() {
+                                (alerting, _watching) = alert(&job, &overrides);
+                            }
+                            if replaced.supervisor() != job.supervisor() {
+                                restarts = Restarts::new(overrides.supervisor.or_else(|| job.supervisor()));
+                            }
+                            if replaced.sampling() != job.sampling() {
+                                sampler = Sampler::new(overrides.sampling.unwrap_or_else(|| job.sampling()));
+                            }
+                            if replaced.max_duty_cycle() != job.max_duty_cycle() {
+                                adaptive = Adaptive::new(overrides.max_duty_cycle.or_else(|| job.max_duty_cycle()));
+                            }
+                            if replaced.budget() != job.budget() {
+                                spending = Spending::new(overrides.budget.or_else(|| job.budget()));
+                            }
+                            if let Some(checkpoint) = &checkpoint {
+                                checkpoint.restore(&mut job).await;
+                            }
+                        }
+                        if let Some(mailbox) = &mailbox {
+                            mailbox.deliver(&mut job).await;
+                        }
+                        if !overrides.enabled.unwrap_or_else(|| job.enabled()) {
+                            events.emit(&name, EventKind::Skipped { reason: SkipReason::Disabled });
+                            continue;
+                        }
+                        if tick.is_none() && !sampler.sample() {
+                            events.emit(&name, EventKind::Skipped { reason: SkipReason::Sampled });
+                            continue;
+                        }
+                        if spending.exhausted() {
+                            events.emit(&name, EventKind::Skipped { reason: SkipReason::Budget });
+                            continue;
+                        }
+                        let load_policy = overrides.load_policy.unwrap_or_else(|| job.load_policy());
+                        if load_policy != LoadPolicy::Ignore && load::pressured(&load_gates).await {
+                            if load_policy == LoadPolicy::Skip {
+                                events.emit(&name, EventKind::Skipped { reason: SkipReason::Load });
+                                continue;
+                            }
+                            let start = Instant::now();
+                            shared.waiting(Duration::MAX);
+                            select! {
+                                biased;
+                                _ = token.cancelled() => {
+                                    events.emit(&name, EventKind::Cancelled { interrupted: shared.interrupted() });
+                                    break;
+                                },
+                                _ = load::relieved(&load_gates) => shared.progressed(),
+                            }
+                            events.emit(&name, EventKind::LoadDelayed { waited: start.elapsed() });
+                        }
+                        let shadow = dry_run || overrides.dry_run.unwrap_or_else(|| job.dry_run());
+                        let started = clock.now();
+                        let completion = overrides.completion.or_else(|| job.completion());
+                        if let Some(completion) = completion.filter(|completion| completion.skip_late)
+                            && completion.late(scheduled, started).is_some()
+                        {
+                            shared.deadline_missed();
+                            telemetry::deadline_missed(&name, &labels);
+                            events.emit(&name, EventKind::Skipped { reason: SkipReason::Late });
+                            continue;
+                        }
+                        let run_token = token.child_token();
+                        let next_tick = match &schedule {
+                            _ if triggered => next,
+                            Some(schedule) => schedule.next_after(scheduled),
+                            None if truncate => truncate_period(scheduled, period, offset, zone, dst).map(|delay| scheduled + delay),
+                            None => Some(scheduled + period),
+                        };
+                        let ctx = RunContext::new(name.clone(), run_token.clone(), states.clone(), scheduled)
+                            .with_deadline(deadline.clone())
+                            .with_started(started)
+                            .with_next_tick(next_tick)
+                            .with_shared(shared.clone())
+                            .with_error_handlers(error_handlers.clone())
+                            .with_dry_run(shadow);
+                        if let Some(dedup) = dedup.as_mut().filter(|_| tick.is_none())
+                            && !dedup.claim(ctx.idempotency_key(), ctx.tick()).await
+                        {
+                            events.emit(&name, EventKind::Skipped { reason: SkipReason::Duplicate });
+                            continue;
+                        }
+                        if let Some(intents) = intents.as_ref().filter(|_| backfilled.is_none()) {
+                            intents.save(scheduled).await;
+                        }
+                        if let Some(ticks) = ticks
+                            .as_ref()
+                            .filter(|_| backfilled.is_none() && misfire != Misfire::SkipToNext)
+                        {
+                            ticks.save(scheduled).await;
+                        }
+                        let run_id = ctx.run_id();
+                        let report = ctx.reported();
+                        let abandoned = ctx.abandoned();
+                        let skipped = ctx.skipped();
+                        events.emit(&name, EventKind::Started { run_id, shadow });
+                        #[cfg(feature = "tracing")]
+                        let level = overrides.log_level.unwrap_or_else(|| job.log_level());
+                        #[cfg(feature = "tracing")]
+                        let span = level.run_span(run_id, shadow);
+                        let cancel_aware = job.with_cancel();
+                        let run = Next::new(&mut job, &layers, ctx).run();
+                        #[cfg(feature = "tracing")]
+                        let run = tracing::Instrument::instrument(run, span.clone());
+                        let interruption = Interruption::new(&shared, &events, &token);
+                        let start = Instant::now();
+                        shared.running(Some(run_token.clone()));
+                        let result = match cancel_aware {
+                            true => CatchUnwind::new(run).await,
+                            false => select! {
+                                biased;
+                                result = CatchUnwind::new(run) => result,
+                                _ = async {
+                                    run_token.cancelled().await;
+                                    if token.is_cancelled() {
+                                        pending::<()>().await;
+                                    }
+                                } => {
+                                    shared.running(None);
+                                    interruption.finish();
+                                    events.emit(&name, EventKind::RunCancelled { run_id });
+                                    continue;
+                                },
+                            },
+                        };
+                        shared.running(None);
+                        let duration = start.elapsed();
+                        interruption.finish();
+                        if result.is_ok() && skipped.load(Ordering::Relaxed) {
+                            events.emit(&name, EventKind::RunCancelled { run_id });
+                            continue;
+                        }
+                        let panicked = result.is_err();
+                        let status = match &result {
+                            Ok(()) => report.lock().unwrap().take().unwrap_or_default(),
+                            Err(panic) => RunStatus::Failed(notify::panic_message(&**panic)),
+                        };
+                        shared.record(started, duration, &status, shadow);
+                        if backfilled.is_some() {
+                            backfill.record(!status.is_failure());
+                        }
+                        adaptive.record(duration);
+                        finish_by.record(duration);
+                        telemetry::run_finished(&name, &labels, duration, &status, shadow);
+                        #[cfg(feature = "tracing")]
+                        level.run_finished(&span, duration, panicked, &status);
+                        events.emit(&name, EventKind::Finished { run_id, duration, panicked, status: status.clone() });
+                        #[cfg(feature = "serde")]
+                        if let Some(history) = &history {
+                            let tick = scheduled;
+                            let status = status.clone();
+                            history.record(RunRecord { run_id, tick, started, duration, status, panicked, shadow }).await;
+                        }
+                        let late = completion.and_then(|completion| completion.late(scheduled, started + duration));
+                        if let Some(late) = late {
+                            shared.deadline_missed();
+                            telemetry::deadline_missed(&name, &labels);
+                            events.emit(&name, EventKind::DeadlineMissed { run_id, late });
+                        }
+                        let quarantine = overrides.quarantine.or_else(|| job.quarantine());
+                        let quarantined = strikes.record(quarantine, panicked || late.is_some());
+                        if let Some(count) = quarantined {
+                            shared.quarantine();
+                            events.emit(&name, EventKind::Quarantined { strikes: count });
+                            alerting.quarantined(count);
+                        }
+                        if let Some(used) = spending.spend(duration) {
+                            events.emit(&name, EventKind::BudgetExhausted { used });
+                        }
+                        match (result, status) {
+                            (Ok(()), RunStatus::Failed(error)) => {
+                                alerting.failed(&error);
+                                match intents.as_ref().filter(|_| backfilled.is_none()) {
+                                    Some(_) if !abandoned.load(Ordering::Relaxed) => {
+                                        recovered = Some(scheduled);
+                                        restarts.failed(&token).await;
+                                    }
+                                    Some(intents) => {
+                                        restarts.succeeded();
+                                        intents.remove().await;
+                                    }
+                                    None => restarts.succeeded(),
+                                }
+                            }
+                            (Ok(()), _) => {
+                                alerting.succeeded();
+                                restarts.succeeded();
+                                if let Some(checkpoint) = &checkpoint {
+                                    checkpoint.save(&job).await;
+                                }
+                                if let Some(publisher) = &publisher {
+                                    publisher.publish(&mut job);
+                                }
+                                if let Some(intents) = intents.as_ref().filter(|_| backfilled.is_none()) {
+                                    intents.remove().await;
+                                }
+                            }
+                            (Err(panic), _) => {
+                                alerting.failed(&*panic);
+                                if let Some(root) = &abort_on_panic {
+                                    root.cancel();
+                                    resume_unwind(panic);
+                                }
+                                if quarantined.is_none() {
+                                    restarts.panicked(panic, &token, &root).await;
+                                    telemetry::restarted(&name, &labels);
+                                }
+                                if intents.is_some() && backfilled.is_none() {
+                                    recovered = Some(scheduled);
+                                }
+                            }
+                        }
+                    }
+                };
+            }
+        }
+    }
+
+    pub fn events(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    pub fn status(&self) -> Status {
+        let timeout = self.liveness_timeout;
+        let mut jobs = Vec::new();
+        let mut schedulers = vec![self.share()];
+        while let Some(scheduler) = schedulers.pop() {
+            let handles = scheduler.handles.lock().unwrap();
+            jobs.extend(handles.iter().map(|entry| entry.status(timeout)));
+            let children = scheduler.children.lock().unwrap();
+            schedulers.extend(children.iter().map(Scheduler::share));
+        }
+        Status::new(self.token.is_cancelled(), self.gate.is_open(), jobs)
+    }
+
+    #[cfg(feature = "serde")]
+    pub async fn history(&self, name: &str) -> Result<Vec<RunRecord>, Error> {
+        match &self.store {
+            Some(store) => Ok(history::load(&**store, &history::key(name)).await?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn export_state(&self) -> SchedulerState {
+        let mut jobs = Vec::new();
+        let mut schedulers = vec![self.share()];
+        while let Some(scheduler) = schedulers.pop() {
+            let handles = scheduler.handles.lock().unwrap();
+            jobs.extend(handles.iter().map(|entry| JobState::capture(&entry.shared)));
+            let children = scheduler.children.lock().unwrap();
+            schedulers.extend(children.iter().map(Scheduler::share));
+        }
+        SchedulerState { jobs }
+    }
+
+    pub fn import_state(self, state: SchedulerState) -> Self {
+        let mut pending: HashMap<_, _> = state
+            .jobs
+            .into_iter()
+            .map(|job| (job.name.clone(), job))
+            .collect();
+        let mut schedulers = vec![self.share()];
+        while let Some(scheduler) = schedulers.pop() {
+            for entry in scheduler.handles.lock().unwrap().iter() {
+                if let Some(state) = pending.remove(&*entry.shared.name) {
+                    state.restore(&entry.shared);
+                }
+            }
+            let children = scheduler.children.lock().unwrap();
+            schedulers.extend(children.iter().map(Scheduler::share));
+        }
+        self.imported.lock().unwrap().extend(pending);
+        self
+    }
+
+    pub fn select(&self, filter: JobFilter) -> JobSet {
+        let mut jobs = Vec::new();
+        let mut schedulers = vec![self.share()];
+        while let Some(scheduler) = schedulers.pop() {
+            let handles = scheduler.handles.lock().unwrap();
+            jobs.extend(
+                handles
+                    .iter()
+                    .filter(|entry| filter.matches(&entry.shared))
+                    .map(|entry| entry.shared.clone()),
+            );
+            let children = scheduler.children.lock().unwrap();
+            schedulers.extend(children.iter().map(Scheduler::share));
+        }
+        JobSet::new(jobs)
+    }
+
+    pub fn spawn_guarded(self, job: impl Job) -> Guard {
+        Guard(Some(self.spawn(job)))
+    }
+
+    pub async fn stop(self) -> StopReport {
+        self.shutdown(None).await
+    }
+
+    pub async fn stop_with_deadline(self, deadline: Duration) -> StopReport {
+        self.shutdown(Some(Instant::now() + deadline)).await
+    }
+
+    async fn shutdown(self, deadline: Option<Instant>) -> StopReport {
+        let cancelled = Instant::now();
+        let mut schedulers = vec![self.share()];
+        while let Some(scheduler) = schedulers.pop() {
+            let graced = scheduler.cancel_grace.map(|grace| cancelled + grace);
+            if let Some(limit) = deadline.into_iter().chain(graced).min() {
+                let _ = scheduler.deadline.set(limit);
+            }
+            let children = scheduler.children.lock().unwrap();
+            schedulers.extend(children.iter().map(Scheduler::share));
+        }
+        self.token.cancel();
+        let mut report = StopReport::default();
+        let mut schedulers = vec![self];
+        while let Some(scheduler) = schedulers.pop() {
+            scheduler.tracker.close();
+            schedulers.append(&mut scheduler.children.lock().unwrap());
+            let startups = std::mem::take(&mut *scheduler.startups.lock().unwrap());
+            for (name, handle) in startups {
+                if let Err(error) = handle.await {
+                    report.failures.push(JobFailure { name, error });
+                }
+            }
+            let handles = std::mem::take(&mut *scheduler.handles.lock().unwrap());
+            for mut entry in handles {
+                let result = match scheduler.deadline.get() {
+                    Some(&limit) if entry.shared.abortable => {
+                        match timeout_at(limit, &mut entry.handle).await {
+                            Ok(result) => Some(result),
+                            Err(_) => {
+                                entry.handle.abort();
+                                let _ = entry.handle.await;
+                                let name = entry.shared.name.to_string();
+                                if deadline.is_none_or(|deadline| limit < deadline) {
+                                    report.unresponsive.push(name.clone());
+                                }
+                                report.aborted.push(name);
+                                None
+                            }
+                        }
+                    }
+                    _ => Some(entry.handle.await),
+                };
+                if entry.shared.interrupted() {
+                    report.interrupted.push(entry.shared.name.to_string());
+                }
+                if let Some(Err(error)) = result {
+                    let name = entry.shared.name.to_string();
+                    report.failures.push(JobFailure { name, error });
+                }
+            }
+            scheduler.tracker.wait().await;
+        }
+        report
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn wait(self) -> StopReport {
+        self.wait_with(None).await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn try_wait(self) -> Result<StopReport, Error> {
+        self.try_wait_with(None).await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn wait_with(self, token: Option<CancellationToken>) -> StopReport {
+        let signal = async {
+            if let Err(_error) = terminate::signalled().await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_error, "failed to listen for shutdown signals");
+                pending::<()>().await;
+            }
+        };
+        let external = async {
+            match token {
+                Some(token) => token.cancelled_owned().await,
+                None => pending().await,
+            }
+        };
+        select! {
+            _ = signal => {},
+            _ = external => {},
+            _ = self.token.cancelled() => {},
+        }
+        self.stop().await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn try_wait_with(
+        self,
+        token: Option<CancellationToken>,
+    ) -> Result<StopReport, Error> {
+        let external = async {
+            match token {
+                Some(token) => token.cancelled_owned().await,
+                None => pending().await,
+            }
+        };
+        let result = select! {
+            res = terminate::signalled() => res.map_err(Error::Signal),
+            _ = external => Ok(()),
+            _ = self.token.cancelled() => Ok(()),
+        };
+        let strict = self.abort_on_panic.is_some();
+        let report = self.stop().await;
+        result?;
+        match strict && !report.failures.is_empty() {
+            true => Err(Error::Jobs(report.failures)),
+            false => Ok(report),
+        }
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn wait_ctrl_c(self) -> StopReport {
+        let signal = async {
+            if let Err(_error) = tokio::signal::ctrl_c().await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_error, "failed to listen for ctrl-c");
+                pending::<()>().await;
+            }
+        };
+        select! {
+            _ = signal => {},
+            _ = self.token.cancelled() => {},
+        }
+        self.stop().await
+    }
+
+    pub async fn wait_cancel(self, token: CancellationToken) -> StopReport {
+        select! {
+            _ = token.cancelled() => {},
+            _ = self.token.cancelled() => {},
+        }
+        self.stop().await
+    }
+
+    pub async fn wait_until(self, signal: impl Future<Output = ()>) -> StopReport {
+        select! {
+            _ = signal => {},
+            _ = self.token.cancelled() => {},
+        }
+        self.stop().await
+    }
+
+    #[cfg(feature = "signal")]
+    pub async fn serve<F, S, E>(self, server: F) -> Result<StopReport, E>
+    where
+        F: FnOnce(WaitForCancellationFutureOwned) -> S,
+        S: Future<Output = Result<(), E>>,
+    {
+        let signal = async {
+            if terminate::signalled().await.is_err() {
+                pending::<()>().await;
+            }
+        };
+        self.serve_until(signal, server).await
+    }
+
+    pub async fn serve_cancel<F, S, E>(
+        self,
+        token: CancellationToken,
+        server: F,
+    ) -> Result<StopReport, E>
+    where
+        F: FnOnce(WaitForCancellationFutureOwned) -> S,
+        S: Future<Output = Result<(), E>>,
+    {
+        self.serve_until(token.cancelled_owned(), server).await
+    }
+
+    async fn serve_until<F, S, E>(
+        self,
+        signal: impl Future<Output = ()>,
+        server: F,
+    ) -> Result<StopReport, E>
+    where
+        F: FnOnce(WaitForCancellationFutureOwned) -> S,
+        S: Future<Output = Result<(), E>>,
+    {
+        let shutdown = CancellationToken::new();
+        let mut server = pin!(server(shutdown.clone().cancelled_owned()));
+        let result = select! {
+            result = &mut server => result,
+            _ = signal => {
+                shutdown.cancel();
+                server.await
+            },
+            _ = self.token.cancelled() => {
+                shutdown.cancel();
+                server.await
+            },
+        };
+        let report = self.stop().await;
+        result.map(|()| report)
+    }
+}
+
+pub struct Guard(Option<Scheduler>);
+
+impl Guard {
+    pub fn spawn(mut self, job: impl Job) -> Self {
+        self.0 = self.0.take().map(|scheduler| scheduler.spawn(job));
+        self
+    }
+
+    pub fn child(&self) -> Scheduler {
+        self.0.as_ref().unwrap().child()
+    }
+
+    pub async fn stop(mut self) -> StopReport {
+        self.0.take().unwrap().stop().await
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let Some(scheduler) = self.0.take() else {
+            return;
+        };
+        scheduler.token.cancel();
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                block_in_place(|| handle.block_on(scheduler.stop()));
+            }
+            _ => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    "guard dropped outside a multi-thread runtime, jobs were cancelled but not awaited; use Guard::stop to wait for them"
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeriodLimit {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) max_shrink: Option<f64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::period::duration::option")
+    )]
+    pub(crate) floor: Option<Duration>,
+}
+
+impl PeriodLimit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_shrink(mut self, factor: f64) -> Self {
+        self.max_shrink = Some(factor);
+        self
+    }
+
+    pub fn floor(mut self, floor: Duration) -> Self {
+        self.floor = Some(floor);
+        self
+    }
+
+    pub(crate) fn apply(&self, requested: Duration, previous: Option<Duration>) -> Duration {
+        let mut period = requested;
+        if let (Some(factor), Some(previous)) =
+            (self.max_shrink.filter(|factor| *factor >= 1.0), previous)
+        {
+            period = period.max(previous.div_f64(factor));
+        }
+        match self.floor {
+            Some(floor) => period.max(floor),
+            None => period,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::thread::available_parallelism;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const RETRY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LoadPolicy {
+    #[default]
+    Ignore,
+    Skip,
+    Delay,
+}
+
+pub trait LoadGate: Send + Sync + 'static {
+    fn pressure(&self) -> Pin<Box<dyn Future<Output = bool> + Send + '_>>;
+}
+
+impl<F, Fut> LoadGate for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send + 'static,
+{
+    fn pressure(&self) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(self())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CpuLoad {
+    max_per_cpu: f64,
+}
+
+impl CpuLoad {
+    pub fn new(max_per_cpu: f64) -> Self {
+        Self { max_per_cpu }
+    }
+}
+
+impl LoadGate for CpuLoad {
+    fn pressure(&self) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(async move {
+            let Ok(loadavg) = tokio::fs::read_to_string("/proc/loadavg").await else {
+                return false;
+            };
+            let Some(load) = loadavg
+                .split_whitespace()
+                .next()
+                .and_then(|load| load.parse::<f64>().ok())
+            else {
+                return false;
+            };
+            let cpus = available_parallelism().map_or(1, usize::from) as f64;
+            load / cpus > self.max_per_cpu
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryPressure {
+    min_available: f64,
+}
+
+impl MemoryPressure {
+    pub fn new(min_available: f64) -> Self {
+        Self { min_available }
+    }
+}
+
+impl LoadGate for MemoryPressure {
+    fn pressure(&self) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(async move {
+            let Ok(meminfo) = tokio::fs::read_to_string("/proc/meminfo").await else {
+                return false;
+            };
+            let field = |name: &str| {
+                meminfo.lines().find_map(|line| {
+                    let value = line.strip_prefix(name)?.strip_prefix(':')?;
+                    value.split_whitespace().next()?.parse::<f64>().ok()
+                })
+            };
+            match (field("MemAvailable"), field("MemTotal")) {
+                (Some(available), Some(total)) if total > 0.0 => {
+                    available / total < self.min_available
+                }
+                _ => false,
+            }
+        })
+    }
+}
+
+pub(crate) type LoadGates = Arc<Vec<Arc<dyn LoadGate>>>;
+
+pub(crate) async fn pressured(gates: &LoadGates) -> bool {
+    for gate in gates.iter() {
+        if gate.pressure().await {
+            return true;
+        }
+    }
+    false
+}
+
+pub(crate) async fn relieved(gates: &LoadGates) {
+    while pressured(gates).await {
+        sleep(RETRY).await;
+    }
+}
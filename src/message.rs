@@ -0,0 +1,81 @@
+use crate::{Job, Run};
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+pub trait MessageJob: Job {
+    type Message: Send + 'static;
+
+    fn handle_message(&mut self, message: Self::Message) -> impl Future<Output = ()> + Send;
+}
+
+pub(crate) type Queue<M> = Mutex<VecDeque<M>>;
+
+#[derive(Clone)]
+pub(crate) struct Post {
+    queue: Arc<dyn Any + Send + Sync>,
+    arrived: Arc<Notify>,
+    capacity: usize,
+}
+
+impl Post {
+    pub(crate) fn send<M: Send + 'static>(&self, message: M) -> Result<(), M> {
+        let Some(queue) = self.queue.downcast_ref::<Queue<M>>() else {
+            return Err(message);
+        };
+        let mut queue = queue.lock().unwrap();
+        if queue.len() >= self.capacity {
+            return Err(message);
+        }
+        queue.push_back(message);
+        self.arrived.notify_one();
+        Ok(())
+    }
+}
+
+pub(crate) struct Mailbox<J> {
+    post: Post,
+    deliver: for<'a> fn(&'a mut J, &'a Post) -> Run<'a>,
+}
+
+impl<J> Mailbox<J> {
+    pub(crate) fn new(capacity: usize) -> Self
+    where
+        J: MessageJob,
+    {
+        Self {
+            post: Post {
+                queue: Arc::new(Queue::<J::Message>::default()),
+                arrived: Arc::new(Notify::new()),
+                capacity: capacity.max(1),
+            },
+            deliver: |job, post| {
+                Box::pin(async move {
+                    let Some(queue) = post.queue.downcast_ref::<Queue<J::Message>>() else {
+                        return;
+                    };
+                    loop {
+                        let message = queue.lock().unwrap().pop_front();
+                        let Some(message) = message else {
+                            break;
+                        };
+                        job.handle_message(message).await;
+                    }
+                })
+            },
+        }
+    }
+
+    pub(crate) fn post(&self) -> Post {
+        self.post.clone()
+    }
+
+    pub(crate) async fn arrived(&self) {
+        self.post.arrived.notified().await
+    }
+
+    pub(crate) async fn deliver(&self, job: &mut J) {
+        (self.deliver)(job, &self.post).await
+    }
+}
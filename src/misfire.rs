@@ -0,0 +1,78 @@
+use crate::Schedule;
+use std::time::{Duration, SystemTime};
+
+const MAX_CATCH_UP: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Misfire {
+    FireNowOnce,
+    FireAllMissed,
+    #[default]
+    SkipToNext,
+    Delay,
+}
+
+pub(crate) fn missed(
+    misfire: Misfire,
+    last: Option<SystemTime>,
+    period: Duration,
+    now: SystemTime,
+) -> Option<SystemTime> {
+    let last = last?;
+    let due = last + period;
+    if now <= due || period.is_zero() {
+        return None;
+    }
+    match misfire {
+        Misfire::FireNowOnce => {
+            let behind = now.duration_since(last).ok()?.as_nanos() / period.as_nanos();
+            Some(last + Duration::from_nanos((behind * period.as_nanos()) as u64))
+        }
+        Misfire::FireAllMissed => Some(due),
+        Misfire::SkipToNext => None,
+        Misfire::Delay => Some(now),
+    }
+}
+
+pub(crate) fn missed_in(
+    misfire: Misfire,
+    schedule: &dyn Schedule,
+    last: Option<SystemTime>,
+    now: SystemTime,
+) -> Option<SystemTime> {
+    let due = schedule.next_after(last?)?;
+    if now <= due {
+        return None;
+    }
+    match misfire {
+        Misfire::FireNowOnce => {
+            let mut latest = due;
+            for _ in 0..MAX_CATCH_UP {
+                match schedule.next_after(latest) {
+                    Some(next) if next < now => latest = next,
+                    _ => break,
+                }
+            }
+            Some(latest)
+        }
+        Misfire::FireAllMissed => Some(due),
+        Misfire::SkipToNext => None,
+        Misfire::Delay => Some(now),
+    }
+}
+
+pub(crate) fn anchored(last: Option<SystemTime>, period: Duration, now: SystemTime) -> Duration {
+    let Some(last) = last else {
+        return period;
+    };
+    let Ok(elapsed) = now.duration_since(last) else {
+        return last.duration_since(now).unwrap_or_default() + period;
+    };
+    if period.is_zero() {
+        return period;
+    }
+    let behind = elapsed.as_nanos() / period.as_nanos() + 1;
+    Duration::from_nanos((behind * period.as_nanos() - elapsed.as_nanos()) as u64)
+}
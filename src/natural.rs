@@ -0,0 +1,322 @@
+use crate::schedule::zone_name;
+use crate::{Cron, Every, Schedule, TruncateZone, Weekday};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+const NUMBERS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+const ORDINALS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, Clone)]
+pub struct Phrase {
+    phrase: String,
+    zone: TruncateZone,
+    spec: Spec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePhraseError(String);
+
+impl fmt::Display for ParsePhraseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid schedule phrase: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParsePhraseError {}
+
+#[derive(Debug, Clone)]
+enum Spec {
+    Interval(Duration, Vec<Weekday>),
+    Times(Vec<Cron>),
+}
+
+#[derive(Default)]
+struct Parsed {
+    interval: Option<Duration>,
+    weekdays: Vec<Weekday>,
+    weekly: bool,
+    day_of_month: Option<String>,
+    times: Vec<(u32, u32)>,
+}
+
+impl Phrase {
+    pub fn parse(phrase: &str) -> Result<Self, ParsePhraseError> {
+        let normalized = phrase.trim().to_ascii_lowercase().replace(',', " ");
+        let words: Vec<&str> = normalized
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "and" | "the" | "of"))
+            .collect();
+        if words.is_empty() {
+            return Err(ParsePhraseError("empty phrase".into()));
+        }
+        let parsed = Parsed::parse(phrase, &words)?;
+        Ok(Self {
+            phrase: phrase.trim().to_string(),
+            zone: TruncateZone::Utc,
+            spec: parsed.into_spec(phrase)?,
+        })
+    }
+
+    pub fn zone(mut self, zone: TruncateZone) -> Self {
+        self.zone = zone;
+        if let Spec::Times(crons) = &mut self.spec {
+            *crons = crons.drain(..).map(|cron| cron.zone(zone)).collect();
+        }
+        self
+    }
+
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+}
+
+impl FromStr for Phrase {
+    type Err = ParsePhraseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Phrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl Schedule for Phrase {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        match &self.spec {
+            Spec::Interval(period, weekdays) if weekdays.is_empty() => {
+                Every::new(*period).zone(self.zone).next_after(after)
+            }
+            Spec::Interval(period, weekdays) => Every::new(*period)
+                .zone(self.zone)
+                .on_days(weekdays)
+                .next_after(after),
+            Spec::Times(crons) => crons.iter().filter_map(|cron| cron.next_after(after)).min(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.spec {
+            Spec::Interval(period, weekdays) if weekdays.is_empty() => {
+                Every::new(*period).zone(self.zone).describe()
+            }
+            Spec::Interval(period, weekdays) => Every::new(*period)
+                .zone(self.zone)
+                .on_days(weekdays)
+                .describe(),
+            Spec::Times(crons) => {
+                let times: Vec<_> = crons.iter().map(Cron::summary).collect();
+                format!("{}, in {}", times.join(" or "), zone_name(self.zone))
+            }
+        }
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.zone
+    }
+}
+
+impl Parsed {
+    fn parse(phrase: &str, words: &[&str]) -> Result<Self, ParsePhraseError> {
+        let unexpected =
+            |word: &str| ParsePhraseError(format!("unexpected `{word}` in `{}`", phrase.trim()));
+        let mut parsed = Self::default();
+        let mut words = words.iter().copied().peekable();
+        while let Some(word) = words.next() {
+            match word {
+                "every" | "each" => {
+                    let repeat = words.peek().and_then(|word| count(word));
+                    if repeat.is_some() {
+                        words.next();
+                    }
+                    let Some(unit) = words.next() else {
+                        return Err(ParsePhraseError(format!("expected a unit after `{word}`")));
+                    };
+                    let repeat = repeat.unwrap_or(1);
+                    if let Some(period) = unit_period(unit) {
+                        parsed.interval = Some(period * repeat);
+                    } else if matches!(unit, "day" | "days") && repeat > 1 {
+                        parsed.interval = Some(Duration::from_secs(86_400) * repeat);
+                    } else if repeat > 1 || !parsed.calendar_unit(unit) {
+                        return Err(unexpected(unit));
+                    }
+                }
+                "on" | "at" | "in" => {}
+                "hourly" => parsed.interval = Some(Duration::from_secs(3600)),
+                "daily" | "nightly" => {}
+                _ if parsed.calendar_unit(word) => {}
+                _ if word.ends_with("st")
+                    || word.ends_with("nd")
+                    || word.ends_with("rd")
+                    || word.ends_with("th")
+                    || word == "last" =>
+                {
+                    let day = ordinal(word).ok_or_else(|| unexpected(word))?;
+                    parsed.day_of_month = Some(day);
+                }
+                _ => {
+                    let time = match words.peek() {
+                        Some(&suffix @ ("am" | "pm")) => {
+                            words.next();
+                            time(&format!("{word}{suffix}"))
+                        }
+                        _ => time(word),
+                    };
+                    parsed.times.push(time.ok_or_else(|| unexpected(word))?);
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn calendar_unit(&mut self, word: &str) -> bool {
+        match word {
+            "weekday" | "weekdays" => self.weekdays.extend(&WEEKDAYS[..5]),
+            "weekend" | "weekends" => self.weekdays.extend(&WEEKDAYS[5..]),
+            "day" | "days" => {}
+            "week" | "weekly" => self.weekly = true,
+            "month" | "monthly" => {
+                self.day_of_month.get_or_insert_with(|| "1".into());
+            }
+            _ => match weekday(word) {
+                Some(day) => self.weekdays.push(day),
+                None => return false,
+            },
+        }
+        true
+    }
+
+    fn into_spec(mut self, phrase: &str) -> Result<Spec, ParsePhraseError> {
+        if self.weekly && self.weekdays.is_empty() {
+            self.weekdays.push(Weekday::Sun);
+        }
+        if let Some(period) = self.interval {
+            if !self.times.is_empty() || self.day_of_month.is_some() {
+                return Err(ParsePhraseError(format!(
+                    "`{}` mixes an interval with times of day or days of the month",
+                    phrase.trim()
+                )));
+            }
+            return Ok(Spec::Interval(period, self.weekdays));
+        }
+        if self.times.is_empty() {
+            self.times.push((0, 0));
+        }
+        let day_of_week = match self.weekdays.is_empty() {
+            true => "?".to_string(),
+            false => self
+                .weekdays
+                .iter()
+                .map(|day| (day.num_days_from_sunday() + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        let day_of_month = match (&self.day_of_month, self.weekdays.is_empty()) {
+            (Some(day), _) => day.clone(),
+            (None, true) => "*".into(),
+            (None, false) => "?".into(),
+        };
+        let crons = self
+            .times
+            .iter()
+            .map(|(hour, minute)| {
+                Cron::parse(&format!("0 {minute} {hour} {day_of_month} * {day_of_week}"))
+                    .map_err(|error| ParsePhraseError(error.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Spec::Times(crons))
+    }
+}
+
+fn count(word: &str) -> Option<u32> {
+    match word {
+        "other" => Some(2),
+        _ => word.parse().ok().or_else(|| {
+            NUMBERS
+                .iter()
+                .position(|number| *number == word)
+                .map(|index| index as u32 + 1)
+        }),
+    }
+}
+
+fn unit_period(word: &str) -> Option<Duration> {
+    let secs = match word {
+        "second" | "seconds" | "sec" | "secs" => 1,
+        "minute" | "minutes" | "min" | "mins" => 60,
+        "hour" | "hours" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let word = word.strip_suffix('s').unwrap_or(word);
+    WEEKDAYS
+        .into_iter()
+        .find(|day| word.len() >= 3 && weekday_name(*day).starts_with(word))
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+fn ordinal(word: &str) -> Option<String> {
+    if word == "last" {
+        return Some("L".into());
+    }
+    let day = match ORDINALS.iter().position(|ordinal| *ordinal == word) {
+        Some(index) => index as u32 + 1,
+        None => word.get(..word.len() - 2)?.parse().ok()?,
+    };
+    (1..=31).contains(&day).then(|| day.to_string())
+}
+
+fn time(word: &str) -> Option<(u32, u32)> {
+    match word {
+        "midnight" => return Some((0, 0)),
+        "noon" | "midday" => return Some((12, 0)),
+        _ => {}
+    }
+    let (clock, shift) = match (word.strip_suffix("am"), word.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, Some(0)),
+        (_, Some(clock)) => (clock, Some(12)),
+        _ => (word, None),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse().ok()?, minute.parse().ok()?),
+        Some(_) => return None,
+        None if shift.is_some() => (clock.parse().ok()?, 0),
+        None => return None,
+    };
+    let hour: u32 = match shift {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(shift) => hour % 12 + shift,
+        None => hour,
+    };
+    (hour < 24 && minute < 60).then_some((hour, minute))
+}
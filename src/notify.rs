@@ -0,0 +1,181 @@
+use crate::Token;
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::runtime::Handle;
+use tokio::select;
+use tokio::sync::watch;
+use tokio::time::{Instant, sleep_until};
+use tokio_util::task::TaskTracker;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlertKind {
+    Failures { count: u32, error: String },
+    Stale { since: Duration },
+    Quarantined { strikes: u32 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub name: String,
+    pub kind: AlertKind,
+    pub at: SystemTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AlertRule {
+    failures: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration::option"))]
+    stale_after: Option<Duration>,
+}
+
+impl AlertRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn failures(mut self, count: u32) -> Self {
+        self.failures = Some(count.max(1));
+        self
+    }
+
+    pub fn stale_after(mut self, window: Duration) -> Self {
+        self.stale_after = Some(window);
+        self
+    }
+}
+
+pub trait Notifier: Send + Sync + 'static {
+    fn notify(&self, alert: Alert) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+pub(crate) type Notifiers = Arc<Vec<Arc<dyn Notifier>>>;
+
+pub(crate) struct Alerting {
+    name: Arc<str>,
+    rule: Option<AlertRule>,
+    notifiers: Notifiers,
+    tracker: TaskTracker,
+    failures: u32,
+    succeeded: watch::Sender<Instant>,
+}
+
+impl Alerting {
+    pub(crate) fn new(
+        name: Arc<str>,
+        rule: Option<AlertRule>,
+        notifiers: Notifiers,
+        tracker: TaskTracker,
+    ) -> Self {
+        Self {
+            name,
+            rule,
+            notifiers,
+            tracker,
+            failures: 0,
+            succeeded: watch::Sender::new(Instant::now()),
+        }
+    }
+
+    pub(crate) fn watch(&self, token: Token) {
+        let Some(window) = self.rule.and_then(|rule| rule.stale_after) else {
+            return;
+        };
+        if self.notifiers.is_empty() {
+            return;
+        }
+        let Ok(runtime) = Handle::try_current() else {
+            return;
+        };
+        let mut succeeded = self.succeeded.subscribe();
+        let name = self.name.clone();
+        let notifiers = self.notifiers.clone();
+        self.tracker.spawn_on(
+            async move {
+                loop {
+                    let last = *succeeded.borrow_and_update();
+                    select! {
+                        _ = token.cancelled() => return,
+                        changed = succeeded.changed() => match changed {
+                            Ok(()) => continue,
+                            Err(_) => return,
+                        },
+                        _ = sleep_until(last + window) => {
+                            let since = last.elapsed();
+                            notify(&notifiers, &name, AlertKind::Stale { since }).await;
+                        }
+                    }
+                    select! {
+                        _ = token.cancelled() => return,
+                        changed = succeeded.changed() => if changed.is_err() {
+                            return;
+                        },
+                    }
+                }
+            },
+            &runtime,
+        );
+    }
+
+    pub(crate) fn succeeded(&mut self) {
+        self.failures = 0;
+        self.succeeded.send_replace(Instant::now());
+    }
+
+    pub(crate) fn failed(&mut self, panic: &(dyn Any + Send)) {
+        self.failures += 1;
+        if self.rule.and_then(|rule| rule.failures) != Some(self.failures) {
+            return;
+        }
+        let kind = AlertKind::Failures {
+            count: self.failures,
+            error: panic_message(panic),
+        };
+        self.send(kind);
+    }
+
+    pub(crate) fn quarantined(&self, strikes: u32) {
+        self.send(AlertKind::Quarantined { strikes });
+    }
+
+    fn send(&self, kind: AlertKind) {
+        let Ok(runtime) = Handle::try_current() else {
+            #[cfg(feature = "tracing")]
+            tracing::error!(
+                job = &*self.name,
+                "no tokio runtime is available, alert not sent"
+            );
+            return;
+        };
+        let name = self.name.clone();
+        let notifiers = self.notifiers.clone();
+        self.tracker.spawn_on(
+            async move { notify(&notifiers, &name, kind).await },
+            &runtime,
+        );
+    }
+}
+
+async fn notify(notifiers: &[Arc<dyn Notifier>], name: &str, kind: AlertKind) {
+    let alert = Alert {
+        name: name.to_string(),
+        kind,
+        at: SystemTime::now(),
+    };
+    for notifier in notifiers {
+        notifier.notify(alert.clone()).await;
+    }
+}
+
+pub(crate) fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "job panicked".to_string()
+    }
+}
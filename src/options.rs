@@ -0,0 +1,320 @@
+use crate::{
+    AlertRule, Budget, Completion, DstPolicy, Job, Layer, LoadPolicy, LogLevel, Misfire, Period,
+    PeriodLimit, Quarantine, RunContext, Sampling, Schedule, Supervisor, SuspendPolicy, Token,
+    TriggerPolicy, TruncateZone,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type Labels = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JobOptions {
+    pub name: Option<String>,
+    pub period: Option<Period>,
+    pub truncate_time: Option<bool>,
+    pub truncate_zone: Option<TruncateZone>,
+    pub dst_policy: Option<DstPolicy>,
+    pub supervisor: Option<Supervisor>,
+    pub exclusion_group: Option<String>,
+    pub alert_rule: Option<AlertRule>,
+    pub critical: Option<bool>,
+    pub labels: Option<Labels>,
+    pub enabled: Option<bool>,
+    pub sampling: Option<Sampling>,
+    pub max_duty_cycle: Option<f64>,
+    pub period_limit: Option<PeriodLimit>,
+    pub quarantine: Option<Quarantine>,
+    pub budget: Option<Budget>,
+    pub completion: Option<Completion>,
+    pub on_suspend: Option<SuspendPolicy>,
+    pub misfire: Option<Misfire>,
+    pub load_policy: Option<LoadPolicy>,
+    pub log_level: Option<LogLevel>,
+    pub dry_run: Option<bool>,
+    pub stagger: Option<bool>,
+    pub finish_by: Option<bool>,
+}
+
+impl JobOptions {
+    pub fn merge(self, overrides: JobOptions) -> Self {
+        Self {
+            name: overrides.name.or(self.name),
+            period: overrides.period.or(self.period),
+            truncate_time: overrides.truncate_time.or(self.truncate_time),
+            truncate_zone: overrides.truncate_zone.or(self.truncate_zone),
+            dst_policy: overrides.dst_policy.or(self.dst_policy),
+            supervisor: overrides.supervisor.or(self.supervisor),
+            exclusion_group: overrides.exclusion_group.or(self.exclusion_group),
+            alert_rule: overrides.alert_rule.or(self.alert_rule),
+            critical: overrides.critical.or(self.critical),
+            labels: overrides.labels.or(self.labels),
+            enabled: overrides.enabled.or(self.enabled),
+            sampling: overrides.sampling.or(self.sampling),
+            max_duty_cycle: overrides.max_duty_cycle.or(self.max_duty_cycle),
+            period_limit: overrides.period_limit.or(self.period_limit),
+            quarantine: overrides.quarantine.or(self.quarantine),
+            budget: overrides.budget.or(self.budget),
+            completion: overrides.completion.or(self.completion),
+            on_suspend: overrides.on_suspend.or(self.on_suspend),
+            misfire: overrides.misfire.or(self.misfire),
+            load_policy: overrides.load_policy.or(self.load_policy),
+            log_level: overrides.log_level.or(self.log_level),
+            dry_run: overrides.dry_run.or(self.dry_run),
+            stagger: overrides.stagger.or(self.stagger),
+            finish_by: overrides.finish_by.or(self.finish_by),
+        }
+    }
+
+    #[cfg(feature = "config")]
+    pub(crate) fn rebase(self, from: &JobOptions, onto: &JobOptions) -> Self {
+        Self {
+            name: rebase(self.name, &from.name, &onto.name),
+            period: rebase(self.period, &from.period, &onto.period),
+            truncate_time: rebase(self.truncate_time, &from.truncate_time, &onto.truncate_time),
+            truncate_zone: rebase(self.truncate_zone, &from.truncate_zone, &onto.truncate_zone),
+            dst_policy: rebase(self.dst_policy, &from.dst_policy, &onto.dst_policy),
+            supervisor: rebase(self.supervisor, &from.supervisor, &onto.supervisor),
+            exclusion_group: rebase(
+                self.exclusion_group,
+                &from.exclusion_group,
+                &onto.exclusion_group,
+            ),
+            alert_rule: rebase(self.alert_rule, &from.alert_rule, &onto.alert_rule),
+            critical: rebase(self.critical, &from.critical, &onto.critical),
+            labels: rebase(self.labels, &from.labels, &onto.labels),
+            enabled: rebase(self.enabled, &from.enabled, &onto.enabled),
+            sampling: rebase(self.sampling, &from.sampling, &onto.sampling),
+            max_duty_cycle: rebase(
+                self.max_duty_cycle,
+                &from.max_duty_cycle,
+                &onto.max_duty_cycle,
+            ),
+            period_limit: rebase(self.period_limit, &from.period_limit, &onto.period_limit),
+            quarantine: rebase(self.quarantine, &from.quarantine, &onto.quarantine),
+            budget: rebase(self.budget, &from.budget, &onto.budget),
+            completion: rebase(self.completion, &from.completion, &onto.completion),
+            on_suspend: rebase(self.on_suspend, &from.on_suspend, &onto.on_suspend),
+            misfire: rebase(self.misfire, &from.misfire, &onto.misfire),
+            load_policy: rebase(self.load_policy, &from.load_policy, &onto.load_policy),
+            log_level: rebase(self.log_level, &from.log_level, &onto.log_level),
+            dry_run: rebase(self.dry_run, &from.dry_run, &onto.dry_run),
+            stagger: rebase(self.stagger, &from.stagger, &onto.stagger),
+            finish_by: rebase(self.finish_by, &from.finish_by, &onto.finish_by),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+fn rebase<T: Clone + PartialEq>(
+    current: Option<T>,
+    from: &Option<T>,
+    onto: &Option<T>,
+) -> Option<T> {
+    match from == onto {
+        true => current,
+        false => onto.clone(),
+    }
+}
+
+pub struct Configured<J> {
+    job: J,
+    options: JobOptions,
+}
+
+impl<J: Job> Configured<J> {
+    pub fn new(job: J, options: JobOptions) -> Self {
+        Self { job, options }
+    }
+
+    pub fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    pub fn inner(&self) -> &J {
+        &self.job
+    }
+
+    pub fn inner_mut(&mut self) -> &mut J {
+        &mut self.job
+    }
+
+    pub fn into_inner(self) -> J {
+        self.job
+    }
+}
+
+impl<J: Job> Job for Configured<J> {
+    fn name(&self) -> &str {
+        match &self.options.name {
+            Some(name) => name,
+            None => self.job.name(),
+        }
+    }
+
+    fn period(&self) -> Duration {
+        match self.options.period {
+            Some(period) => period.into(),
+            None => self.job.period(),
+        }
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.options
+            .truncate_time
+            .unwrap_or_else(|| self.job.with_truncate_time())
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        self.options
+            .truncate_zone
+            .unwrap_or_else(|| self.job.truncate_zone())
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        self.options
+            .dst_policy
+            .unwrap_or_else(|| self.job.dst_policy())
+    }
+
+    fn run(&mut self) -> impl Future<Output = ()> + Send {
+        self.job.run()
+    }
+
+    fn with_cancel(&self) -> bool {
+        self.job.with_cancel()
+    }
+
+    fn run_with_cancel(&mut self, token: Token) -> impl Future<Output = ()> + Send {
+        self.job.run_with_cancel(token)
+    }
+
+    fn run_with_context(&mut self, ctx: RunContext) -> impl Future<Output = ()> + Send {
+        self.job.run_with_context(ctx)
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.options.supervisor.or_else(|| self.job.supervisor())
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        self.job.layers()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        match &self.options.exclusion_group {
+            Some(group) => Some(group),
+            None => self.job.exclusion_group(),
+        }
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        self.job.shard_key()
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        self.options.alert_rule.or_else(|| self.job.alert_rule())
+    }
+
+    fn critical(&self) -> bool {
+        self.options.critical.unwrap_or_else(|| self.job.critical())
+    }
+
+    fn labels(&self) -> Labels {
+        self.options
+            .labels
+            .clone()
+            .unwrap_or_else(|| self.job.labels())
+    }
+
+    fn enabled(&self) -> bool {
+        self.options.enabled.unwrap_or_else(|| self.job.enabled())
+    }
+
+    fn abortable(&self) -> bool {
+        self.job.abortable()
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        self.job.trigger_policy()
+    }
+
+    fn log_level(&self) -> LogLevel {
+        self.options
+            .log_level
+            .unwrap_or_else(|| self.job.log_level())
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        self.job.schedule()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.options.dry_run.unwrap_or_else(|| self.job.dry_run())
+    }
+
+    fn stagger(&self) -> bool {
+        self.options.stagger.unwrap_or_else(|| self.job.stagger())
+    }
+
+    fn finish_by(&self) -> bool {
+        self.options
+            .finish_by
+            .unwrap_or_else(|| self.job.finish_by())
+    }
+
+    fn sampling(&self) -> Sampling {
+        self.options.sampling.unwrap_or_else(|| self.job.sampling())
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        self.options
+            .max_duty_cycle
+            .or_else(|| self.job.max_duty_cycle())
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        self.options
+            .period_limit
+            .or_else(|| self.job.period_limit())
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        self.options.quarantine.or_else(|| self.job.quarantine())
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        self.options.budget.or_else(|| self.job.budget())
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        self.options.completion.or_else(|| self.job.completion())
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        self.options
+            .on_suspend
+            .unwrap_or_else(|| self.job.on_suspend())
+    }
+
+    fn misfire(&self) -> Misfire {
+        self.options.misfire.unwrap_or_else(|| self.job.misfire())
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        self.options
+            .load_policy
+            .unwrap_or_else(|| self.job.load_policy())
+    }
+
+    fn durable(&self) -> bool {
+        self.job.durable()
+    }
+
+    fn dedup(&self) -> bool {
+        self.job.dedup()
+    }
+}
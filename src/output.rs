@@ -0,0 +1,52 @@
+use crate::Job;
+use std::any::Any;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+pub trait OutputJob: Job {
+    type Output: Clone + Send + Sync + 'static;
+
+    fn output(&mut self) -> Option<Self::Output>;
+}
+
+pub(crate) type Outputs = Arc<dyn Any + Send + Sync>;
+
+pub(crate) struct Publisher<J> {
+    sender: Outputs,
+    publish: fn(&mut J, &Outputs),
+}
+
+impl<J> Publisher<J> {
+    pub(crate) fn new(capacity: usize) -> Self
+    where
+        J: OutputJob,
+    {
+        Self {
+            sender: Arc::new(broadcast::Sender::<J::Output>::new(capacity)),
+            publish: |job, sender| {
+                if let Some(output) = job.output()
+                    && let Some(sender) = sender.downcast_ref::<broadcast::Sender<J::Output>>()
+                {
+                    let _ = sender.send(output);
+                }
+            },
+        }
+    }
+
+    pub(crate) fn outputs(&self) -> Outputs {
+        self.sender.clone()
+    }
+
+    pub(crate) fn publish(&self, job: &mut J) {
+        (self.publish)(job, &self.sender)
+    }
+}
+
+pub(crate) fn subscribe<T: Clone + Send + 'static>(
+    outputs: Option<&Outputs>,
+) -> broadcast::Receiver<T> {
+    match outputs.and_then(|outputs| outputs.downcast_ref::<broadcast::Sender<T>>()) {
+        Some(sender) => sender.subscribe(),
+        None => broadcast::Sender::new(1).subscribe(),
+    }
+}
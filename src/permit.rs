@@ -0,0 +1,48 @@
+use crate::{Layer, Next, Run};
+use std::sync::Arc;
+use tokio::select;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+pub trait PermitProvider: Send + Sync + 'static {
+    type Permit: Send;
+
+    fn acquire(&self) -> impl Future<Output = Option<Self::Permit>> + Send;
+}
+
+impl PermitProvider for Arc<Semaphore> {
+    type Permit = OwnedSemaphorePermit;
+
+    async fn acquire(&self) -> Option<Self::Permit> {
+        self.clone().acquire_owned().await.ok()
+    }
+}
+
+pub struct Permits<P>(Arc<P>);
+
+impl<P: PermitProvider> Permits<P> {
+    pub fn new(provider: P) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl<P> Clone for Permits<P> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<P: PermitProvider> Layer for Permits<P> {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let token = next.context().token();
+            select! {
+                biased;
+                _ = token.cancelled() => next.context().skip(),
+                permit = self.0.acquire() => match permit {
+                    Some(_permit) => next.run().await,
+                    None => next.context().skip(),
+                },
+            }
+        })
+    }
+}
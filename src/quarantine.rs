@@ -0,0 +1,28 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quarantine {
+    pub(crate) strikes: u32,
+}
+
+impl Quarantine {
+    pub fn after(strikes: u32) -> Self {
+        Self {
+            strikes: strikes.max(1),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Strikes(u32);
+
+impl Strikes {
+    pub(crate) fn record(&mut self, quarantine: Option<Quarantine>, struck: bool) -> Option<u32> {
+        self.0 = match struck {
+            true => self.0 + 1,
+            false => 0,
+        };
+        let quarantine = quarantine.filter(|quarantine| self.0 >= quarantine.strikes)?;
+        self.0 = 0;
+        Some(quarantine.strikes)
+    }
+}
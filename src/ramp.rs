@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Clone)]
+pub(crate) struct Ramp {
+    window: Duration,
+    boot: Instant,
+    spawned: Arc<AtomicU32>,
+}
+
+impl Ramp {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            boot: Instant::now(),
+            spawned: Arc::default(),
+        }
+    }
+
+    pub(crate) fn start(&self) -> Instant {
+        let index = self.spawned.fetch_add(1, Ordering::Relaxed);
+        self.boot + self.window.mul_f64(corput(index))
+    }
+}
+
+fn corput(mut index: u32) -> f64 {
+    let mut fraction = 0.0;
+    let mut weight = 0.5;
+    while index > 0 {
+        if index & 1 == 1 {
+            fraction += weight;
+        }
+        weight /= 2.0;
+        index >>= 1;
+    }
+    fraction
+}
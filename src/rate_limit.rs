@@ -0,0 +1,65 @@
+use crate::{Layer, Next, Run};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::select;
+use tokio::time::{Instant, sleep};
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+#[derive(Clone)]
+pub struct RateLimit {
+    capacity: f64,
+    per: Duration,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimit {
+    pub fn new(runs: u32, per: Duration) -> Self {
+        let capacity = runs.max(1) as f64;
+        Self {
+            capacity,
+            per: per.max(Duration::from_nanos(1)),
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                refilled: Instant::now(),
+            })),
+        }
+    }
+
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let rate = self.capacity / self.per.as_secs_f64();
+        let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(self.capacity);
+        bucket.refilled = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            sleep(wait).await;
+        }
+    }
+}
+
+impl Layer for RateLimit {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let token = next.context().token();
+            select! {
+                biased;
+                _ = token.cancelled() => next.context().skip(),
+                _ = self.acquire() => next.run().await,
+            }
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use crate::schedule;
+use crate::{Schedule, TruncateZone};
+use chrono::{DateTime, Months, NaiveDateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+const AVERAGE_MONTH: Duration = Duration::from_secs(2_629_746);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repeating {
+    expression: String,
+    start: DateTime<Utc>,
+    months: u32,
+    fixed: Duration,
+    count: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRepeatingError(String);
+
+impl fmt::Display for ParseRepeatingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid repeating interval: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRepeatingError {}
+
+impl Repeating {
+    pub fn parse(expression: &str) -> Result<Self, ParseRepeatingError> {
+        let expression = expression.trim();
+        let invalid = |reason: String| ParseRepeatingError(format!("{reason} in `{expression}`"));
+        let [repeat, start, interval] = expression.split('/').collect::<Vec<_>>()[..] else {
+            return Err(invalid("expected `R[n]/start/duration`".into()));
+        };
+        let count = match repeat.strip_prefix('R') {
+            Some("") => None,
+            Some(count) => Some(
+                count
+                    .parse()
+                    .map_err(|_| invalid(format!("`{count}` is not a repetition count")))?,
+            ),
+            None => return Err(invalid(format!("`{repeat}` should start with `R`"))),
+        };
+        let start =
+            datetime(start).ok_or_else(|| invalid(format!("`{start}` is not a date-time")))?;
+        let (months, fixed) = match interval.starts_with('P') {
+            true => duration(interval).map_err(invalid)?,
+            false => {
+                let end = datetime(interval).ok_or_else(|| {
+                    invalid(format!("`{interval}` is not a duration or date-time"))
+                })?;
+                let fixed = (end - start)
+                    .to_std()
+                    .map_err(|_| invalid(format!("`{interval}` is before the start")))?;
+                (0, fixed)
+            }
+        };
+        if months == 0 && fixed.is_zero() {
+            return Err(invalid("the interval is empty".into()));
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            start,
+            months,
+            fixed,
+            count,
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    pub fn count(&self) -> Option<u64> {
+        self.count
+    }
+
+    fn nth(&self, n: u64) -> Option<DateTime<Utc>> {
+        let months = u32::try_from(n.checked_mul(self.months as u64)?).ok()?;
+        let nanos = self.fixed.as_nanos().checked_mul(n as u128)?;
+        let secs = i64::try_from(nanos / 1_000_000_000).ok()?;
+        let fixed = chrono::Duration::new(secs, (nanos % 1_000_000_000) as u32)?;
+        self.start
+            .checked_add_months(Months::new(months))?
+            .checked_add_signed(fixed)
+    }
+}
+
+impl FromStr for Repeating {
+    type Err = ParseRepeatingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Repeating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl Schedule for Repeating {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        let after = DateTime::<Utc>::from(after);
+        let elapsed = (after - self.start).to_std().unwrap_or_default();
+        let step = AVERAGE_MONTH * self.months + self.fixed;
+        let mut n = (elapsed.as_nanos() / step.as_nanos()).saturating_sub(2) as u64;
+        loop {
+            if self.count.is_some_and(|count| n >= count) {
+                return None;
+            }
+            let tick = self.nth(n)?;
+            if tick > after {
+                return Some(tick.into());
+            }
+            n += 1;
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut period = match (self.months, self.fixed.is_zero()) {
+            (0, _) => schedule::every(self.fixed),
+            (12, true) => "every year".into(),
+            (months, true) if months % 12 == 0 => format!("every {} years", months / 12),
+            (1, true) => "every month".into(),
+            (months, true) => format!("every {months} months"),
+            (months, false) => format!("every {months} months {}", schedule::duration(self.fixed)),
+        };
+        period += &format!(
+            ", starting {}",
+            schedule::at(TruncateZone::Utc, self.start.into())
+        );
+        match self.count {
+            Some(1) => period + ", once",
+            Some(count) => period + &format!(", {count} times"),
+            None => period,
+        }
+    }
+}
+
+fn datetime(text: &str) -> Option<DateTime<Utc>> {
+    match DateTime::parse_from_rfc3339(text) {
+        Ok(at) => Some(at.to_utc()),
+        Err(_) => Some(
+            NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S")
+                .ok()?
+                .and_utc(),
+        ),
+    }
+}
+
+fn duration(text: &str) -> Result<(u32, Duration), String> {
+    let invalid = || format!("`{text}` is not an ISO 8601 duration");
+    let too_long = || format!("`{text}` is too long");
+    let body = text.strip_prefix('P').ok_or_else(invalid)?;
+    let (date, time) = match body.split_once('T') {
+        Some((_, "")) => return Err(invalid()),
+        Some((date, time)) => (date, time),
+        None => (body, ""),
+    };
+    if date.is_empty() && time.is_empty() {
+        return Err(invalid());
+    }
+    let mut months: u32 = 0;
+    let mut fixed = Duration::ZERO;
+    for (part, units) in [(date, "YMWD"), (time, "HMS")] {
+        let mut number = String::new();
+        let mut seen = 0;
+        for c in part.chars() {
+            if c.is_ascii_digit() || c == '.' && units == "HMS" {
+                number.push(c);
+                continue;
+            }
+            let position = units.find(c).filter(|&i| i >= seen).ok_or_else(invalid)?;
+            seen = position + 1;
+            let whole = || number.parse::<u64>().map_err(|_| invalid());
+            let added = match (units, c) {
+                ("HMS", 'S') => {
+                    let value: f64 = number.parse().map_err(|_| invalid())?;
+                    Duration::try_from_secs_f64(value).map_err(|_| too_long())?
+                }
+                ("YMWD", 'Y' | 'M') => {
+                    let scale = if c == 'Y' { 12 } else { 1 };
+                    let added = whole()?
+                        .checked_mul(scale)
+                        .and_then(|added| u32::try_from(added).ok())
+                        .ok_or_else(too_long)?;
+                    months = months.checked_add(added).ok_or_else(too_long)?;
+                    Duration::ZERO
+                }
+                ("YMWD", 'W') => seconds(whole()?, 7 * 86_400).ok_or_else(too_long)?,
+                ("YMWD", 'D') => seconds(whole()?, 86_400).ok_or_else(too_long)?,
+                ("HMS", 'H') => seconds(whole()?, 3600).ok_or_else(too_long)?,
+                ("HMS", 'M') => seconds(whole()?, 60).ok_or_else(too_long)?,
+                _ => return Err(invalid()),
+            };
+            fixed = fixed.checked_add(added).ok_or_else(too_long)?;
+            number.clear();
+        }
+        if !number.is_empty() {
+            return Err(invalid());
+        }
+    }
+    Ok((months, fixed))
+}
+
+fn seconds(amount: u64, unit: u64) -> Option<Duration> {
+    amount.checked_mul(unit).map(Duration::from_secs)
+}
@@ -0,0 +1,196 @@
+use crate::journal;
+use crate::schedule;
+use crate::{Event, EventKind, RunStatus, Schedule, SkipReason, TruncateZone};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const NANO: Duration = Duration::from_nanos(1);
+
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayedTick {
+    pub tick: SystemTime,
+    pub decision: TickDecision,
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TickDecision {
+    Ran {
+        run_id: u64,
+        started: SystemTime,
+        status: Option<RunStatus>,
+    },
+    Skipped(SkipReason),
+    Overrun {
+        run_id: u64,
+    },
+    Quarantined,
+    Stopped,
+    Unrecorded,
+}
+
+impl Replay {
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        let mut events: Vec<_> = events.into_iter().collect();
+        events.sort_by_key(|event| event.at);
+        Self { events }
+    }
+
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let mut events = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = journal::parse(&line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {} is not an event log entry", index + 1),
+                )
+            })?;
+            events.push(event);
+        }
+        Ok(Self::new(events))
+    }
+
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn job<S: Schedule + ?Sized>(
+        &self,
+        name: &str,
+        schedule: &S,
+        range: Range<SystemTime>,
+    ) -> Vec<ReplayedTick> {
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|event| &*event.name == name)
+            .collect();
+        let mut ticks = Vec::new();
+        let mut next = schedule.next_after(range.start);
+        while let Some(tick) = next.filter(|tick| *tick < range.end) {
+            next = schedule.next_after(tick).filter(|next| *next > tick);
+            ticks.push(replay_tick(&events, tick, next));
+        }
+        ticks
+    }
+
+    pub fn tick<S: Schedule + ?Sized>(
+        &self,
+        name: &str,
+        schedule: &S,
+        at: SystemTime,
+    ) -> Option<ReplayedTick> {
+        let tick = schedule.next_after(at.checked_sub(NANO)?)?;
+        self.job(name, schedule, tick - NANO..tick + NANO)
+            .into_iter()
+            .next()
+    }
+}
+
+fn replay_tick(events: &[&Event], tick: SystemTime, next: Option<SystemTime>) -> ReplayedTick {
+    let (before, rest) = events.split_at(events.partition_point(|event| event.at < tick));
+    let during: Vec<Event> = rest
+        .iter()
+        .take_while(|event| next.is_none_or(|next| event.at < next))
+        .map(|event| (*event).clone())
+        .collect();
+    let decision = during
+        .iter()
+        .find_map(|event| match event.kind {
+            EventKind::Started { run_id, .. } => Some(TickDecision::Ran {
+                run_id,
+                started: event.at,
+                status: finished(rest, run_id),
+            }),
+            _ => None,
+        })
+        .or_else(|| {
+            during.iter().find_map(|event| match event.kind {
+                EventKind::Skipped { reason } => Some(TickDecision::Skipped(reason)),
+                _ => None,
+            })
+        })
+        .unwrap_or_else(|| {
+            before
+                .iter()
+                .rev()
+                .find_map(|event| match event.kind {
+                    EventKind::Started { run_id, .. } if finished(before, run_id).is_none() => {
+                        Some(TickDecision::Overrun { run_id })
+                    }
+                    EventKind::Quarantined { .. } => Some(TickDecision::Quarantined),
+                    EventKind::Cancelled { .. } => Some(TickDecision::Stopped),
+                    EventKind::Started { .. } => Some(TickDecision::Unrecorded),
+                    _ => None,
+                })
+                .unwrap_or(TickDecision::Unrecorded)
+        });
+    ReplayedTick {
+        tick,
+        decision,
+        events: during,
+    }
+}
+
+fn finished(events: &[&Event], id: u64) -> Option<RunStatus> {
+    events.iter().find_map(|event| match &event.kind {
+        EventKind::Finished { run_id, status, .. } if *run_id == id => Some(status.clone()),
+        _ => None,
+    })
+}
+
+impl fmt::Display for TickDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ran {
+                run_id,
+                status: Some(status),
+                ..
+            } => write!(f, "ran as run {run_id}, {}", status.label()),
+            Self::Ran { run_id, .. } => write!(f, "ran as run {run_id}, never finished"),
+            Self::Skipped(reason) => write!(f, "skipped ({})", journal::skip_reason(*reason)),
+            Self::Overrun { run_id } => write!(f, "missed, run {run_id} was still in progress"),
+            Self::Quarantined => write!(f, "missed, the job was quarantined"),
+            Self::Stopped => write!(f, "missed, the scheduler had stopped"),
+            Self::Unrecorded => write!(f, "missed, nothing was recorded for this tick"),
+        }
+    }
+}
+
+impl fmt::Display for ReplayedTick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tick = schedule::at(TruncateZone::Utc, self.tick);
+        match &self.decision {
+            TickDecision::Ran {
+                run_id,
+                started,
+                status,
+            } => {
+                let late = started.duration_since(self.tick).unwrap_or_default();
+                let status = status.as_ref().map_or("never finished", RunStatus::label);
+                write!(
+                    f,
+                    "{tick}: ran as run {run_id} {late:?} after the tick, {status}"
+                )
+            }
+            decision => write!(f, "{tick}: {decision}"),
+        }
+    }
+}
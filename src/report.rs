@@ -0,0 +1,36 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RunStatus {
+    #[default]
+    Success,
+    NoWork,
+    PartialSuccess(String),
+    Failed(String),
+}
+
+impl RunStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::NoWork => "no_work",
+            Self::PartialSuccess(_) => "partial_success",
+            Self::Failed(_) => "failed",
+        }
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            Self::PartialSuccess(detail) | Self::Failed(detail) => Some(detail),
+            Self::Success | Self::NoWork => None,
+        }
+    }
+}
+
+pub(crate) type Report = Arc<Mutex<Option<RunStatus>>>;
@@ -0,0 +1,49 @@
+use std::hash::{BuildHasher, RandomState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Sampling {
+    #[default]
+    Always,
+    EveryNth(u32),
+    Fraction(f64),
+    Random(f64),
+}
+
+pub(crate) struct Sampler {
+    sampling: Sampling,
+    ticks: u64,
+    state: u64,
+}
+
+impl Sampler {
+    pub(crate) fn new(sampling: Sampling) -> Self {
+        Self {
+            sampling,
+            ticks: 0,
+            state: RandomState::new().hash_one(0u64) | 1,
+        }
+    }
+
+    pub(crate) fn sample(&mut self) -> bool {
+        let tick = self.ticks;
+        self.ticks += 1;
+        match self.sampling {
+            Sampling::Always => true,
+            Sampling::EveryNth(n) => tick.is_multiple_of(n.max(1) as u64),
+            Sampling::Fraction(fraction) => {
+                let fraction = fraction.clamp(0.0, 1.0);
+                ((tick + 1) as f64 * fraction).floor() > (tick as f64 * fraction).floor()
+            }
+            Sampling::Random(probability) => self.random() < probability,
+        }
+    }
+
+    fn random(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,434 @@
+use crate::{DstPolicy, TruncateZone};
+use chrono::{DateTime, Datelike, NaiveTime, TimeDelta, Utc, Weekday};
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(86_400);
+const NANO: Duration = Duration::from_nanos(1);
+const MAX_STEPS: usize = 100_000;
+
+pub trait Schedule: Send + Sync + 'static {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime>;
+
+    fn fires_at_start(&self, _started: SystemTime) -> bool {
+        false
+    }
+
+    fn zone(&self) -> TruncateZone {
+        TruncateZone::Utc
+    }
+
+    fn describe(&self) -> String {
+        "custom schedule".into()
+    }
+
+    fn explain(&self, now: SystemTime) -> String {
+        match self.next_after(now) {
+            Some(next) => format!("{}, next at {}", self.describe(), at(self.zone(), next)),
+            None => format!("{}, no upcoming runs", self.describe()),
+        }
+    }
+
+    fn fire_times(&self, after: SystemTime) -> FireTimes<'_, Self>
+    where
+        Self: Sized,
+    {
+        FireTimes {
+            schedule: self,
+            after: Some(after),
+        }
+    }
+
+    fn between(self, start: Duration, end: Duration) -> Filtered<Self>
+    where
+        Self: Sized,
+    {
+        Filtered::new(self, Rule::Between(time_of_day(start), time_of_day(end)))
+    }
+
+    fn on_days(self, days: &[Weekday]) -> Filtered<Self>
+    where
+        Self: Sized,
+    {
+        let days = days
+            .iter()
+            .fold(0, |mask, day| mask | 1 << day.num_days_from_monday());
+        Filtered::new(self, Rule::OnDays(days))
+    }
+
+    fn except(self, range: Range<SystemTime>) -> Filtered<Self>
+    where
+        Self: Sized,
+    {
+        Filtered::new(self, Rule::Except(range))
+    }
+
+    fn or(self, other: impl Schedule) -> Union
+    where
+        Self: Sized,
+    {
+        Union::new().with(self).with(other)
+    }
+}
+
+impl<S: Schedule + ?Sized> Schedule for Arc<S> {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        (**self).next_after(after)
+    }
+
+    fn fires_at_start(&self, started: SystemTime) -> bool {
+        (**self).fires_at_start(started)
+    }
+
+    fn zone(&self) -> TruncateZone {
+        (**self).zone()
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn explain(&self, now: SystemTime) -> String {
+        (**self).explain(now)
+    }
+}
+
+impl<S: Schedule + ?Sized> Schedule for Box<S> {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        (**self).next_after(after)
+    }
+
+    fn fires_at_start(&self, started: SystemTime) -> bool {
+        (**self).fires_at_start(started)
+    }
+
+    fn zone(&self) -> TruncateZone {
+        (**self).zone()
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn explain(&self, now: SystemTime) -> String {
+        (**self).explain(now)
+    }
+}
+
+pub struct FireTimes<'a, S> {
+    schedule: &'a S,
+    after: Option<SystemTime>,
+}
+
+impl<S: Schedule> Iterator for FireTimes<'_, S> {
+    type Item = SystemTime;
+
+    fn next(&mut self) -> Option<SystemTime> {
+        let next = self
+            .after
+            .and_then(|after| self.schedule.next_after(after))
+            .filter(|next| self.after < Some(*next));
+        self.after = next;
+        next
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Every {
+    period: Duration,
+    offset: Duration,
+    zone: TruncateZone,
+    dst: DstPolicy,
+}
+
+impl Every {
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            offset: Duration::ZERO,
+            zone: TruncateZone::Utc,
+            dst: DstPolicy::default(),
+        }
+    }
+
+    pub fn offset(mut self, offset: Duration) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn zone(mut self, zone: TruncateZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    pub fn dst_policy(mut self, dst: DstPolicy) -> Self {
+        self.dst = dst;
+        self
+    }
+}
+
+impl Schedule for Every {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        if self.period.is_zero() {
+            return None;
+        }
+        self.zone
+            .next_boundary(self.period, self.offset, after, self.dst)
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.zone
+    }
+
+    fn describe(&self) -> String {
+        let mut text = every(self.period);
+        if !self.offset.is_zero() {
+            text += &format!(", offset by {}", duration(self.offset));
+        }
+        text + &format!(", aligned to {}", zone_name(self.zone))
+    }
+}
+
+impl fmt::Display for Every {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Union(Vec<Arc<dyn Schedule>>);
+
+impl Union {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, schedule: impl Schedule) -> Self {
+        self.0.push(Arc::new(schedule));
+        self
+    }
+}
+
+impl Schedule for Union {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        self.0
+            .iter()
+            .filter_map(|schedule| schedule.next_after(after))
+            .min()
+    }
+
+    fn fires_at_start(&self, started: SystemTime) -> bool {
+        self.0
+            .iter()
+            .any(|schedule| schedule.fires_at_start(started))
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.0
+            .first()
+            .map_or(TruncateZone::Utc, |schedule| schedule.zone())
+    }
+
+    fn describe(&self) -> String {
+        match self.0.is_empty() {
+            true => "never".into(),
+            false => self
+                .0
+                .iter()
+                .map(|schedule| schedule.describe())
+                .collect::<Vec<_>>()
+                .join(" or "),
+        }
+    }
+
+    fn or(self, other: impl Schedule) -> Union {
+        self.with(other)
+    }
+}
+
+impl fmt::Display for Union {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    Between(NaiveTime, NaiveTime),
+    OnDays(u8),
+    Except(Range<SystemTime>),
+}
+
+impl Rule {
+    fn resume(&self, zone: TruncateZone, at: SystemTime) -> Option<SystemTime> {
+        match self {
+            Self::Between(start, end) => {
+                let local = zone.local(at);
+                let time = local.time();
+                let inside = match start <= end {
+                    true => *start <= time && time < *end || start == end,
+                    false => *start <= time || time < *end,
+                };
+                if inside {
+                    return None;
+                }
+                let mut date = local.date();
+                if time >= *start {
+                    date = date.succ_opt()?;
+                }
+                Some(zone.resolve_local(date.and_time(*start)) - NANO)
+            }
+            Self::OnDays(days) => {
+                let local = zone.local(at);
+                if days & 1 << local.weekday().num_days_from_monday() != 0 {
+                    return None;
+                }
+                let midnight = local.date().succ_opt()?.and_time(NaiveTime::MIN);
+                Some(zone.resolve_local(midnight) - NANO)
+            }
+            Self::Except(range) => range.contains(&at).then(|| range.end - NANO),
+        }
+    }
+
+    fn describe(&self, zone: TruncateZone) -> String {
+        match self {
+            Self::Between(start, end) => {
+                format!(
+                    "between {} and {}",
+                    start.format("%H:%M"),
+                    end.format("%H:%M")
+                )
+            }
+            Self::OnDays(days) => {
+                let days: Vec<String> = (0..7u8)
+                    .filter(|day| days & 1 << day != 0)
+                    .filter_map(|day| Weekday::try_from(day).ok())
+                    .map(|day| day.to_string())
+                    .collect();
+                match days.is_empty() {
+                    true => "on no days".into(),
+                    false => format!("on {}", days.join(", ")),
+                }
+            }
+            Self::Except(range) => {
+                format!(
+                    "except from {} to {}",
+                    at(zone, range.start),
+                    at(zone, range.end)
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Filtered<S> {
+    schedule: S,
+    rule: Rule,
+}
+
+impl<S> Filtered<S> {
+    fn new(schedule: S, rule: Rule) -> Self {
+        Self { schedule, rule }
+    }
+}
+
+impl<S: Schedule> Schedule for Filtered<S> {
+    fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+        if self.rule == Rule::OnDays(0) {
+            return None;
+        }
+        let zone = self.schedule.zone();
+        let mut after = after;
+        for _ in 0..MAX_STEPS {
+            let next = self.schedule.next_after(after)?;
+            match self.rule.resume(zone, next) {
+                Some(resume) => after = resume.max(next),
+                None => return Some(next),
+            }
+        }
+        None
+    }
+
+    fn fires_at_start(&self, started: SystemTime) -> bool {
+        self.rule != Rule::OnDays(0)
+            && self.schedule.fires_at_start(started)
+            && self.rule.resume(self.schedule.zone(), started).is_none()
+    }
+
+    fn zone(&self) -> TruncateZone {
+        self.schedule.zone()
+    }
+
+    fn describe(&self) -> String {
+        let zone = self.schedule.zone();
+        format!("{}, {}", self.schedule.describe(), self.rule.describe(zone))
+    }
+}
+
+impl<S: Schedule> fmt::Display for Filtered<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+fn time_of_day(since_midnight: Duration) -> NaiveTime {
+    let since_midnight = TimeDelta::from_std(since_midnight.min(DAY)).unwrap_or_default();
+    NaiveTime::MIN + since_midnight
+}
+
+pub(crate) fn every(period: Duration) -> String {
+    match duration(period).strip_prefix("1 ") {
+        Some(unit) if !unit.contains(' ') => format!("every {unit}"),
+        _ => format!("every {}", duration(period)),
+    }
+}
+
+pub(crate) fn duration(duration: Duration) -> String {
+    let mut secs = duration.as_secs();
+    let mut parts = Vec::new();
+    for (unit, size) in [
+        ("day", 86_400),
+        ("hour", 3600),
+        ("minute", 60),
+        ("second", 1),
+    ] {
+        let count = secs / size;
+        secs %= size;
+        match count {
+            0 => {}
+            1 => parts.push(format!("1 {unit}")),
+            count => parts.push(format!("{count} {unit}s")),
+        }
+    }
+    match duration.subsec_millis() {
+        0 if parts.is_empty() => parts.push(format!("{}ns", duration.subsec_nanos())),
+        0 => {}
+        millis => parts.push(format!("{millis}ms")),
+    }
+    parts.join(" ")
+}
+
+pub(crate) fn zone_name(zone: TruncateZone) -> String {
+    match zone {
+        TruncateZone::Utc => "UTC".into(),
+        TruncateZone::Local => "local time".into(),
+        #[cfg(feature = "tz")]
+        TruncateZone::Tz(tz) => tz.name().into(),
+    }
+}
+
+pub(crate) fn at(zone: TruncateZone, at: SystemTime) -> String {
+    match zone {
+        TruncateZone::Utc => DateTime::<Utc>::from(at)
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string(),
+        zone => format!(
+            "{} {}",
+            zone.local(at).format("%Y-%m-%d %H:%M:%S"),
+            zone_name(zone)
+        ),
+    }
+}
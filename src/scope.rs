@@ -0,0 +1,57 @@
+use crate::Token;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::select;
+use tokio::time::{Instant, MissedTickBehavior, interval_at};
+
+type Loop<'env> = Pin<Box<dyn Future<Output = ()> + Send + 'env>>;
+
+pub struct Scope<'env> {
+    token: Token,
+    loops: Vec<Loop<'env>>,
+}
+
+impl<'env> Scope<'env> {
+    pub fn every<F, Fut>(&mut self, period: Duration, mut run: F) -> &mut Self
+    where
+        F: FnMut() -> Fut + Send + 'env,
+        Fut: Future<Output = ()> + Send + 'env,
+    {
+        let token = self.token.clone();
+        let period = period.max(Duration::from_millis(1));
+        self.loops.push(Box::pin(async move {
+            let mut ticks = interval_at(Instant::now() + period, period);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                select! {
+                    biased;
+                    _ = token.cancelled() => break,
+                    _ = ticks.tick() => run().await,
+                }
+            }
+        }));
+        self
+    }
+}
+
+pub async fn scope<'env, F>(token: Token, build: F)
+where
+    F: FnOnce(&mut Scope<'env>),
+{
+    let mut scope = Scope {
+        token,
+        loops: Vec::new(),
+    };
+    build(&mut scope);
+    let mut loops = scope.loops;
+    poll_fn(|cx| {
+        loops.retain_mut(|job| job.as_mut().poll(cx).is_pending());
+        match loops.is_empty() {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    })
+    .await
+}
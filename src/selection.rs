@@ -0,0 +1,99 @@
+use crate::Labels;
+use crate::status::JobShared;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    name: Option<String>,
+    group: Option<String>,
+    labels: Labels,
+}
+
+impl JobFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, pattern: &str) -> Self {
+        self.name = Some(pattern.to_string());
+        self
+    }
+
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    pub fn label(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub(crate) fn matches(&self, shared: &JobShared) -> bool {
+        self.name
+            .as_ref()
+            .is_none_or(|pattern| glob(pattern.as_bytes(), shared.name.as_bytes()))
+            && self
+                .group
+                .as_ref()
+                .is_none_or(|group| shared.group().as_deref() == Some(group.as_str()))
+            && (self.labels.is_empty() || {
+                let labels = shared.labels();
+                self.labels
+                    .iter()
+                    .all(|(key, value)| labels.get(key) == Some(value))
+            })
+    }
+}
+
+pub struct JobSet {
+    jobs: Vec<Arc<JobShared>>,
+}
+
+impl JobSet {
+    pub(crate) fn new(jobs: Vec<Arc<JobShared>>) -> Self {
+        Self { jobs }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.jobs.iter().map(|job| job.name.to_string()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn enable(&self) {
+        self.jobs.iter().for_each(|job| job.set_enabled(true));
+    }
+
+    pub fn disable(&self) {
+        self.jobs.iter().for_each(|job| job.set_enabled(false));
+    }
+
+    pub fn trigger(&self) {
+        self.jobs.iter().for_each(|job| {
+            job.trigger_now();
+        });
+    }
+
+    pub fn cancel_runs(&self) -> usize {
+        self.jobs.iter().filter(|job| job.cancel_run()).count()
+    }
+}
+
+fn glob(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob(&pattern[1..], name) || (!name.is_empty() && glob(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
@@ -0,0 +1,25 @@
+use crate::stagger;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+#[derive(Clone, Default)]
+pub(crate) struct Shards(Arc<[Handle]>);
+
+impl Shards {
+    pub(crate) fn new(runtimes: impl IntoIterator<Item = Handle>) -> Self {
+        Self(runtimes.into_iter().collect())
+    }
+
+    pub(crate) fn pick(&self, key: &str) -> Option<&Handle> {
+        match self.0.len() {
+            0 => None,
+            shards => self
+                .0
+                .get((stagger::hash(key.as_bytes()) % shards as u64) as usize),
+        }
+    }
+}
+
+pub(crate) fn runtime(shard: Option<&Handle>) -> Option<Handle> {
+    shard.cloned().or_else(|| Handle::try_current().ok())
+}
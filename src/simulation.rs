@@ -0,0 +1,149 @@
+use crate::clock::Clock;
+use crate::{Job, JobHandle, Layer, Next, Run, Scheduler, StopReport};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tokio::task::yield_now;
+use tokio::time::{Instant, pause, sleep};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Succeeded,
+    Panicked,
+    Aborted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fire {
+    pub job: String,
+    pub tick: SystemTime,
+    pub started: SystemTime,
+    pub finished: SystemTime,
+    pub outcome: Outcome,
+}
+
+type Fires = Arc<Mutex<Vec<Fire>>>;
+
+pub struct Simulation {
+    scheduler: Scheduler,
+    clock: Clock,
+    start: SystemTime,
+    fires: Fires,
+}
+
+impl Simulation {
+    pub fn new(start: SystemTime) -> Self {
+        Self::from_scheduler(start, Scheduler::new())
+    }
+
+    pub fn from_scheduler(start: SystemTime, mut scheduler: Scheduler) -> Self {
+        pause();
+        let clock = Clock::Virtual {
+            start,
+            origin: Instant::now(),
+        };
+        let fires = Fires::default();
+        scheduler.clock = clock;
+        let scheduler = scheduler.layer(Recorder {
+            clock,
+            fires: fires.clone(),
+        });
+        Self {
+            scheduler,
+            clock,
+            start,
+            fires,
+        }
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    pub fn spawn<J: Job>(&self, job: J) -> JobHandle<J> {
+        self.scheduler.spawn_with_handle(job)
+    }
+
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.now().duration_since(self.start).unwrap_or_default()
+    }
+
+    pub async fn advance(&self, by: Duration) {
+        sleep(by).await;
+        yield_now().await;
+    }
+
+    pub async fn advance_to(&self, at: SystemTime) {
+        self.advance(at.duration_since(self.now()).unwrap_or_default())
+            .await;
+    }
+
+    pub fn fires(&self) -> Vec<Fire> {
+        self.fires.lock().unwrap().clone()
+    }
+
+    pub fn fire_times(&self, job: &str) -> Vec<Duration> {
+        self.fires
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|fire| fire.job == job)
+            .map(|fire| fire.tick.duration_since(self.start).unwrap_or_default())
+            .collect()
+    }
+
+    pub async fn stop(self) -> StopReport {
+        self.scheduler.stop().await
+    }
+}
+
+struct Recorder {
+    clock: Clock,
+    fires: Fires,
+}
+
+impl Layer for Recorder {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        let ctx = next.context();
+        let pending = Pending {
+            recorder: self,
+            job: ctx.name().to_string(),
+            tick: ctx.tick(),
+            started: ctx.started(),
+            outcome: None,
+        };
+        Box::pin(async move {
+            let mut pending = pending;
+            next.run().await;
+            pending.outcome = Some(Outcome::Succeeded);
+        })
+    }
+}
+
+struct Pending<'a> {
+    recorder: &'a Recorder,
+    job: String,
+    tick: SystemTime,
+    started: SystemTime,
+    outcome: Option<Outcome>,
+}
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        let outcome = self.outcome.unwrap_or(match thread::panicking() {
+            true => Outcome::Panicked,
+            false => Outcome::Aborted,
+        });
+        self.recorder.fires.lock().unwrap().push(Fire {
+            job: std::mem::take(&mut self.job),
+            tick: self.tick,
+            started: self.started,
+            finished: self.recorder.clock.now(),
+            outcome,
+        });
+    }
+}
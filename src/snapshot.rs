@@ -0,0 +1,48 @@
+use crate::status::JobShared;
+use crate::{JobOptions, JobStats};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchedulerState {
+    pub jobs: Vec<JobState>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobState {
+    pub name: String,
+    pub critical: bool,
+    pub enabled: bool,
+    pub options: JobOptions,
+    pub stats: JobStats,
+}
+
+pub(crate) type Imported = Arc<Mutex<HashMap<String, JobState>>>;
+
+impl JobState {
+    pub(crate) fn capture(shared: &JobShared) -> Self {
+        Self {
+            name: shared.name.to_string(),
+            critical: shared.critical(),
+            enabled: shared.enabled(),
+            options: shared.options.borrow().clone(),
+            stats: shared.stats.lock().unwrap().clone(),
+        }
+    }
+
+    pub(crate) fn restore(self, shared: &JobShared) {
+        let mut options = self.options;
+        options.enabled = Some(self.enabled);
+        shared
+            .options
+            .send_modify(|current| *current = current.clone().merge(options));
+        let mut stats = shared.stats.lock().unwrap();
+        if self.stats.last_run > stats.last_run {
+            stats.last_run = self.stats.last_run;
+            stats.last_duration = self.stats.last_duration;
+        }
+        stats.last_success = stats.last_success.max(self.stats.last_success);
+    }
+}
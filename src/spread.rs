@@ -0,0 +1,68 @@
+use crate::stagger;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Spread {
+    #[default]
+    Off,
+    Registration,
+    NameHash,
+}
+
+struct Member {
+    name: Arc<str>,
+    hash: u64,
+    period: Option<Duration>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Spreader {
+    spread: Spread,
+    members: Arc<Mutex<Vec<Member>>>,
+}
+
+impl Spreader {
+    pub(crate) fn new(spread: Spread) -> Self {
+        Self {
+            spread,
+            members: Arc::default(),
+        }
+    }
+
+    pub(crate) fn register(&self, name: &Arc<str>, period: Option<Duration>) -> usize {
+        let mut members = self.members.lock().unwrap();
+        if self.spread != Spread::Off {
+            members.push(Member {
+                name: name.clone(),
+                hash: stagger::hash(name.as_bytes()),
+                period,
+            });
+        }
+        members.len().saturating_sub(1)
+    }
+
+    pub(crate) fn offset(&self, member: usize, period: Option<Duration>) -> Duration {
+        if self.spread == Spread::Off {
+            return Duration::ZERO;
+        }
+        let mut members = self.members.lock().unwrap();
+        members[member].period = period;
+        let Some(period) = period else {
+            return Duration::ZERO;
+        };
+        let own = &members[member];
+        let before = |(index, other): &(usize, &Member)| match self.spread {
+            Spread::NameHash => (other.hash, &other.name, *index) < (own.hash, &own.name, member),
+            _ => *index < member,
+        };
+        let peers = members
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.period == Some(period));
+        let (rank, count) = peers.fold((0, 0), |(rank, count), peer| {
+            (rank + before(&peer) as u128, count + 1)
+        });
+        Duration::from_nanos((period.as_nanos() * rank / count) as u64)
+    }
+}
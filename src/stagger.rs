@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+pub(crate) fn offset(key: &str, name: &str, period: Duration) -> Duration {
+    let period = period.as_nanos();
+    if period == 0 {
+        return Duration::ZERO;
+    }
+    let hash = hash(&[key.as_bytes(), &[0], name.as_bytes()].concat());
+    Duration::from_nanos((hash as u128 % period) as u64)
+}
+
+pub(crate) fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+pub(crate) fn hostname() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().filter(|host| !host.is_empty()))
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .unwrap_or_default()
+}
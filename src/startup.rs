@@ -0,0 +1,49 @@
+use crate::Token;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+pub trait StartupJob: Send + 'static {
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn run(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+pub(crate) type Startups = Vec<(String, JoinHandle<()>)>;
+
+#[derive(Clone, Default)]
+pub(crate) struct Gate(Arc<watch::Sender<usize>>);
+
+impl Gate {
+    pub(crate) fn enter(&self, token: Token) -> Pass {
+        self.0.send_modify(|pending| *pending += 1);
+        Pass {
+            gate: self.clone(),
+            token,
+        }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        *self.0.borrow() == 0
+    }
+
+    pub(crate) async fn opened(&self) {
+        let _ = self.0.subscribe().wait_for(|pending| *pending == 0).await;
+    }
+}
+
+pub(crate) struct Pass {
+    gate: Gate,
+    token: Token,
+}
+
+impl Drop for Pass {
+    fn drop(&mut self) {
+        match std::thread::panicking() {
+            true => self.token.cancel(),
+            false => self.gate.0.send_modify(|pending| *pending -= 1),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::report::Report;
+use crate::{Layer, Next, Run, RunStatus};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Clone)]
+pub struct Statsd {
+    socket: Arc<UdpSocket>,
+    prefix: String,
+    tags: bool,
+}
+
+impl Statsd {
+    pub fn new(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let mut error = None;
+        for addr in addr.to_socket_addrs()? {
+            match connect(addr) {
+                Ok(socket) => {
+                    return Ok(Self {
+                        socket: Arc::new(socket),
+                        prefix: "periodic_job".to_string(),
+                        tags: false,
+                    });
+                }
+                Err(failed) => error = Some(failed),
+            }
+        }
+        Err(error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no address to send metrics to")
+        }))
+    }
+
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn dogstatsd(mut self, tags: bool) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    fn send(&self, job: &str, metric: &str, value: &str) {
+        let packet = if self.tags {
+            format!("{}.{metric}:{value}|#job:{}", self.prefix, sanitize(job))
+        } else {
+            format!("{}.{}.{metric}:{value}", self.prefix, sanitize(job))
+        };
+        let _ = self.socket.send(packet.as_bytes());
+    }
+}
+
+fn connect(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let local = match addr {
+        SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.connect(addr)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+struct Timing<'a> {
+    statsd: &'a Statsd,
+    job: String,
+    start: Instant,
+    report: Report,
+}
+
+impl Drop for Timing<'_> {
+    fn drop(&mut self) {
+        let millis = self.start.elapsed().as_secs_f64() * 1000.0;
+        self.statsd.send(&self.job, "runs", "1|c");
+        self.statsd
+            .send(&self.job, "duration", &format!("{millis:.3}|ms"));
+        let status = match std::thread::panicking() {
+            true => Some(RunStatus::Failed(String::new())),
+            false => self.report.lock().unwrap().clone(),
+        };
+        let metric = match status {
+            Some(RunStatus::Failed(_)) => "failures",
+            Some(RunStatus::NoWork) => "no_work",
+            Some(RunStatus::PartialSuccess(_)) => "partial",
+            Some(RunStatus::Success) | None => return,
+        };
+        self.statsd.send(&self.job, metric, "1|c");
+    }
+}
+
+impl Layer for Statsd {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let _timing = Timing {
+                statsd: self,
+                job: next.context().name().to_string(),
+                start: Instant::now(),
+                report: next.context().reported(),
+            };
+            next.run().await;
+        })
+    }
+}
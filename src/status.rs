@@ -0,0 +1,319 @@
+use crate::backfill::Requests;
+use crate::event::Events;
+use crate::{EventKind, Job, JobOptions, Labels, RunStatus, Token, TriggerDecision};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Notify, watch};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JobStats {
+    pub runs: u64,
+    pub failures: u64,
+    pub no_work_runs: u64,
+    pub partial_runs: u64,
+    pub shadow_runs: u64,
+    pub interrupted_runs: u64,
+    pub deadline_misses: u64,
+    pub last_run: Option<SystemTime>,
+    pub last_success: Option<SystemTime>,
+    pub last_duration: Option<Duration>,
+    pub last_status: Option<RunStatus>,
+    pub next_run: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub name: String,
+    pub critical: bool,
+    pub enabled: bool,
+    pub alive: bool,
+    pub stalled: bool,
+    pub quarantined: bool,
+    pub labels: Labels,
+    pub stats: JobStats,
+}
+
+#[derive(Debug, Clone)]
+pub struct Status {
+    pub live: bool,
+    pub ready: bool,
+    pub jobs: Vec<JobStatus>,
+}
+
+#[derive(Clone, Copy)]
+enum Progress {
+    Waiting(Option<Instant>),
+    Busy(Instant),
+}
+
+impl Progress {
+    fn stalled(self, timeout: Duration) -> bool {
+        let since = match self {
+            Self::Waiting(due) => due,
+            Self::Busy(at) => Some(at),
+        };
+        since.is_some_and(|since| since.elapsed() > timeout)
+    }
+}
+
+pub(crate) struct JobShared {
+    pub(crate) name: Arc<str>,
+    pub(crate) critical: bool,
+    pub(crate) enabled: bool,
+    pub(crate) abortable: bool,
+    pub(crate) group: Mutex<Option<Arc<str>>>,
+    pub(crate) labels: Labels,
+    pub(crate) stats: Mutex<JobStats>,
+    pub(crate) options: watch::Sender<JobOptions>,
+    pub(crate) finished: watch::Sender<bool>,
+    pub(crate) quarantined: watch::Sender<bool>,
+    pub(crate) trigger: Notify,
+    pub(crate) pending: AtomicBool,
+    pub(crate) events: Events,
+    pub(crate) backfills: Requests,
+    pub(crate) run: Mutex<Option<Token>>,
+    progress: Mutex<Progress>,
+}
+
+impl JobShared {
+    pub(crate) fn new<J: Job>(job: &J, max_backfills: usize, events: Events) -> Self {
+        Self {
+            name: job.name().into(),
+            critical: job.critical(),
+            enabled: job.enabled(),
+            abortable: job.abortable(),
+            group: Mutex::new(job.exclusion_group().map(Arc::from)),
+            labels: job.labels(),
+            stats: Mutex::default(),
+            options: watch::Sender::default(),
+            finished: watch::Sender::new(false),
+            quarantined: watch::Sender::new(false),
+            trigger: Notify::new(),
+            pending: AtomicBool::new(false),
+            events,
+            backfills: Requests::new(max_backfills),
+            run: Mutex::default(),
+            progress: Mutex::new(Progress::Waiting(None)),
+        }
+    }
+
+    pub(crate) fn critical(&self) -> bool {
+        self.options.borrow().critical.unwrap_or(self.critical)
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.options.borrow().enabled.unwrap_or(self.enabled)
+    }
+
+    pub(crate) fn group(&self) -> Option<Arc<str>> {
+        let options = self.options.borrow();
+        options
+            .exclusion_group
+            .as_deref()
+            .map(Arc::from)
+            .or_else(|| self.group.lock().unwrap().clone())
+    }
+
+    pub(crate) fn set_group(&self, group: Option<&str>) {
+        *self.group.lock().unwrap() = group.map(Arc::from);
+    }
+
+    pub(crate) fn labels(&self) -> Labels {
+        let options = self.options.borrow();
+        options
+            .labels
+            .clone()
+            .unwrap_or_else(|| self.labels.clone())
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.options
+            .send_modify(|options| options.enabled = Some(enabled));
+    }
+
+    pub(crate) fn cancel_run(&self) -> bool {
+        let run = self.run.lock().unwrap();
+        if let Some(token) = run.as_ref() {
+            token.cancel();
+        }
+        run.is_some()
+    }
+
+    pub(crate) fn trigger_now(&self) -> TriggerDecision {
+        let decision = {
+            let run = self.run.lock().unwrap();
+            match (self.pending.swap(true, Ordering::AcqRel), run.is_some()) {
+                (true, _) => TriggerDecision::Coalesced,
+                (false, true) => TriggerDecision::Queued,
+                (false, false) => TriggerDecision::Accepted,
+            }
+        };
+        self.trigger.notify_one();
+        self.events
+            .emit(&self.name, EventKind::Triggered { decision });
+        decision
+    }
+
+    pub(crate) fn triggered(&self) {
+        self.pending.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn is_quarantined(&self) -> bool {
+        *self.quarantined.borrow()
+    }
+
+    pub(crate) fn quarantine(&self) {
+        self.quarantined.send_replace(true);
+    }
+
+    pub(crate) fn unquarantine(&self) -> bool {
+        self.quarantined.send_replace(false)
+    }
+
+    pub(crate) async fn released(&self) {
+        let _ = self
+            .quarantined
+            .subscribe()
+            .wait_for(|quarantined| !quarantined)
+            .await;
+    }
+
+    pub(crate) fn interrupted(&self) -> bool {
+        self.stats.lock().unwrap().interrupted_runs > 0
+    }
+
+    pub(crate) fn running(&self, run: Option<Token>) {
+        *self.progress.lock().unwrap() = match run {
+            Some(_) => Progress::Busy(Instant::now()),
+            None => Progress::Waiting(None),
+        };
+        *self.run.lock().unwrap() = run;
+    }
+
+    pub(crate) fn progressed(&self) {
+        *self.progress.lock().unwrap() = Progress::Busy(Instant::now());
+    }
+
+    pub(crate) fn waiting(&self, delay: Duration) {
+        let due = Instant::now().checked_add(delay);
+        *self.progress.lock().unwrap() = Progress::Waiting(due);
+    }
+
+    pub(crate) fn deadline_missed(&self) {
+        self.stats.lock().unwrap().deadline_misses += 1;
+    }
+
+    pub(crate) fn schedule(&self, next: Option<SystemTime>) {
+        self.stats.lock().unwrap().next_run = next;
+    }
+
+    pub(crate) fn record(
+        &self,
+        started: SystemTime,
+        duration: Duration,
+        status: &RunStatus,
+        shadow: bool,
+    ) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.runs += 1;
+        if shadow {
+            stats.shadow_runs += 1;
+        }
+        stats.last_run = Some(started);
+        stats.last_duration = Some(duration);
+        match status {
+            RunStatus::Failed(_) => stats.failures += 1,
+            RunStatus::NoWork => stats.no_work_runs += 1,
+            RunStatus::PartialSuccess(_) => stats.partial_runs += 1,
+            RunStatus::Success => {}
+        }
+        if !status.is_failure() {
+            stats.last_success = Some(started + duration);
+        }
+        stats.last_status = Some(status.clone());
+    }
+}
+
+pub(crate) struct Interruption<'a> {
+    shared: &'a JobShared,
+    events: &'a Events,
+    token: &'a Token,
+    finished: bool,
+}
+
+impl<'a> Interruption<'a> {
+    pub(crate) fn new(shared: &'a JobShared, events: &'a Events, token: &'a Token) -> Self {
+        Self {
+            shared,
+            events,
+            token,
+            finished: false,
+        }
+    }
+
+    pub(crate) fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for Interruption<'_> {
+    fn drop(&mut self) {
+        if !self.token.is_cancelled() {
+            return;
+        }
+        self.shared.stats.lock().unwrap().interrupted_runs += 1;
+        if !self.finished {
+            let kind = EventKind::Cancelled { interrupted: true };
+            self.events.emit(&self.shared.name, kind);
+        }
+    }
+}
+
+pub(crate) struct Finished(pub(crate) Arc<JobShared>);
+
+impl Drop for Finished {
+    fn drop(&mut self) {
+        self.0.backfills.close();
+        self.0.finished.send_replace(true);
+    }
+}
+
+pub(crate) struct Entry {
+    pub(crate) shared: Arc<JobShared>,
+    pub(crate) handle: JoinHandle<()>,
+}
+
+impl Entry {
+    pub(crate) fn status(&self, timeout: Duration) -> JobStatus {
+        let alive = !self.handle.is_finished();
+        JobStatus {
+            name: self.shared.name.to_string(),
+            critical: self.shared.critical(),
+            enabled: self.shared.enabled(),
+            alive,
+            stalled: alive && self.shared.progress.lock().unwrap().stalled(timeout),
+            quarantined: self.shared.is_quarantined(),
+            labels: self.shared.labels(),
+            stats: self.shared.stats.lock().unwrap().clone(),
+        }
+    }
+}
+
+impl Status {
+    pub(crate) fn new(cancelled: bool, started: bool, jobs: Vec<JobStatus>) -> Self {
+        Self {
+            live: !cancelled && jobs.iter().all(|job| job.alive && !job.stalled),
+            ready: started
+                && jobs
+                    .iter()
+                    .filter(|job| job.critical && job.enabled)
+                    .all(|job| job.stats.last_success.is_some()),
+            jobs,
+        }
+    }
+}
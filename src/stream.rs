@@ -0,0 +1,160 @@
+use crate::{
+    AlertRule, Budget, Completion, DstPolicy, Job, Labels, Layer, LoadPolicy, LogLevel, Misfire,
+    PeriodLimit, Quarantine, RunContext, Sampling, Schedule, Supervisor, SuspendPolicy,
+    TriggerPolicy, TruncateZone,
+};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+struct Never;
+
+impl Schedule for Never {
+    fn next_after(&self, _after: SystemTime) -> Option<SystemTime> {
+        None
+    }
+
+    fn describe(&self) -> String {
+        "on stream items".into()
+    }
+}
+
+pub(crate) struct Streamed<J> {
+    job: J,
+    min_interval: Duration,
+}
+
+impl<J: Job> Streamed<J> {
+    pub(crate) fn new(job: J, min_interval: Duration) -> Self {
+        Self { job, min_interval }
+    }
+}
+
+impl<J: Job> Job for Streamed<J> {
+    fn name(&self) -> &str {
+        self.job.name()
+    }
+
+    fn period(&self) -> Duration {
+        self.job.period()
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.job.with_truncate_time()
+    }
+
+    fn truncate_zone(&self) -> TruncateZone {
+        self.job.truncate_zone()
+    }
+
+    fn dst_policy(&self) -> DstPolicy {
+        self.job.dst_policy()
+    }
+
+    fn run_with_context(&mut self, ctx: RunContext) -> impl Future<Output = ()> + Send {
+        self.job.run_with_context(ctx)
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.job.supervisor()
+    }
+
+    fn layers(&self) -> Vec<Arc<dyn Layer>> {
+        self.job.layers()
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        self.job.exclusion_group()
+    }
+
+    fn shard_key(&self) -> Option<&str> {
+        self.job.shard_key()
+    }
+
+    fn alert_rule(&self) -> Option<AlertRule> {
+        self.job.alert_rule()
+    }
+
+    fn critical(&self) -> bool {
+        self.job.critical()
+    }
+
+    fn labels(&self) -> Labels {
+        self.job.labels()
+    }
+
+    fn enabled(&self) -> bool {
+        self.job.enabled()
+    }
+
+    fn abortable(&self) -> bool {
+        self.job.abortable()
+    }
+
+    fn trigger_policy(&self) -> TriggerPolicy {
+        self.job.trigger_policy().throttle(self.min_interval)
+    }
+
+    fn log_level(&self) -> LogLevel {
+        self.job.log_level()
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        Some(Arc::new(Never))
+    }
+
+    fn dry_run(&self) -> bool {
+        self.job.dry_run()
+    }
+
+    fn stagger(&self) -> bool {
+        self.job.stagger()
+    }
+
+    fn finish_by(&self) -> bool {
+        self.job.finish_by()
+    }
+
+    fn sampling(&self) -> Sampling {
+        self.job.sampling()
+    }
+
+    fn max_duty_cycle(&self) -> Option<f64> {
+        self.job.max_duty_cycle()
+    }
+
+    fn period_limit(&self) -> Option<PeriodLimit> {
+        self.job.period_limit()
+    }
+
+    fn quarantine(&self) -> Option<Quarantine> {
+        self.job.quarantine()
+    }
+
+    fn budget(&self) -> Option<Budget> {
+        self.job.budget()
+    }
+
+    fn completion(&self) -> Option<Completion> {
+        self.job.completion()
+    }
+
+    fn on_suspend(&self) -> SuspendPolicy {
+        self.job.on_suspend()
+    }
+
+    fn misfire(&self) -> Misfire {
+        self.job.misfire()
+    }
+
+    fn load_policy(&self) -> LoadPolicy {
+        self.job.load_policy()
+    }
+
+    fn durable(&self) -> bool {
+        self.job.durable()
+    }
+
+    fn dedup(&self) -> bool {
+        self.job.dedup()
+    }
+}
@@ -0,0 +1,151 @@
+use crate::Token;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::select;
+use tokio::time::{Instant, sleep};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Escalation {
+    Job,
+    Group,
+    Scheduler,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Supervisor {
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    backoff: Duration,
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    max_backoff: Duration,
+    intensity: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::period::duration"))]
+    window: Duration,
+    escalation: Escalation,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self {
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            intensity: 3,
+            window: Duration::from_secs(5),
+            escalation: Escalation::Job,
+        }
+    }
+
+    pub fn backoff(mut self, backoff: Duration, max_backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = max_backoff.max(backoff);
+        self
+    }
+
+    pub fn intensity(mut self, restarts: usize, window: Duration) -> Self {
+        self.intensity = restarts;
+        self.window = window;
+        self
+    }
+
+    pub fn escalate(mut self, escalation: Escalation) -> Self {
+        self.escalation = escalation;
+        self
+    }
+}
+
+pub(crate) struct Restarts {
+    supervisor: Option<Supervisor>,
+    history: VecDeque<Instant>,
+    backoff: Duration,
+}
+
+impl Restarts {
+    pub(crate) fn new(supervisor: Option<Supervisor>) -> Self {
+        Self {
+            backoff: supervisor.map(|s| s.backoff).unwrap_or_default(),
+            supervisor,
+            history: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn succeeded(&mut self) {
+        self.backoff = self.supervisor.map(|s| s.backoff).unwrap_or_default();
+    }
+
+    pub(crate) async fn failed(&mut self, token: &Token) {
+        let supervisor = self.supervisor.unwrap_or_default();
+        let backoff = self.backoff.max(supervisor.backoff);
+        self.backoff = (backoff * 2).min(supervisor.max_backoff);
+        select! {
+            _ = token.cancelled() => {},
+            _ = sleep(backoff) => {},
+        }
+    }
+
+    pub(crate) async fn panicked(
+        &mut self,
+        panic: Box<dyn Any + Send>,
+        token: &Token,
+        root: &Token,
+    ) {
+        let Some(supervisor) = self.supervisor else {
+            resume_unwind(panic);
+        };
+        let now = Instant::now();
+        while self
+            .history
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > supervisor.window)
+        {
+            self.history.pop_front();
+        }
+        if self.history.len() >= supervisor.intensity {
+            match supervisor.escalation {
+                Escalation::Job => {}
+                Escalation::Group => token.cancel(),
+                Escalation::Scheduler => root.cancel(),
+            }
+            resume_unwind(panic);
+        }
+        self.history.push_back(now);
+        let backoff = self.backoff;
+        self.backoff = (backoff * 2).min(supervisor.max_backoff);
+        select! {
+            _ = token.cancelled() => {},
+            _ = sleep(backoff) => {},
+        }
+    }
+}
+
+pub(crate) struct CatchUnwind<F>(F);
+
+impl<F: Future + Unpin> CatchUnwind<F> {
+    pub(crate) fn new(future: F) -> Self {
+        Self(future)
+    }
+}
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match catch_unwind(AssertUnwindSafe(|| Pin::new(&mut self.0).poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
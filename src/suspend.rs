@@ -0,0 +1,23 @@
+use std::time::{Duration, SystemTime};
+
+const TOLERANCE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SuspendPolicy {
+    #[default]
+    RunNow,
+    Realign,
+    Skip,
+}
+
+pub(crate) fn suspended(
+    since: SystemTime,
+    now: SystemTime,
+    expected: Duration,
+) -> Option<Duration> {
+    let slept = now.duration_since(since).ok()?;
+    let tolerance = TOLERANCE.saturating_add(expected / 100);
+    (slept > expected.saturating_add(tolerance)).then_some(slept)
+}
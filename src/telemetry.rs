@@ -0,0 +1,151 @@
+use crate::{Labels, RunStatus};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LogLevel {
+    Off,
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! with_level {
+    ($level:expr, |$const:ident| $body:expr) => {
+        match $level {
+            LogLevel::Off => None,
+            LogLevel::Trace => {
+                const $const: tracing::Level = tracing::Level::TRACE;
+                Some($body)
+            }
+            LogLevel::Debug => {
+                const $const: tracing::Level = tracing::Level::DEBUG;
+                Some($body)
+            }
+            LogLevel::Info => {
+                const $const: tracing::Level = tracing::Level::INFO;
+                Some($body)
+            }
+            LogLevel::Warn => {
+                const $const: tracing::Level = tracing::Level::WARN;
+                Some($body)
+            }
+            LogLevel::Error => {
+                const $const: tracing::Level = tracing::Level::ERROR;
+                Some($body)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "tracing")]
+impl LogLevel {
+    pub(crate) fn job_span(self, name: &str) -> tracing::Span {
+        with_level!(self, |LEVEL| tracing::span!(LEVEL, "job", name))
+            .unwrap_or_else(tracing::Span::none)
+    }
+
+    pub(crate) fn run_span(self, run_id: u64, shadow: bool) -> tracing::Span {
+        with_level!(self, |LEVEL| tracing::span!(LEVEL, "run", run_id, shadow))
+            .unwrap_or_else(tracing::Span::none)
+    }
+
+    pub(crate) fn run_finished(
+        self,
+        span: &tracing::Span,
+        duration: Duration,
+        panicked: bool,
+        status: &RunStatus,
+    ) {
+        if panicked && self != Self::Off {
+            tracing::error!(parent: span, ?duration, "run panicked");
+        } else if let RunStatus::Failed(error) = status
+            && self != Self::Off
+        {
+            tracing::error!(parent: span, ?duration, error, "run failed");
+        } else if !panicked {
+            let status = status.label();
+            with_level!(self, |LEVEL| {
+                tracing::event!(parent: span, LEVEL, ?duration, status, "run finished")
+            });
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn labels(job: &str, labels: &Labels) -> Vec<metrics::Label> {
+    let job = metrics::Label::new("job", job.to_string());
+    std::iter::once(job)
+        .chain(
+            labels
+                .iter()
+                .map(|(key, value)| metrics::Label::new(key.clone(), value.clone())),
+        )
+        .collect()
+}
+
+pub(crate) struct Active(
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))] String,
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))] Labels,
+);
+
+impl Active {
+    pub(crate) fn new(job: &str, job_labels: &Labels) -> Self {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!("periodic_job_active", labels(job, job_labels)).increment(1);
+        Self(job.to_string(), job_labels.clone())
+    }
+}
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!("periodic_job_active", labels(&self.0, &self.1)).decrement(1);
+    }
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn run_finished(
+    job: &str,
+    job_labels: &Labels,
+    duration: Duration,
+    status: &RunStatus,
+    shadow: bool,
+) {
+    #[cfg(feature = "metrics")]
+    {
+        let labels = labels(job, job_labels);
+        metrics::counter!("periodic_job_runs_total", labels.clone()).increment(1);
+        if shadow {
+            metrics::counter!("periodic_job_shadow_runs_total", labels.clone()).increment(1);
+        }
+        if status.is_failure() {
+            metrics::counter!("periodic_job_failures_total", labels.clone()).increment(1);
+        }
+        let mut outcome = labels.clone();
+        outcome.push(metrics::Label::new("status", status.label()));
+        metrics::counter!("periodic_job_outcomes_total", outcome).increment(1);
+        metrics::histogram!("periodic_job_run_duration_seconds", labels).record(duration);
+    }
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn deadline_missed(job: &str, job_labels: &Labels) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "periodic_job_deadline_misses_total",
+        labels(job, job_labels)
+    )
+    .increment(1);
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn restarted(job: &str, job_labels: &Labels) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("periodic_job_restarts_total", labels(job, job_labels)).increment(1);
+}
@@ -0,0 +1,126 @@
+use crate::{Configured, Job, JobHandle, JobOptions, Scheduler};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+pub(crate) type Templates = Arc<Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>>;
+
+type Instances<P, J> = Mutex<HashMap<P, JobHandle<Configured<J>>>>;
+
+pub(crate) struct Registered<P, J> {
+    name: Arc<str>,
+    create: Box<dyn Fn(&P) -> J + Send + Sync>,
+    instances: Instances<P, J>,
+}
+
+impl<P, J> Registered<P, J> {
+    pub(crate) fn new(name: &str, create: impl Fn(&P) -> J + Send + Sync + 'static) -> Self {
+        Self {
+            name: name.into(),
+            create: Box::new(create),
+            instances: Mutex::default(),
+        }
+    }
+}
+
+pub struct Template<P, J> {
+    registered: Arc<Registered<P, J>>,
+    scheduler: Scheduler,
+}
+
+impl<P, J> Clone for Template<P, J> {
+    fn clone(&self) -> Self {
+        Self {
+            registered: self.registered.clone(),
+            scheduler: self.scheduler.share(),
+        }
+    }
+}
+
+impl<P, J> Template<P, J>
+where
+    P: Clone + Eq + Hash + Display + Send + 'static,
+    J: Job,
+{
+    pub(crate) fn new(registered: Arc<Registered<P, J>>, scheduler: Scheduler) -> Self {
+        Self {
+            registered,
+            scheduler,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.registered.name
+    }
+
+    pub fn spawn(&self, param: P) -> JobHandle<Configured<J>> {
+        let mut instances = self.registered.instances.lock().unwrap();
+        if let Some(handle) = instances.get(&param) {
+            return handle.clone();
+        }
+        let options = JobOptions {
+            name: Some(format!("{}/{param}", self.registered.name)),
+            ..JobOptions::default()
+        };
+        let job = (self.registered.create)(&param).configure(options);
+        let handle = self.scheduler.spawn_with_handle(job);
+        instances.insert(param, handle.clone());
+        handle
+    }
+
+    pub fn get(&self, param: &P) -> Option<JobHandle<Configured<J>>> {
+        self.registered
+            .instances
+            .lock()
+            .unwrap()
+            .get(param)
+            .cloned()
+    }
+
+    pub fn params(&self) -> Vec<P> {
+        self.registered
+            .instances
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    pub fn handles(&self) -> Vec<JobHandle<Configured<J>>> {
+        self.registered
+            .instances
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    pub fn enable_all(&self) {
+        self.handles().iter().for_each(JobHandle::enable);
+    }
+
+    pub fn disable_all(&self) {
+        self.handles().iter().for_each(JobHandle::disable);
+    }
+
+    pub fn trigger_all(&self) {
+        self.handles().iter().for_each(JobHandle::trigger);
+    }
+
+    pub fn cancel_runs(&self) -> usize {
+        self.handles()
+            .iter()
+            .filter(|handle| handle.cancel_run())
+            .count()
+    }
+
+    pub fn configure_all(&self, options: JobOptions) {
+        for handle in self.handles() {
+            handle.configure(options.clone());
+        }
+    }
+}
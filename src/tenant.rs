@@ -0,0 +1,49 @@
+use crate::{Layer, Next, RateLimit, Run};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::select;
+use tokio::sync::Semaphore;
+
+#[derive(Clone, Default)]
+pub struct Quota {
+    pub(crate) concurrency: Option<usize>,
+    pub(crate) rate_limit: Option<RateLimit>,
+}
+
+impl Quota {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn concurrency(mut self, runs: usize) -> Self {
+        self.concurrency = Some(runs);
+        self
+    }
+
+    pub fn rate_limit(mut self, runs: u32, per: Duration) -> Self {
+        self.rate_limit = Some(RateLimit::new(runs, per));
+        self
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Concurrency(Arc<Semaphore>);
+
+impl Concurrency {
+    pub(crate) fn new(runs: usize) -> Self {
+        Self(Arc::new(Semaphore::new(runs.max(1))))
+    }
+}
+
+impl Layer for Concurrency {
+    fn call<'a>(&'a self, next: Next<'a>) -> Run<'a> {
+        Box::pin(async move {
+            let token = next.context().token();
+            select! {
+                biased;
+                _ = token.cancelled() => next.context().skip(),
+                _permit = self.0.acquire() => next.run().await,
+            }
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use std::io;
+
+#[cfg(unix)]
+pub(crate) async fn signalled() -> io::Result<()> {
+    use tokio::select;
+    use tokio::signal::unix::{SignalKind, signal};
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    select! {
+        _ = terminate.recv() => {},
+        _ = interrupt.recv() => {},
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn signalled() -> io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
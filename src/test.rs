@@ -0,0 +1,270 @@
+use crate::{Fire, Job, RunContext, Schedule, Scheduler, Simulation, StopReport, Supervisor};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+
+#[derive(Debug, Clone, Default)]
+pub struct MockCounters(Arc<Counts>);
+
+#[derive(Debug, Default)]
+struct Counts {
+    runs: AtomicU64,
+    completed: AtomicU64,
+    panics: AtomicU64,
+    aborted: AtomicU64,
+    running: AtomicU64,
+    max_running: AtomicU64,
+    ticks: Mutex<Vec<SystemTime>>,
+}
+
+impl MockCounters {
+    pub fn runs(&self) -> u64 {
+        self.0.runs.load(Ordering::SeqCst)
+    }
+
+    pub fn completed(&self) -> u64 {
+        self.0.completed.load(Ordering::SeqCst)
+    }
+
+    pub fn panics(&self) -> u64 {
+        self.0.panics.load(Ordering::SeqCst)
+    }
+
+    pub fn aborted(&self) -> u64 {
+        self.0.aborted.load(Ordering::SeqCst)
+    }
+
+    pub fn running(&self) -> u64 {
+        self.0.running.load(Ordering::SeqCst)
+    }
+
+    pub fn max_running(&self) -> u64 {
+        self.0.max_running.load(Ordering::SeqCst)
+    }
+
+    pub fn ticks(&self) -> Vec<SystemTime> {
+        self.0.ticks.lock().unwrap().clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct MockJob {
+    name: String,
+    period: Duration,
+    truncate: bool,
+    schedule: Option<Arc<dyn Schedule>>,
+    supervisor: Option<Supervisor>,
+    group: Option<String>,
+    abortable: bool,
+    durations: Vec<Duration>,
+    panics: BTreeSet<u64>,
+    counters: MockCounters,
+}
+
+impl MockJob {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            period: Duration::from_secs(1),
+            truncate: false,
+            schedule: None,
+            supervisor: None,
+            group: None,
+            abortable: false,
+            durations: Vec::new(),
+            panics: BTreeSet::new(),
+            counters: MockCounters::default(),
+        }
+    }
+
+    pub fn every(mut self, period: Duration) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn truncated(mut self) -> Self {
+        self.truncate = true;
+        self
+    }
+
+    pub fn on_schedule(mut self, schedule: impl Schedule) -> Self {
+        self.schedule = Some(Arc::new(schedule));
+        self
+    }
+
+    pub fn supervised(mut self, supervisor: Supervisor) -> Self {
+        self.supervisor = Some(supervisor);
+        self
+    }
+
+    pub fn in_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn abortable(mut self) -> Self {
+        self.abortable = true;
+        self
+    }
+
+    pub fn taking(mut self, duration: Duration) -> Self {
+        self.durations = vec![duration];
+        self
+    }
+
+    pub fn taking_each(mut self, durations: impl IntoIterator<Item = Duration>) -> Self {
+        self.durations = durations.into_iter().collect();
+        self
+    }
+
+    pub fn panicking_on(mut self, runs: impl IntoIterator<Item = u64>) -> Self {
+        self.panics.extend(runs);
+        self
+    }
+
+    pub fn sharing(mut self, counters: &MockCounters) -> Self {
+        self.counters = counters.clone();
+        self
+    }
+
+    pub fn counters(&self) -> MockCounters {
+        self.counters.clone()
+    }
+}
+
+impl Job for MockJob {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn period(&self) -> Duration {
+        self.period
+    }
+
+    fn with_truncate_time(&self) -> bool {
+        self.truncate
+    }
+
+    fn schedule(&self) -> Option<Arc<dyn Schedule>> {
+        self.schedule.clone()
+    }
+
+    fn supervisor(&self) -> Option<Supervisor> {
+        self.supervisor
+    }
+
+    fn exclusion_group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    fn abortable(&self) -> bool {
+        self.abortable
+    }
+
+    async fn run_with_context(&mut self, ctx: RunContext) {
+        let counts = &self.counters.0;
+        let run = counts.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        counts.ticks.lock().unwrap().push(ctx.tick());
+        let running = counts.running.fetch_add(1, Ordering::SeqCst) + 1;
+        counts.max_running.fetch_max(running, Ordering::SeqCst);
+        let mut guard = Running {
+            counts,
+            finished: false,
+        };
+        let index = (run as usize - 1).min(self.durations.len().saturating_sub(1));
+        if let Some(duration) = self.durations.get(index) {
+            sleep(*duration).await;
+        }
+        if self.panics.contains(&run) {
+            counts.panics.fetch_add(1, Ordering::SeqCst);
+            guard.finished = true;
+            panic!("mock job `{}` panicked on run {run}", self.name);
+        }
+        counts.completed.fetch_add(1, Ordering::SeqCst);
+        guard.finished = true;
+    }
+}
+
+struct Running<'a> {
+    counts: &'a Counts,
+    finished: bool,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        self.counts.running.fetch_sub(1, Ordering::SeqCst);
+        if !self.finished {
+            self.counts.aborted.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+pub struct Scenario {
+    scheduler: Scheduler,
+    start: SystemTime,
+    jobs: Vec<MockJob>,
+}
+
+#[derive(Debug)]
+pub struct ScenarioReport {
+    pub stop: StopReport,
+    pub fires: Vec<Fire>,
+    pub jobs: HashMap<String, MockCounters>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::with_scheduler(Scheduler::new())
+    }
+
+    pub fn with_scheduler(scheduler: Scheduler) -> Self {
+        Self {
+            scheduler,
+            start: UNIX_EPOCH,
+            jobs: Vec::new(),
+        }
+    }
+
+    pub fn starting_at(mut self, start: SystemTime) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn job(mut self, job: MockJob) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    pub async fn run_for(self, duration: Duration) -> ScenarioReport {
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|job| (job.name.clone(), job.counters()))
+            .collect();
+        let simulation = Simulation::from_scheduler(self.start, self.scheduler);
+        for job in self.jobs {
+            simulation.spawn(job);
+        }
+        simulation.advance(duration).await;
+        let fires = simulation.fires();
+        ScenarioReport {
+            stop: simulation.stop().await,
+            fires,
+            jobs,
+        }
+    }
+}
+
+impl ScenarioReport {
+    pub fn counters(&self, job: &str) -> &MockCounters {
+        &self.jobs[job]
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Store;
+use std::io;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+pub(crate) struct TickStore {
+    store: Arc<dyn Store>,
+    key: String,
+}
+
+impl TickStore {
+    pub(crate) fn new(store: Arc<dyn Store>, key: String) -> Self {
+        Self { store, key }
+    }
+
+    pub(crate) async fn load(&self) -> Option<SystemTime> {
+        let result = self.store.load(&self.key).await.and_then(|bytes| {
+            let Some(bytes) = bytes else {
+                return Ok(None);
+            };
+            let millis = std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|millis| millis.trim().parse().ok())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid tick"))?;
+            Ok(Some(SystemTime::UNIX_EPOCH + Duration::from_millis(millis)))
+        });
+        self.log(&result, "failed to load tick");
+        result.ok().flatten()
+    }
+
+    pub(crate) async fn save(&self, tick: SystemTime) {
+        let millis = tick
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let result = self
+            .store
+            .save(&self.key, millis.to_string().into_bytes())
+            .await;
+        self.log(&result, "failed to save tick");
+    }
+
+    pub(crate) async fn remove(&self) {
+        let result = self.store.remove(&self.key).await;
+        self.log(&result, "failed to remove tick");
+    }
+
+    fn log<T>(&self, _result: &io::Result<T>, _message: &str) {
+        #[cfg(feature = "tracing")]
+        if let Err(error) = _result {
+            tracing::warn!(key = self.key, %error, "{_message}");
+        }
+    }
+}
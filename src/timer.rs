@@ -0,0 +1,192 @@
+use crate::Token;
+use std::collections::BTreeMap;
+use std::future::pending;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::select;
+use tokio::sync::{Notify, oneshot};
+use tokio::time::{Instant, sleep, sleep_until};
+use tokio_util::task::TaskTracker;
+
+const AUTO_JOBS: usize = 1000;
+const AUTO_MIN_DELAY: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimerMode {
+    #[default]
+    Auto,
+    PerJob,
+    Shared,
+}
+
+#[derive(Clone)]
+pub(crate) struct Timers {
+    mode: TimerMode,
+    jobs: Arc<AtomicUsize>,
+    queue: Arc<OnceLock<Arc<Queue>>>,
+    tracker: TaskTracker,
+    token: Token,
+}
+
+impl Timers {
+    pub(crate) fn new(mode: TimerMode, tracker: TaskTracker, token: Token) -> Self {
+        Self {
+            mode,
+            jobs: Arc::default(),
+            queue: Arc::default(),
+            tracker,
+            token,
+        }
+    }
+
+    pub(crate) fn start(&self, runtime: &Handle) {
+        if self.mode == TimerMode::PerJob {
+            return;
+        }
+        self.queue.get_or_init(|| {
+            let queue = Arc::new(Queue::new());
+            let driver = drive(
+                Arc::downgrade(&queue),
+                queue.wake.clone(),
+                self.token.clone(),
+            );
+            self.tracker.spawn_on(driver, runtime);
+            queue
+        });
+    }
+
+    pub(crate) fn enroll(&self) -> Enrolled {
+        self.jobs.fetch_add(1, Ordering::Relaxed);
+        Enrolled(self.jobs.clone())
+    }
+
+    pub(crate) async fn sleep(&self, delay: Duration) {
+        let shared = match self.mode {
+            TimerMode::PerJob => false,
+            TimerMode::Shared => true,
+            TimerMode::Auto => {
+                delay >= AUTO_MIN_DELAY && self.jobs.load(Ordering::Relaxed) >= AUTO_JOBS
+            }
+        };
+        let deadline = Instant::now().checked_add(delay);
+        match (shared, self.queue.get(), deadline) {
+            (true, Some(queue), Some(deadline)) => queue.sleep_until(deadline).await,
+            _ => sleep(delay).await,
+        }
+    }
+}
+
+pub(crate) struct Enrolled(Arc<AtomicUsize>);
+
+impl Drop for Enrolled {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+type Bucket = Vec<(u64, oneshot::Sender<()>)>;
+
+struct Queue {
+    base: Instant,
+    buckets: Mutex<BTreeMap<u64, Bucket>>,
+    next_id: AtomicU64,
+    wake: Arc<Notify>,
+}
+
+impl Queue {
+    fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            buckets: Mutex::default(),
+            next_id: AtomicU64::new(0),
+            wake: Arc::default(),
+        }
+    }
+
+    fn instant(&self, tick: u64) -> Instant {
+        self.base + Duration::from_millis(tick)
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        let since = deadline.saturating_duration_since(self.base);
+        let tick = since.as_nanos().div_ceil(1_000_000) as u64;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = oneshot::channel();
+        {
+            let mut buckets = self.buckets.lock().unwrap();
+            buckets.entry(tick).or_default().push((id, sender));
+            if buckets
+                .first_key_value()
+                .is_some_and(|(first, _)| *first == tick)
+            {
+                self.wake.notify_one();
+            }
+        }
+        let _entry = Entry {
+            queue: self,
+            tick,
+            id,
+        };
+        let _ = receiver.await;
+    }
+}
+
+impl Drop for Queue {
+    fn drop(&mut self) {
+        self.wake.notify_one();
+    }
+}
+
+struct Entry<'a> {
+    queue: &'a Queue,
+    tick: u64,
+    id: u64,
+}
+
+impl Drop for Entry<'_> {
+    fn drop(&mut self) {
+        let mut buckets = self.queue.buckets.lock().unwrap();
+        if let Some(bucket) = buckets.get_mut(&self.tick) {
+            bucket.retain(|(id, _)| *id != self.id);
+            if bucket.is_empty() {
+                buckets.remove(&self.tick);
+            }
+        }
+    }
+}
+
+async fn drive(queue: Weak<Queue>, wake: Arc<Notify>, token: Token) {
+    loop {
+        let next = {
+            let Some(queue) = queue.upgrade() else {
+                return;
+            };
+            let now = Instant::now();
+            let mut buckets = queue.buckets.lock().unwrap();
+            while let Some(due) = buckets
+                .first_entry()
+                .filter(|due| queue.instant(*due.key()) <= now)
+            {
+                for (_, sender) in due.remove() {
+                    let _ = sender.send(());
+                }
+            }
+            buckets
+                .first_key_value()
+                .map(|(tick, _)| queue.instant(*tick))
+        };
+        let next = async {
+            match next {
+                Some(next) => sleep_until(next).await,
+                None => pending().await,
+            }
+        };
+        select! {
+            _ = token.cancelled() => return,
+            _ = next => {},
+            _ = wake.notified() => {},
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::timer::{Enrolled, Timers};
+use std::pin::pin;
+use std::time::Duration;
+use tokio::select;
+use tokio::sync::Notify;
+use tokio::task::yield_now;
+use tokio::time::{Instant, sleep, sleep_until};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TriggerPolicy {
+    debounce: Option<Duration>,
+    throttle: Option<Duration>,
+}
+
+impl TriggerPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn debounce(mut self, quiet: Duration) -> Self {
+        self.debounce = Some(quiet);
+        self
+    }
+
+    pub fn throttle(mut self, window: Duration) -> Self {
+        self.throttle = Some(window);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerDecision {
+    Accepted,
+    Queued,
+    Coalesced,
+}
+
+pub(crate) struct Triggers {
+    policy: TriggerPolicy,
+    last: Option<Instant>,
+    timers: Timers,
+    _enrolled: Enrolled,
+}
+
+impl Triggers {
+    pub(crate) fn new(policy: TriggerPolicy, timers: Timers) -> Self {
+        Self {
+            policy,
+            last: None,
+            _enrolled: timers.enroll(),
+            timers,
+        }
+    }
+
+    pub(crate) async fn wait(&mut self, notify: &Notify, delay: Duration) -> bool {
+        if delay.is_zero() {
+            yield_now().await;
+            return false;
+        }
+        let mut tick = pin!(self.timers.sleep(delay));
+        select! {
+            _ = &mut tick => return false,
+            _ = notify.notified() => {}
+        }
+        if let Some(quiet) = self.policy.debounce {
+            loop {
+                select! {
+                    _ = &mut tick => return false,
+                    _ = notify.notified() => {}
+                    _ = sleep(quiet) => break,
+                }
+            }
+        }
+        if let Some(window) = self.policy.throttle
+            && let Some(last) = self.last
+        {
+            let mut open = pin!(sleep_until(last + window));
+            loop {
+                select! {
+                    _ = &mut tick => return false,
+                    _ = &mut open => break,
+                    _ = notify.notified() => {}
+                }
+            }
+        }
+        self.last = Some(Instant::now());
+        true
+    }
+}
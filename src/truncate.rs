@@ -0,0 +1,242 @@
+use chrono::{DateTime, Local, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use std::time::{Duration, SystemTime};
+
+const DAY: i64 = 86_400_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TruncateZone {
+    #[default]
+    Utc,
+    Local,
+    #[cfg(feature = "tz")]
+    Tz(chrono_tz::Tz),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DstGap {
+    Skip,
+    #[default]
+    NextValid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DstOverlap {
+    #[default]
+    Once,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DstPolicy {
+    gap: DstGap,
+    overlap: DstOverlap,
+}
+
+impl DstPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn gap(mut self, gap: DstGap) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn overlap(mut self, overlap: DstOverlap) -> Self {
+        self.overlap = overlap;
+        self
+    }
+}
+
+impl TruncateZone {
+    pub fn next_tick(
+        self,
+        period: Duration,
+        after: SystemTime,
+        dst: DstPolicy,
+    ) -> Option<SystemTime> {
+        self.next_boundary(period, Duration::ZERO, after, dst)
+    }
+
+    pub(crate) fn next_boundary(
+        self,
+        period: Duration,
+        offset: Duration,
+        after: SystemTime,
+        dst: DstPolicy,
+    ) -> Option<SystemTime> {
+        let period = i64::try_from(period.as_nanos()).ok()?.max(1);
+        let offset = i64::try_from(offset.as_nanos()).ok()? % period;
+        let after = after.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+        let after = i64::try_from(after.as_nanos()).ok()?;
+        let next = match self {
+            Self::Utc => next_tick(&Utc, period, offset, after, dst),
+            Self::Local => next_tick(&Local, period, offset, after, dst),
+            #[cfg(feature = "tz")]
+            Self::Tz(tz) => next_tick(&tz, period, offset, after, dst),
+        }?;
+        Some(SystemTime::UNIX_EPOCH + Duration::from_nanos(next as u64))
+    }
+
+    pub(crate) fn local(self, at: SystemTime) -> NaiveDateTime {
+        let at = DateTime::<Utc>::from(at).naive_utc();
+        match self {
+            Self::Utc => at,
+            Self::Local => Local.from_utc_datetime(&at).naive_local(),
+            #[cfg(feature = "tz")]
+            Self::Tz(tz) => tz.from_utc_datetime(&at).naive_local(),
+        }
+    }
+
+    pub(crate) fn transition_near(self, at: SystemTime) -> Duration {
+        let at = at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let Ok(at) = i64::try_from(at.as_nanos()) else {
+            return Duration::ZERO;
+        };
+        let transition = match self {
+            Self::Utc => 0,
+            Self::Local => transition(&Local, at),
+            #[cfg(feature = "tz")]
+            Self::Tz(tz) => transition(&tz, at),
+        };
+        Duration::from_nanos(transition as u64)
+    }
+
+    pub(crate) fn resolve_local(self, naive: NaiveDateTime) -> SystemTime {
+        match self {
+            Self::Utc => resolve_local(&Utc, naive),
+            Self::Local => resolve_local(&Local, naive),
+            #[cfg(feature = "tz")]
+            Self::Tz(tz) => resolve_local(&tz, naive),
+        }
+    }
+}
+
+pub(crate) fn truncate_period(
+    now: SystemTime,
+    period: Duration,
+    offset: Duration,
+    zone: TruncateZone,
+    dst: DstPolicy,
+) -> Option<Duration> {
+    let next = zone.next_boundary(period, offset, now, dst)?;
+    Some(next.duration_since(now).unwrap_or_default())
+}
+
+fn next_tick<Tz: TimeZone>(
+    tz: &Tz,
+    period: i64,
+    shift: i64,
+    after: i64,
+    dst: DstPolicy,
+) -> Option<i64> {
+    let boundary_after = |local: i64| {
+        let start = local.checked_sub(shift)?.div_euclid(period);
+        start
+            .checked_mul(period)?
+            .checked_add(shift)?
+            .checked_add(period)
+    };
+    let here = offset(tz, after);
+    let mut boundaries = vec![boundary_after(after.saturating_add(here))];
+    for (start, end) in [
+        (after.saturating_sub(DAY), after),
+        (after, after.saturating_add(DAY)),
+    ] {
+        let (before, since) = (offset(tz, start), offset(tz, end));
+        if before != since {
+            let at = transition_at(tz, start, end);
+            boundaries.extend([
+                boundary_after(after.saturating_add(before)),
+                boundary_after(after.saturating_add(since)),
+                boundary_after(at.saturating_add(before.min(since) - 1)),
+                boundary_after(at.saturating_add(before.max(since) - 1)),
+            ]);
+        }
+    }
+    boundaries
+        .into_iter()
+        .flatten()
+        .filter_map(|boundary| resolve_boundary(tz, boundary, after, dst))
+        .min()
+        .or_else(|| boundary_after(after.saturating_add(here))?.checked_sub(here))
+}
+
+fn resolve_boundary<Tz: TimeZone>(
+    tz: &Tz,
+    boundary: i64,
+    after: i64,
+    dst: DstPolicy,
+) -> Option<i64> {
+    let naive = DateTime::from_timestamp_nanos(boundary).naive_utc();
+    let ticks = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(tick) => [Some(tick), None],
+        LocalResult::Ambiguous(first, second) => match dst.overlap {
+            DstOverlap::Once => [Some(first), None],
+            DstOverlap::Both => [Some(first), Some(second)],
+        },
+        LocalResult::None => match dst.gap {
+            DstGap::Skip => [None, None],
+            DstGap::NextValid => [Some(after_gap(tz, naive)), None],
+        },
+    };
+    ticks
+        .into_iter()
+        .flatten()
+        .filter_map(|tick| tick.timestamp_nanos_opt())
+        .filter(|&tick| tick > after)
+        .min()
+}
+
+fn transition_at<Tz: TimeZone>(tz: &Tz, start: i64, end: i64) -> i64 {
+    const SECOND: i64 = 1_000_000_000;
+    let before = offset(tz, start);
+    let (mut low, mut high) = (start.div_euclid(SECOND), end.div_euclid(SECOND) + 1);
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        match offset(tz, middle * SECOND) == before {
+            true => low = middle,
+            false => high = middle,
+        }
+    }
+    high * SECOND
+}
+
+fn resolve_local<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> SystemTime {
+    let at = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => at,
+        LocalResult::None => after_gap(tz, naive),
+    };
+    at.with_timezone(&Utc).into()
+}
+
+fn offset<Tz: TimeZone>(tz: &Tz, at: i64) -> i64 {
+    let at = DateTime::from_timestamp_nanos(at).naive_utc();
+    tz.offset_from_utc_datetime(&at).fix().local_minus_utc() as i64 * 1_000_000_000
+}
+
+fn transition<Tz: TimeZone>(tz: &Tz, at: i64) -> i64 {
+    let here = offset(tz, at);
+    [at - DAY, at + DAY]
+        .map(|near| (offset(tz, near) - here).abs())
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+}
+
+fn after_gap<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> DateTime<Tz> {
+    let before = tz
+        .offset_from_utc_datetime(&(naive - chrono::Duration::days(1)))
+        .fix();
+    tz.from_utc_datetime(&(naive - before))
+}
@@ -0,0 +1,101 @@
+use crate::{Budget, EnvError, Job, JobOptions, Sampling};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnError {
+    ZeroPeriod { name: String },
+    PeriodOutOfRange { name: String, period: Duration },
+    DuplicateName { name: String },
+    InvalidSampling { name: String, sampling: Sampling },
+    InvalidDutyCycle { name: String, duty_cycle: f64 },
+    InvalidBudget { name: String, budget: Budget },
+    RuntimeUnavailable { name: String },
+    InvalidEnv { name: String, error: EnvError },
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroPeriod { name } => write!(f, "job {name} has a zero period"),
+            Self::PeriodOutOfRange { name, period } => {
+                write!(
+                    f,
+                    "job {name} has a period too long to truncate to: {period:?}"
+                )
+            }
+            Self::DuplicateName { name } => write!(f, "a job named {name} is already spawned"),
+            Self::InvalidSampling { name, sampling } => {
+                write!(f, "job {name} has an invalid sampling: {sampling:?}")
+            }
+            Self::InvalidDutyCycle { name, duty_cycle } => {
+                write!(
+                    f,
+                    "job {name} has a duty cycle outside (0, 1]: {duty_cycle}"
+                )
+            }
+            Self::InvalidBudget { name, budget } => {
+                write!(
+                    f,
+                    "job {name} has a budget that never or always binds: {budget:?}"
+                )
+            }
+            Self::RuntimeUnavailable { name } => {
+                write!(f, "no tokio runtime is available to spawn job {name}")
+            }
+            Self::InvalidEnv { name, error } => {
+                write!(f, "job {name} has an invalid environment override: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+pub(crate) fn validate<J: Job>(job: &J, options: &JobOptions) -> Result<(), SpawnError> {
+    let name = || job.name().to_string();
+    let period = options.period.map_or_else(|| job.period(), Duration::from);
+    if period.is_zero() && job.schedule().is_none() {
+        return Err(SpawnError::ZeroPeriod { name: name() });
+    }
+    let truncate = options
+        .truncate_time
+        .unwrap_or_else(|| job.with_truncate_time());
+    if truncate && job.schedule().is_none() && i64::try_from(period.as_nanos()).is_err() {
+        return Err(SpawnError::PeriodOutOfRange {
+            name: name(),
+            period,
+        });
+    }
+    let sampling = options.sampling.unwrap_or_else(|| job.sampling());
+    let valid = match sampling {
+        Sampling::Always => true,
+        Sampling::EveryNth(n) => n > 0,
+        Sampling::Fraction(fraction) | Sampling::Random(fraction) => {
+            (0.0..=1.0).contains(&fraction)
+        }
+    };
+    if !valid {
+        return Err(SpawnError::InvalidSampling {
+            name: name(),
+            sampling,
+        });
+    }
+    if let Some(duty_cycle) = options.max_duty_cycle.or_else(|| job.max_duty_cycle())
+        && !(duty_cycle > 0.0 && duty_cycle <= 1.0)
+    {
+        return Err(SpawnError::InvalidDutyCycle {
+            name: name(),
+            duty_cycle,
+        });
+    }
+    if let Some(budget) = options.budget.or_else(|| job.budget())
+        && (budget.limit.is_zero() || budget.limit >= budget.window)
+    {
+        return Err(SpawnError::InvalidBudget {
+            name: name(),
+            budget,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::{Alert, AlertKind, Notifier};
+use serde_json::json;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime};
+
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(url, reqwest::Client::new())
+    }
+
+    pub fn with_client(url: impl Into<String>, client: reqwest::Client) -> Self {
+        Self {
+            url: url.into(),
+            client,
+        }
+    }
+
+    fn payload(alert: &Alert) -> serde_json::Value {
+        let timestamp = |at: SystemTime| {
+            at.duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        };
+        match &alert.kind {
+            AlertKind::Failures { count, error } => json!({
+                "job": alert.name,
+                "event": "failures",
+                "failures": count,
+                "error": error,
+                "timestamp": timestamp(alert.at),
+            }),
+            AlertKind::Stale { since } => json!({
+                "job": alert.name,
+                "event": "stale",
+                "last_success": timestamp(alert.at.checked_sub(*since).unwrap_or(alert.at)),
+                "timestamp": timestamp(alert.at),
+            }),
+            AlertKind::Quarantined { strikes } => json!({
+                "job": alert.name,
+                "event": "quarantined",
+                "strikes": strikes,
+                "timestamp": timestamp(alert.at),
+            }),
+        }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, alert: Alert) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            let result = self
+                .client
+                .post(&self.url)
+                .timeout(Duration::from_secs(10))
+                .json(&Self::payload(&alert))
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            #[cfg(feature = "tracing")]
+            if let Err(error) = result {
+                tracing::warn!(job = alert.name, %error, "failed to post webhook alert");
+            }
+            #[cfg(not(feature = "tracing"))]
+            let _ = result;
+        })
+    }
+}